@@ -0,0 +1,231 @@
+use std::error::Error;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use vending_machine::domain::entities::{Product, Receipt, Sale};
+use vending_machine::domain::views::{Availability, PublicProductView};
+
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: &str) -> Self {
+        Self {
+            status,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn unauthorized() -> Self {
+        Self::new(
+            StatusCode::UNAUTHORIZED,
+            "A valid session token is required",
+        )
+    }
+
+    pub fn forbidden() -> Self {
+        Self::new(
+            StatusCode::FORBIDDEN,
+            "This session is not allowed to perform this action",
+        )
+    }
+
+    pub fn conflict(message: &str) -> Self {
+        Self::new(StatusCode::CONFLICT, message)
+    }
+}
+
+impl From<Box<dyn Error>> for ApiError {
+    fn from(error: Box<dyn Error>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, &error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(ErrorResponse {
+                error: self.message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+#[derive(Serialize)]
+pub struct ProductResponse {
+    pub column_id: u32,
+    pub name: String,
+    pub category: String,
+    pub price: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refrigerated: Option<bool>,
+    pub available: bool,
+}
+
+impl From<Product> for ProductResponse {
+    fn from(product: Product) -> Self {
+        Self {
+            column_id: product.column_id.as_value(),
+            name: product.name.as_ref().to_string(),
+            category: product.category.as_ref().to_string(),
+            price: product.price.as_value(),
+            available: !product.quantity.is_empty(),
+            quantity: Some(product.quantity.as_value()),
+            refrigerated: Some(product.refrigerated),
+        }
+    }
+}
+
+impl From<PublicProductView> for ProductResponse {
+    fn from(product: PublicProductView) -> Self {
+        Self {
+            column_id: product.column_id.as_value(),
+            name: product.name.as_ref().to_string(),
+            category: product.category.as_ref().to_string(),
+            price: product.price.as_value(),
+            quantity: None,
+            refrigerated: None,
+            available: product.availability == Availability::Available,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub enum PaymentRequest {
+    Card { card_number: String },
+    Wallet { badge_id: String, pin: String },
+}
+
+#[derive(Deserialize)]
+pub struct PurchaseRequest {
+    pub column_id: u32,
+    pub quantity: u32,
+    pub payment: PaymentRequest,
+    #[serde(default)]
+    pub keep_change: bool,
+}
+
+#[derive(Serialize)]
+pub struct ReceiptItemResponse {
+    pub product_name: String,
+    pub quantity: u32,
+    pub unit_price: f32,
+    pub discount: f32,
+}
+
+#[derive(Serialize)]
+pub struct ReceiptResponse {
+    pub transaction_id: u32,
+    pub date: String,
+    pub items: Vec<ReceiptItemResponse>,
+    pub total: f32,
+    pub amount_paid: f32,
+    pub change: f32,
+    pub credit: f32,
+}
+
+impl From<Receipt> for ReceiptResponse {
+    fn from(receipt: Receipt) -> Self {
+        Self {
+            transaction_id: receipt.transaction_id.as_value(),
+            date: receipt.date.to_rfc3339(),
+            total: receipt.total(),
+            items: receipt
+                .items
+                .into_iter()
+                .map(|item| ReceiptItemResponse {
+                    product_name: item.product_name.as_ref().to_string(),
+                    quantity: item.quantity.as_value(),
+                    unit_price: item.unit_price.as_value(),
+                    discount: item.discount,
+                })
+                .collect(),
+            amount_paid: receipt.amount_paid,
+            change: receipt.change,
+            credit: receipt.credit,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Serialize)]
+pub struct SessionResponse {
+    pub token: String,
+    pub role: String,
+    pub lock_state: String,
+}
+
+#[derive(Deserialize)]
+pub struct LockRequest {
+    pub reason: String,
+}
+
+#[derive(Serialize)]
+pub struct MachineStateResponse {
+    pub lock_state: String,
+}
+
+#[derive(Serialize)]
+pub struct SaleResponse {
+    pub id: Option<u32>,
+    pub date: String,
+    pub column_id: u32,
+    pub product_name: String,
+    pub quantity: u32,
+    pub amount: f32,
+    pub payment_method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub void_of: Option<u32>,
+}
+
+impl From<Sale> for SaleResponse {
+    fn from(sale: Sale) -> Self {
+        Self {
+            id: sale.id.as_ref().map(|id| id.as_value()),
+            date: sale.date.to_rfc3339(),
+            column_id: sale.column_id.as_value(),
+            product_name: sale.product_name.as_ref().to_string(),
+            quantity: sale.quantity.as_value(),
+            amount: sale.amount(),
+            payment_method: sale.payment_method.as_ref().to_string(),
+            void_of: sale.reversal.map(|reversal| reversal.sale_id.as_value()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SalesReportResponse {
+    pub total: f32,
+    pub sales: Vec<SaleResponse>,
+}
+
+#[derive(Deserialize)]
+pub struct RestockRequest {
+    pub column_id: u32,
+    pub name: String,
+    pub category: String,
+    pub price: f32,
+    pub quantity: u32,
+    #[serde(default)]
+    pub refrigerated: bool,
+}
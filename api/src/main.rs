@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use infrastructure::card::SimulatedCardProcessor;
+use infrastructure::dispenser::SimulatedDispenser;
+use infrastructure::jobs;
+use infrastructure::receipts::JsonReceiptPrinter;
+use infrastructure::sqlite::{
+    DbConn, SqliteCashCollectionRepository, SqliteCreditRepository,
+    SqliteCustomerAccountRepository, SqliteFaultLogRepository, SqliteMachineHistoryRepository,
+    SqliteMachineStateRepository, SqlitePaymentJournalRepository, SqliteProductRepository,
+    SqliteSaleRepository, SqliteScheduleRepository, SqliteSensorReadingRepository,
+    SqliteStockMovementRepository, SqliteStorageDiagnostics, SqliteSupplierRepository,
+    SqliteWriteOffRepository, SqliteZReportRepository,
+};
+use vending_machine::application::states::{Guest, Unlocked};
+use vending_machine::application::VendingMachine;
+use vending_machine::domain::interfaces::{CardProcessor, CustomerAccountRepository};
+use yadir::core::contracts::DIBuilder;
+use yadir::core::primitives::{DIManager, DIObj, Lifetime};
+use yadir::{deps, let_deps};
+
+use crate::sessions::SessionRegistry;
+use crate::terminals::HttpPaymentTerminals;
+
+mod contracts;
+mod routes;
+mod sessions;
+mod terminals;
+
+const SESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone)]
+pub struct AppState {
+    vending_machine: VendingMachine<Guest, Unlocked>,
+    card_processor: Box<dyn CardProcessor>,
+    customer_account_repository: Box<dyn CustomerAccountRepository>,
+    sessions: SessionRegistry,
+}
+
+#[async_trait]
+impl DIBuilder for AppState {
+    type Input = deps!(
+        VendingMachine<Guest, Unlocked>,
+        Box<dyn CardProcessor>,
+        Box<dyn CustomerAccountRepository>
+    );
+    type Output = AppState;
+
+    async fn build(input: Self::Input) -> Self::Output {
+        let_deps!(vending_machine, card_processor, customer_account_repository <- input);
+
+        AppState {
+            vending_machine,
+            card_processor,
+            customer_account_repository,
+            sessions: SessionRegistry::new(Duration::from_secs(
+                env_or("SESSION_IDLE_MINUTES", 15) * 60,
+            )),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenvy::dotenv().ok();
+
+    let mut manager = DIManager::default();
+
+    manager
+        .register::<DbConn>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteProductRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteSaleRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteMachineStateRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteMachineHistoryRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteScheduleRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteSupplierRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteCashCollectionRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteStockMovementRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteZReportRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteCustomerAccountRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteCreditRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqlitePaymentJournalRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteSensorReadingRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteWriteOffRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteFaultLogRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteStorageDiagnostics>(Some(Lifetime::Singleton))
+        .await
+        .register::<SimulatedDispenser>(Some(Lifetime::Singleton))
+        .await
+        .register::<SimulatedCardProcessor>(Some(Lifetime::Singleton))
+        .await
+        .register::<HttpPaymentTerminals>(Some(Lifetime::Singleton))
+        .await
+        .register::<JsonReceiptPrinter>(Some(Lifetime::Singleton))
+        .await
+        .register::<VendingMachine<Guest, Unlocked>>(Some(Lifetime::Singleton))
+        .await
+        .register::<AppState>(Some(Lifetime::Singleton))
+        .await;
+
+    let state = manager.resolve::<AppState>().await.unwrap().extract();
+
+    jobs::start(state.vending_machine.clone()).await?;
+
+    let sessions = state.sessions.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SESSION_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let expired = sessions.expire_idle();
+            if expired > 0 {
+                println!("Expired {} idle session(s)", expired);
+            }
+        }
+    });
+
+    let address = std::env::var("API_ADDRESS").unwrap_or("127.0.0.1:3000".to_string());
+    let listener = tokio::net::TcpListener::bind(&address).await?;
+    println!("Listening on {}", address);
+
+    axum::serve(listener, routes::router(state)).await?;
+
+    Ok(())
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.parse::<T>().ok())
+        .unwrap_or(default)
+}
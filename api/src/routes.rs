@@ -0,0 +1,276 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+
+use vending_machine::domain::entities::{
+    CardNumber, Category, ChangePolicy, LockReason, Name, Password, Pin, Price, Product, Quantity,
+    Value,
+};
+use vending_machine::domain::interfaces::PaymentTerminal;
+
+use crate::contracts::{
+    ApiError, LockRequest, LoginRequest, MachineStateResponse, PaymentRequest, ProductResponse,
+    PurchaseRequest, ReceiptResponse, RestockRequest, SalesReportResponse, SessionResponse,
+};
+use crate::sessions::{Session, SessionGuard};
+use crate::terminals::{BadgeWalletTerminal, RemoteCardTerminal};
+use crate::AppState;
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/session",
+            get(current_session)
+                .post(open_session)
+                .delete(close_session),
+        )
+        .route("/products", get(list_products))
+        .route("/purchases", post(purchase))
+        .route("/login", post(login))
+        .route("/logout", post(logout))
+        .route("/reports/sales", get(sales_report))
+        .route("/machine/lock", post(lock))
+        .route("/machine/unlock", post(unlock))
+        .route("/restock", post(restock))
+        .with_state(state)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("authorization")?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+async fn checkout(state: &AppState, headers: &HeaderMap) -> Result<SessionGuard, ApiError> {
+    let token = bearer_token(headers).ok_or(ApiError::unauthorized())?;
+
+    state
+        .sessions
+        .checkout(token)
+        .await
+        .ok_or(ApiError::unauthorized())
+}
+
+/// Transitions run on a copy of the session so the slot keeps the previous
+/// one if the request future is dropped before the transition completes.
+fn snapshot(guard: &SessionGuard) -> Result<Session, ApiError> {
+    guard.as_ref().cloned().ok_or(ApiError::unauthorized())
+}
+
+fn session_response(token: &str, session: &Session) -> Json<SessionResponse> {
+    Json(SessionResponse {
+        token: token.to_string(),
+        role: session.role().to_string(),
+        lock_state: session.lock_state().to_string(),
+    })
+}
+
+fn register_session(state: &AppState, session: Session) -> Json<SessionResponse> {
+    let role = session.role();
+    let lock_state = session.lock_state();
+    let token = state.sessions.open(session);
+
+    Json(SessionResponse {
+        token,
+        role: role.to_string(),
+        lock_state: lock_state.to_string(),
+    })
+}
+
+async fn open_session(State(state): State<AppState>) -> Json<SessionResponse> {
+    let session = Session::guest(state.vending_machine.clone()).await;
+
+    register_session(&state, session)
+}
+
+async fn current_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<SessionResponse>, ApiError> {
+    let guard = checkout(&state, &headers).await?;
+    let session = guard.as_ref().ok_or(ApiError::unauthorized())?;
+
+    Ok(session_response(
+        bearer_token(&headers).unwrap_or_default(),
+        session,
+    ))
+}
+
+async fn close_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, ApiError> {
+    let token = bearer_token(&headers).ok_or(ApiError::unauthorized())?;
+
+    match state.sessions.close(token) {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiError::unauthorized()),
+    }
+}
+
+async fn list_products(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<ProductResponse>>, ApiError> {
+    if bearer_token(&headers).is_none() {
+        let session = Session::guest(state.vending_machine.clone()).await;
+
+        return Ok(Json(session.products().await));
+    }
+
+    let guard = checkout(&state, &headers).await?;
+    let session = guard.as_ref().ok_or(ApiError::unauthorized())?;
+
+    Ok(Json(session.products().await))
+}
+
+async fn purchase(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<PurchaseRequest>,
+) -> Result<Json<ReceiptResponse>, ApiError> {
+    let column_id = Value::parse(&request.column_id.to_string())?;
+    let quantity = Value::parse(&request.quantity.to_string())?;
+
+    let terminal: Box<dyn PaymentTerminal> = match request.payment {
+        PaymentRequest::Card { card_number } => Box::new(RemoteCardTerminal::new(
+            state.card_processor.clone(),
+            CardNumber::parse(&card_number)?,
+        )),
+        PaymentRequest::Wallet { badge_id, pin } => Box::new(BadgeWalletTerminal::new(
+            state.customer_account_repository.clone(),
+            Name::parse(&badge_id)?,
+            Pin::parse(&pin)?,
+        )),
+    };
+    let change_policy = match request.keep_change {
+        true => ChangePolicy::KeepAsCredit,
+        false => ChangePolicy::Refund,
+    };
+
+    let receipt = match bearer_token(&headers) {
+        Some(_) => {
+            let mut guard = checkout(&state, &headers).await?;
+            let session = guard.as_mut().ok_or(ApiError::unauthorized())?;
+
+            session
+                .buy(column_id, quantity, terminal, change_policy)
+                .await?
+        }
+        None => {
+            let mut session = Session::guest(state.vending_machine.clone()).await;
+
+            session
+                .buy(column_id, quantity, terminal, change_policy)
+                .await?
+        }
+    };
+
+    Ok(Json(ReceiptResponse::from(receipt)))
+}
+
+async fn login(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<SessionResponse>, ApiError> {
+    let username = Name::parse(&request.username)?;
+    let password = Password::parse(&request.password)?;
+
+    let Some(token) = bearer_token(&headers) else {
+        let session = Session::guest(state.vending_machine.clone()).await;
+        let (session, result) = session.login(&username, &password).await;
+        result?;
+
+        return Ok(register_session(&state, session));
+    };
+
+    let mut guard = checkout(&state, &headers).await?;
+    let session = snapshot(&guard)?;
+
+    let (session, result) = session.login(&username, &password).await;
+    let response = session_response(token, &session);
+    *guard = Some(session);
+
+    result.map(|_| response)
+}
+
+async fn logout(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<SessionResponse>, ApiError> {
+    let mut guard = checkout(&state, &headers).await?;
+    let session = snapshot(&guard)?;
+
+    let (session, result) = session.logout().await;
+    let response = session_response(bearer_token(&headers).unwrap_or_default(), &session);
+    *guard = Some(session);
+
+    result.map(|_| response)
+}
+
+async fn sales_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<SalesReportResponse>, ApiError> {
+    let guard = checkout(&state, &headers).await?;
+    let session = guard.as_ref().ok_or(ApiError::unauthorized())?;
+
+    Ok(Json(session.sales_report().await?))
+}
+
+async fn lock(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<LockRequest>,
+) -> Result<Json<MachineStateResponse>, ApiError> {
+    let reason = LockReason::parse(&request.reason)?;
+
+    let mut guard = checkout(&state, &headers).await?;
+    let session = snapshot(&guard)?;
+
+    let (session, result) = session.lock(reason).await;
+    let lock_state = session.lock_state().to_string();
+    *guard = Some(session);
+
+    result.map(|_| Json(MachineStateResponse { lock_state }))
+}
+
+async fn unlock(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<MachineStateResponse>, ApiError> {
+    let mut guard = checkout(&state, &headers).await?;
+    let session = snapshot(&guard)?;
+
+    let (session, result) = session.unlock().await;
+    let lock_state = session.lock_state().to_string();
+    *guard = Some(session);
+
+    result.map(|_| Json(MachineStateResponse { lock_state }))
+}
+
+async fn restock(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<RestockRequest>,
+) -> Result<Json<ProductResponse>, ApiError> {
+    let product = Product {
+        column_id: Value::parse(&request.column_id.to_string())?,
+        name: Name::parse(&request.name)?,
+        category: Category::parse(&request.category)?,
+        price: Price::parse_f32(request.price)?,
+        quantity: Quantity::parse(&request.quantity.to_string())?,
+        refrigerated: request.refrigerated,
+    };
+
+    let mut guard = checkout(&state, &headers).await?;
+    let session = guard.as_mut().ok_or(ApiError::unauthorized())?;
+
+    session.restock(product.clone()).await?;
+
+    Ok(Json(ProductResponse::from(product)))
+}
@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use rand::Rng;
+use tokio::sync::OwnedMutexGuard;
+
+use vending_machine::application::states::{
+    Admin, AuthResult, Guest, LockStatus, Locked, Maintenance, Resumed, Supplier, Technician,
+    Unlocked,
+};
+use vending_machine::application::{PaymentTerminals, VendingMachine};
+use vending_machine::domain::entities::{
+    ChangePolicy, LockReason, Name, Password, Product, Receipt, Value,
+};
+use vending_machine::domain::interfaces::PaymentTerminal;
+
+use crate::contracts::{ApiError, ProductResponse, SaleResponse, SalesReportResponse};
+
+#[derive(Clone)]
+pub enum Session {
+    GuestUnlocked(VendingMachine<Guest, Unlocked>),
+    GuestLocked(VendingMachine<Guest, Locked>),
+    GuestMaintenance(VendingMachine<Guest, Maintenance>),
+    AdminUnlocked(VendingMachine<Admin, Unlocked>),
+    AdminLocked(VendingMachine<Admin, Locked>),
+    AdminMaintenance(VendingMachine<Admin, Maintenance>),
+    SupplierUnlocked(VendingMachine<Supplier, Unlocked>),
+    SupplierLocked(VendingMachine<Supplier, Locked>),
+    SupplierMaintenance(VendingMachine<Supplier, Maintenance>),
+    TechnicianUnlocked(VendingMachine<Technician, Unlocked>),
+    TechnicianLocked(VendingMachine<Technician, Locked>),
+    TechnicianMaintenance(VendingMachine<Technician, Maintenance>),
+}
+
+async fn resume_guest<L: LockStatus>(vm: VendingMachine<Guest, L>) -> Session {
+    match vm.resume().await {
+        Resumed::Unlocked(vm) => Session::GuestUnlocked(vm),
+        Resumed::Locked(vm) => Session::GuestLocked(vm),
+        Resumed::Maintenance(vm) => Session::GuestMaintenance(vm),
+    }
+}
+
+async fn resume_admin<L: LockStatus>(vm: VendingMachine<Admin, L>) -> Session {
+    match vm.resume().await {
+        Resumed::Unlocked(vm) => Session::AdminUnlocked(vm),
+        Resumed::Locked(vm) => Session::AdminLocked(vm),
+        Resumed::Maintenance(vm) => Session::AdminMaintenance(vm),
+    }
+}
+
+async fn resume_supplier<L: LockStatus>(vm: VendingMachine<Supplier, L>) -> Session {
+    match vm.resume().await {
+        Resumed::Unlocked(vm) => Session::SupplierUnlocked(vm),
+        Resumed::Locked(vm) => Session::SupplierLocked(vm),
+        Resumed::Maintenance(vm) => Session::SupplierMaintenance(vm),
+    }
+}
+
+async fn resume_technician<L: LockStatus>(vm: VendingMachine<Technician, L>) -> Session {
+    match vm.resume().await {
+        Resumed::Unlocked(vm) => Session::TechnicianUnlocked(vm),
+        Resumed::Locked(vm) => Session::TechnicianLocked(vm),
+        Resumed::Maintenance(vm) => Session::TechnicianMaintenance(vm),
+    }
+}
+
+async fn sign_in<L: LockStatus>(
+    vm: VendingMachine<Guest, L>,
+    username: &Name,
+    password: &Password,
+) -> (Session, Result<(), ApiError>) {
+    match vm.login(username, password).await {
+        AuthResult::SuccessAdmin(vm) => (resume_admin(vm).await, Ok(())),
+        AuthResult::SuccessSupplier(vm) => (resume_supplier(vm).await, Ok(())),
+        AuthResult::SuccessTechnician(vm) => (resume_technician(vm).await, Ok(())),
+        AuthResult::Failure(vm) => (
+            resume_guest(vm).await,
+            Err(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "Invalid username or password",
+            )),
+        ),
+    }
+}
+
+impl Session {
+    pub async fn guest(vm: VendingMachine<Guest, Unlocked>) -> Session {
+        resume_guest(vm).await
+    }
+
+    pub async fn resume(self) -> Session {
+        match self {
+            Session::GuestUnlocked(vm) => resume_guest(vm).await,
+            Session::GuestLocked(vm) => resume_guest(vm).await,
+            Session::GuestMaintenance(vm) => resume_guest(vm).await,
+            Session::AdminUnlocked(vm) => resume_admin(vm).await,
+            Session::AdminLocked(vm) => resume_admin(vm).await,
+            Session::AdminMaintenance(vm) => resume_admin(vm).await,
+            Session::SupplierUnlocked(vm) => resume_supplier(vm).await,
+            Session::SupplierLocked(vm) => resume_supplier(vm).await,
+            Session::SupplierMaintenance(vm) => resume_supplier(vm).await,
+            Session::TechnicianUnlocked(vm) => resume_technician(vm).await,
+            Session::TechnicianLocked(vm) => resume_technician(vm).await,
+            Session::TechnicianMaintenance(vm) => resume_technician(vm).await,
+        }
+    }
+
+    pub fn role(&self) -> &'static str {
+        match self {
+            Session::GuestUnlocked(_) | Session::GuestLocked(_) | Session::GuestMaintenance(_) => {
+                "guest"
+            }
+            Session::AdminUnlocked(_) | Session::AdminLocked(_) | Session::AdminMaintenance(_) => {
+                "admin"
+            }
+            Session::SupplierUnlocked(_)
+            | Session::SupplierLocked(_)
+            | Session::SupplierMaintenance(_) => "supplier",
+            Session::TechnicianUnlocked(_)
+            | Session::TechnicianLocked(_)
+            | Session::TechnicianMaintenance(_) => "technician",
+        }
+    }
+
+    pub fn lock_state(&self) -> &'static str {
+        match self {
+            Session::GuestUnlocked(_)
+            | Session::AdminUnlocked(_)
+            | Session::SupplierUnlocked(_)
+            | Session::TechnicianUnlocked(_) => "unlocked",
+            Session::GuestLocked(_)
+            | Session::AdminLocked(_)
+            | Session::SupplierLocked(_)
+            | Session::TechnicianLocked(_) => "locked",
+            Session::GuestMaintenance(_)
+            | Session::AdminMaintenance(_)
+            | Session::SupplierMaintenance(_)
+            | Session::TechnicianMaintenance(_) => "maintenance",
+        }
+    }
+
+    pub async fn login(
+        self,
+        username: &Name,
+        password: &Password,
+    ) -> (Session, Result<(), ApiError>) {
+        match self {
+            Session::GuestUnlocked(vm) => sign_in(vm, username, password).await,
+            Session::GuestLocked(vm) => sign_in(vm, username, password).await,
+            Session::GuestMaintenance(vm) => sign_in(vm, username, password).await,
+            session => (
+                session,
+                Err(ApiError::conflict("This session is already logged in")),
+            ),
+        }
+    }
+
+    pub async fn logout(self) -> (Session, Result<(), ApiError>) {
+        match self {
+            Session::AdminUnlocked(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::AdminLocked(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::AdminMaintenance(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::SupplierUnlocked(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::SupplierLocked(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::SupplierMaintenance(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::TechnicianUnlocked(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::TechnicianLocked(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            Session::TechnicianMaintenance(vm) => (resume_guest(vm.logout()).await, Ok(())),
+            session => (session, Err(ApiError::unauthorized())),
+        }
+    }
+
+    pub async fn products(&self) -> Vec<ProductResponse> {
+        match self {
+            Session::GuestUnlocked(vm) => map_products(vm.look_up().await),
+            Session::GuestLocked(vm) => map_products(vm.look_up().await),
+            Session::GuestMaintenance(vm) => map_products(vm.look_up().await),
+            Session::AdminUnlocked(vm) => map_products(vm.look_up().await),
+            Session::AdminLocked(vm) => map_products(vm.look_up().await),
+            Session::AdminMaintenance(vm) => map_products(vm.look_up().await),
+            Session::SupplierUnlocked(vm) => map_products(vm.look_up().await),
+            Session::SupplierLocked(vm) => map_products(vm.look_up().await),
+            Session::SupplierMaintenance(vm) => map_products(vm.look_up().await),
+            Session::TechnicianUnlocked(vm) => map_products(vm.look_up().await),
+            Session::TechnicianLocked(vm) => map_products(vm.look_up().await),
+            Session::TechnicianMaintenance(vm) => map_products(vm.look_up().await),
+        }
+    }
+
+    pub async fn buy(
+        &mut self,
+        column_id: Value,
+        qty: Value,
+        terminal: Box<dyn PaymentTerminal>,
+        change_policy: ChangePolicy,
+    ) -> Result<Receipt, ApiError> {
+        let vm = match self {
+            Session::GuestUnlocked(vm) => vm,
+            Session::GuestLocked(_) | Session::GuestMaintenance(_) => {
+                return Err(ApiError::conflict("The machine is not accepting purchases"))
+            }
+            _ => return Err(ApiError::forbidden()),
+        };
+
+        let payment_method = terminal.method();
+        *vm = vm
+            .clone()
+            .with_payment_terminals(PaymentTerminals::default().register(terminal));
+
+        vm.buy(column_id, qty, payment_method, change_policy)
+            .await
+            .map_err(|e| e.to_string())
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, &e))
+    }
+
+    pub async fn sales_report(&self) -> Result<SalesReportResponse, ApiError> {
+        let sales = match self {
+            Session::AdminUnlocked(vm) => vm.list_sales_report().await,
+            Session::AdminLocked(vm) => vm.list_sales_report().await,
+            Session::AdminMaintenance(vm) => vm.list_sales_report().await,
+            _ => return Err(ApiError::forbidden()),
+        };
+
+        let sales: Vec<SaleResponse> = sales.into_iter().map(SaleResponse::from).collect();
+
+        Ok(SalesReportResponse {
+            total: sales.iter().fold(0.0, |total, sale| total + sale.amount),
+            sales,
+        })
+    }
+
+    pub async fn restock(&mut self, product: Product) -> Result<(), ApiError> {
+        let result = match self {
+            Session::SupplierUnlocked(vm) => vm.supply_own_product(product).await,
+            Session::SupplierMaintenance(vm) => vm.supply_own_product(product).await,
+            Session::SupplierLocked(_) => {
+                return Err(ApiError::conflict(
+                    "The machine must be unlocked or in maintenance to restock",
+                ))
+            }
+            _ => return Err(ApiError::forbidden()),
+        };
+
+        result.map_err(ApiError::from)
+    }
+
+    pub async fn lock(self, reason: LockReason) -> (Session, Result<(), ApiError>) {
+        match self {
+            Session::AdminUnlocked(vm) => match vm.clone().lock(reason, None).await {
+                Ok(vm) => (Session::AdminLocked(vm), Ok(())),
+                Err(e) => (Session::AdminUnlocked(vm), Err(ApiError::from(e))),
+            },
+            Session::AdminMaintenance(vm) => match vm.clone().lock(reason, None).await {
+                Ok(vm) => (Session::AdminLocked(vm), Ok(())),
+                Err(e) => (Session::AdminMaintenance(vm), Err(ApiError::from(e))),
+            },
+            Session::AdminLocked(vm) => (
+                Session::AdminLocked(vm),
+                Err(ApiError::conflict("The machine is already locked")),
+            ),
+            session => (session, Err(ApiError::forbidden())),
+        }
+    }
+
+    pub async fn unlock(self) -> (Session, Result<(), ApiError>) {
+        match self {
+            Session::AdminLocked(vm) => match vm.clone().unlock().await {
+                Ok(vm) => (Session::AdminUnlocked(vm), Ok(())),
+                Err(e) => (Session::AdminLocked(vm), Err(ApiError::from(e))),
+            },
+            Session::AdminMaintenance(vm) => match vm.clone().unlock().await {
+                Ok(vm) => (Session::AdminUnlocked(vm), Ok(())),
+                Err(e) => (Session::AdminMaintenance(vm), Err(ApiError::from(e))),
+            },
+            Session::AdminUnlocked(vm) => (
+                Session::AdminUnlocked(vm),
+                Err(ApiError::conflict("The machine is already unlocked")),
+            ),
+            session => (session, Err(ApiError::forbidden())),
+        }
+    }
+}
+
+fn map_products<T: Into<ProductResponse>>(products: Vec<T>) -> Vec<ProductResponse> {
+    products.into_iter().map(Into::into).collect()
+}
+
+pub type SessionSlot = Arc<tokio::sync::Mutex<Option<Session>>>;
+pub type SessionGuard = OwnedMutexGuard<Option<Session>>;
+
+struct SessionEntry {
+    slot: SessionSlot,
+    last_seen: Instant,
+}
+
+#[derive(Clone)]
+pub struct SessionRegistry {
+    sessions: Arc<Mutex<HashMap<String, SessionEntry>>>,
+    idle_timeout: Duration,
+}
+
+impl SessionRegistry {
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self {
+            sessions: Arc::default(),
+            idle_timeout,
+        }
+    }
+
+    pub fn open(&self, session: Session) -> String {
+        let token: String = (0..16)
+            .map(|_| format!("{:02x}", rand::thread_rng().gen::<u8>()))
+            .collect();
+
+        self.sessions.lock().unwrap().insert(
+            token.clone(),
+            SessionEntry {
+                slot: Arc::new(tokio::sync::Mutex::new(Some(session))),
+                last_seen: Instant::now(),
+            },
+        );
+
+        token
+    }
+
+    pub async fn checkout(&self, token: &str) -> Option<SessionGuard> {
+        let slot = {
+            let mut sessions = self.sessions.lock().unwrap();
+            let entry = sessions.get_mut(token)?;
+            entry.last_seen = Instant::now();
+            entry.slot.clone()
+        };
+
+        let mut guard = slot.lock_owned().await;
+        let session = guard.take()?;
+        *guard = Some(session.resume().await);
+
+        Some(guard)
+    }
+
+    pub fn close(&self, token: &str) -> bool {
+        self.sessions.lock().unwrap().remove(token).is_some()
+    }
+
+    pub fn expire_idle(&self) -> usize {
+        let mut sessions = self.sessions.lock().unwrap();
+        let before = sessions.len();
+        sessions.retain(|_, entry| entry.last_seen.elapsed() < self.idle_timeout);
+
+        before - sessions.len()
+    }
+}
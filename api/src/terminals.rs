@@ -0,0 +1,263 @@
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use yadir::core::contracts::DIBuilder;
+use yadir::deps;
+
+use vending_machine::application::PaymentTerminals;
+use vending_machine::domain::entities::{
+    CardAuthorization, CardNumber, Name, PaymentMethod, Pin, Price,
+};
+use vending_machine::domain::interfaces::{
+    CardProcessor, CustomerAccountRepository, PaymentTerminal, Terminal,
+};
+
+pub struct HttpPaymentTerminals;
+
+#[async_trait]
+impl DIBuilder for HttpPaymentTerminals {
+    type Input = deps!();
+    type Output = PaymentTerminals;
+
+    async fn build(_input: Self::Input) -> Self::Output {
+        PaymentTerminals::default()
+    }
+}
+
+#[derive(Clone)]
+pub struct RemoteCardTerminal {
+    card_processor: Box<dyn CardProcessor>,
+    card: CardNumber,
+    pending: Arc<Mutex<Option<CardAuthorization>>>,
+}
+
+impl RemoteCardTerminal {
+    pub fn new(card_processor: Box<dyn CardProcessor>, card: CardNumber) -> Self {
+        Self {
+            card_processor,
+            card,
+            pending: Arc::default(),
+        }
+    }
+
+    fn take_pending(&self) -> Result<Option<CardAuthorization>, Box<dyn Error>> {
+        Ok(self
+            .pending
+            .lock()
+            .map_err(|_| "Card terminal state is unavailable")?
+            .take())
+    }
+}
+
+impl Terminal for RemoteCardTerminal {
+    fn prompt(&self, _message: &str) {}
+}
+
+#[async_trait]
+impl PaymentTerminal for RemoteCardTerminal {
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Card
+    }
+
+    async fn is_available(&self) -> bool {
+        self.card_processor.is_online().await
+    }
+
+    async fn request(&self, due: Price) -> Result<Price, Box<dyn Error>> {
+        let authorization = self
+            .card_processor
+            .authorize(&self.card, due.clone())
+            .await?;
+
+        *self
+            .pending
+            .lock()
+            .map_err(|_| "Card terminal state is unavailable")? = Some(authorization);
+
+        Ok(due)
+    }
+
+    async fn refund(&self, _amount: Price) -> Result<(), Box<dyn Error>> {
+        Err(Box::from(
+            "Card payments are authorized for the exact amount due",
+        ))
+    }
+
+    fn reference(&self) -> Option<String> {
+        self.pending
+            .lock()
+            .ok()?
+            .as_ref()
+            .map(|authorization| authorization.id.clone())
+    }
+
+    async fn confirm(&self) -> Result<(), Box<dyn Error>> {
+        let Some(authorization) = self.take_pending()? else {
+            return Ok(());
+        };
+
+        self.card_processor.capture(&authorization).await
+    }
+
+    async fn cancel(&self) -> Result<Option<Price>, Box<dyn Error>> {
+        let Some(authorization) = self.take_pending()? else {
+            return Ok(None);
+        };
+
+        self.card_processor.void(&authorization).await?;
+
+        Ok(Some(authorization.amount))
+    }
+
+    async fn release(&self, amount: Price) -> Result<Option<Price>, Box<dyn Error>> {
+        {
+            let mut pending = self
+                .pending
+                .lock()
+                .map_err(|_| "Card terminal state is unavailable")?;
+            let Some(authorization) = pending.as_mut() else {
+                return Ok(None);
+            };
+
+            let remaining = authorization.amount.as_value() - amount.as_value();
+            if remaining > 0.005 {
+                authorization.amount = Price::parse_f32(remaining)?;
+                return Ok(Some(amount));
+            }
+        }
+
+        self.cancel().await
+    }
+}
+
+#[derive(Clone)]
+pub struct BadgeWalletTerminal {
+    customer_account_repository: Box<dyn CustomerAccountRepository>,
+    badge_id: Name,
+    pin: Pin,
+    pending: Arc<Mutex<Option<Price>>>,
+}
+
+impl BadgeWalletTerminal {
+    pub fn new(
+        customer_account_repository: Box<dyn CustomerAccountRepository>,
+        badge_id: Name,
+        pin: Pin,
+    ) -> Self {
+        Self {
+            customer_account_repository,
+            badge_id,
+            pin,
+            pending: Arc::default(),
+        }
+    }
+
+    fn take_pending(&self) -> Result<Option<Price>, Box<dyn Error>> {
+        Ok(self
+            .pending
+            .lock()
+            .map_err(|_| "Wallet terminal state is unavailable")?
+            .take())
+    }
+
+    async fn reverse_payment(&self, badge_id: &Name, amount: Price) -> Result<(), Box<dyn Error>> {
+        let mut repository = self.customer_account_repository.clone();
+        let mut account = repository
+            .find(badge_id)
+            .await
+            .ok_or("Customer account not found")?;
+
+        account.reverse_payment(amount);
+        repository.save(account).await
+    }
+}
+
+impl Terminal for BadgeWalletTerminal {
+    fn prompt(&self, _message: &str) {}
+}
+
+#[async_trait]
+impl PaymentTerminal for BadgeWalletTerminal {
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Wallet
+    }
+
+    async fn request(&self, due: Price) -> Result<Price, Box<dyn Error>> {
+        let mut repository = self.customer_account_repository.clone();
+        let mut account = repository
+            .find(&self.badge_id)
+            .await
+            .filter(|account| account.verify_pin(&self.pin))
+            .ok_or("Invalid badge id or PIN")?;
+
+        account.pay(due.clone())?;
+        repository.save(account).await?;
+        *self
+            .pending
+            .lock()
+            .map_err(|_| "Wallet terminal state is unavailable")? = Some(due.clone());
+
+        Ok(due)
+    }
+
+    async fn refund(&self, _amount: Price) -> Result<(), Box<dyn Error>> {
+        Err(Box::from(
+            "Wallet payments are debited for the exact amount due",
+        ))
+    }
+
+    fn reference(&self) -> Option<String> {
+        Some(self.badge_id.as_ref().to_string())
+    }
+
+    async fn reverse(&self, amount: Price, reference: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let badge_id = Name::parse(reference.ok_or("No wallet badge was recorded")?)?;
+
+        self.reverse_payment(&badge_id, amount).await
+    }
+
+    async fn confirm(&self) -> Result<(), Box<dyn Error>> {
+        self.take_pending()?;
+
+        Ok(())
+    }
+
+    async fn cancel(&self) -> Result<Option<Price>, Box<dyn Error>> {
+        let Some(amount) = self.take_pending()? else {
+            return Ok(None);
+        };
+
+        self.reverse_payment(&self.badge_id, amount.clone()).await?;
+
+        Ok(Some(amount))
+    }
+
+    async fn release(&self, amount: Price) -> Result<Option<Price>, Box<dyn Error>> {
+        let released = {
+            let mut pending = self
+                .pending
+                .lock()
+                .map_err(|_| "Wallet terminal state is unavailable")?;
+            let Some(debited) = pending.clone() else {
+                return Ok(None);
+            };
+
+            let remaining = debited.as_value() - amount.as_value();
+            match remaining > 0.005 {
+                true => {
+                    *pending = Some(Price::parse_f32(remaining)?);
+                    true
+                }
+                false => false,
+            }
+        };
+        if !released {
+            return self.cancel().await;
+        }
+
+        self.reverse_payment(&self.badge_id, amount.clone()).await?;
+
+        Ok(Some(amount))
+    }
+}
@@ -0,0 +1,60 @@
+use infrastructure::mdb::{open_device, MdbPeripheralSimulator};
+
+fn prompt_usage() {
+    println!("Commands: coin <type>, bill <type>, tubes, dispensed, quit");
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenvy::dotenv().ok();
+
+    let path = std::env::args()
+        .nth(1)
+        .or(std::env::var("MDB_DEVICE").ok())
+        .ok_or("Usage: mdb-simulator <device>")?;
+
+    let simulator = MdbPeripheralSimulator::default();
+    let stream = open_device(&path).await?;
+
+    let peripheral = simulator.clone();
+    tokio::spawn(async move {
+        if let Err(e) = peripheral.serve(stream).await {
+            eprintln!("MDB simulator error: {}", e);
+        }
+    });
+
+    println!("Simulating a coin changer and a bill validator on {}", path);
+    prompt_usage();
+
+    loop {
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let mut words = line.split_whitespace();
+        let result = match (words.next(), words.next().map(str::parse::<u8>)) {
+            (Some("coin"), Some(Ok(coin_type))) => simulator.insert_coin(coin_type),
+            (Some("bill"), Some(Ok(bill_type))) => simulator.insert_bill(bill_type),
+            (Some("tubes"), None) => {
+                println!("Tube counts: {:?}", simulator.tube_counts());
+                Ok(())
+            }
+            (Some("dispensed"), None) => {
+                for (coin_type, count) in simulator.dispensed() {
+                    println!("Coin type {}: {}", coin_type, count);
+                }
+                Ok(())
+            }
+            (Some("quit"), None) => return Ok(()),
+            _ => {
+                prompt_usage();
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("Error: {}", e);
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::terminals::CliTerminal;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use vending_machine::application::states::{Admin, Guest, Locked, Supplier, Unlocked};
+use vending_machine::application::states::{
+    Admin, Guest, Locked, Maintenance, Supplier, Technician, Unlocked,
+};
 
 pub enum PromptPerspective {
     GuestUnlocked(CliTerminal<Guest, Unlocked>),
@@ -10,6 +12,12 @@ pub enum PromptPerspective {
     AdminLocked(CliTerminal<Admin, Locked>),
     SupplierUnlocked(CliTerminal<Supplier, Unlocked>),
     SupplierLocked(CliTerminal<Supplier, Locked>),
+    GuestMaintenance(CliTerminal<Guest, Maintenance>),
+    AdminMaintenance(CliTerminal<Admin, Maintenance>),
+    SupplierMaintenance(CliTerminal<Supplier, Maintenance>),
+    TechnicianUnlocked(CliTerminal<Technician, Unlocked>),
+    TechnicianLocked(CliTerminal<Technician, Locked>),
+    TechnicianMaintenance(CliTerminal<Technician, Maintenance>),
 }
 
 impl PromptPerspective {
@@ -21,6 +29,12 @@ impl PromptPerspective {
             PromptPerspective::AdminLocked(terminal) => terminal.run().await,
             PromptPerspective::SupplierUnlocked(terminal) => terminal.run().await,
             PromptPerspective::SupplierLocked(terminal) => terminal.run().await,
+            PromptPerspective::GuestMaintenance(terminal) => terminal.run().await,
+            PromptPerspective::AdminMaintenance(terminal) => terminal.run().await,
+            PromptPerspective::SupplierMaintenance(terminal) => terminal.run().await,
+            PromptPerspective::TechnicianUnlocked(terminal) => terminal.run().await,
+            PromptPerspective::TechnicianLocked(terminal) => terminal.run().await,
+            PromptPerspective::TechnicianMaintenance(terminal) => terminal.run().await,
         }
     }
 }
@@ -28,7 +42,10 @@ impl PromptPerspective {
 pub(crate) enum GuestUnlockedCommand {
     Login,
     ListProducts,
+    SearchProducts,
     BuyProduct,
+    ReprintReceipt,
+    ReturnCredit,
     Exit,
 }
 
@@ -37,8 +54,11 @@ impl Display for GuestUnlockedCommand {
         match self {
             GuestUnlockedCommand::Login => write!(f, "1. Login"),
             GuestUnlockedCommand::ListProducts => write!(f, "2. List Products"),
-            GuestUnlockedCommand::BuyProduct => write!(f, "3. Buy Product"),
-            GuestUnlockedCommand::Exit => write!(f, "4. Exit"),
+            GuestUnlockedCommand::SearchProducts => write!(f, "3. Search Products"),
+            GuestUnlockedCommand::BuyProduct => write!(f, "4. Buy Product"),
+            GuestUnlockedCommand::ReprintReceipt => write!(f, "5. Reprint Receipt"),
+            GuestUnlockedCommand::ReturnCredit => write!(f, "6. Return Credit"),
+            GuestUnlockedCommand::Exit => write!(f, "7. Exit"),
         }
     }
 }
@@ -50,8 +70,11 @@ impl TryFrom<&str> for GuestUnlockedCommand {
         match value {
             "1" => Ok(GuestUnlockedCommand::Login),
             "2" => Ok(GuestUnlockedCommand::ListProducts),
-            "3" => Ok(GuestUnlockedCommand::BuyProduct),
-            "4" => Ok(GuestUnlockedCommand::Exit),
+            "3" => Ok(GuestUnlockedCommand::SearchProducts),
+            "4" => Ok(GuestUnlockedCommand::BuyProduct),
+            "5" => Ok(GuestUnlockedCommand::ReprintReceipt),
+            "6" => Ok(GuestUnlockedCommand::ReturnCredit),
+            "7" => Ok(GuestUnlockedCommand::Exit),
             _ => Err(Box::from("Invalid command")),
         }
     }
@@ -60,6 +83,9 @@ impl TryFrom<&str> for GuestUnlockedCommand {
 pub(crate) enum GuestLockedCommand {
     Login,
     ListProducts,
+    SearchProducts,
+    ReprintReceipt,
+    ReturnCredit,
     Exit,
 }
 
@@ -68,7 +94,10 @@ impl Display for GuestLockedCommand {
         match self {
             GuestLockedCommand::Login => write!(f, "1. Login"),
             GuestLockedCommand::ListProducts => write!(f, "2. List Products"),
-            GuestLockedCommand::Exit => write!(f, "3. Exit"),
+            GuestLockedCommand::SearchProducts => write!(f, "3. Search Products"),
+            GuestLockedCommand::ReprintReceipt => write!(f, "4. Reprint Receipt"),
+            GuestLockedCommand::ReturnCredit => write!(f, "5. Return Credit"),
+            GuestLockedCommand::Exit => write!(f, "6. Exit"),
         }
     }
 }
@@ -80,7 +109,48 @@ impl TryFrom<&str> for GuestLockedCommand {
         match value {
             "1" => Ok(GuestLockedCommand::Login),
             "2" => Ok(GuestLockedCommand::ListProducts),
-            "3" => Ok(GuestLockedCommand::Exit),
+            "3" => Ok(GuestLockedCommand::SearchProducts),
+            "4" => Ok(GuestLockedCommand::ReprintReceipt),
+            "5" => Ok(GuestLockedCommand::ReturnCredit),
+            "6" => Ok(GuestLockedCommand::Exit),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum GuestMaintenanceCommand {
+    Login,
+    ListProducts,
+    SearchProducts,
+    ReprintReceipt,
+    ReturnCredit,
+    Exit,
+}
+
+impl Display for GuestMaintenanceCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuestMaintenanceCommand::Login => write!(f, "1. Login"),
+            GuestMaintenanceCommand::ListProducts => write!(f, "2. List Products"),
+            GuestMaintenanceCommand::SearchProducts => write!(f, "3. Search Products"),
+            GuestMaintenanceCommand::ReprintReceipt => write!(f, "4. Reprint Receipt"),
+            GuestMaintenanceCommand::ReturnCredit => write!(f, "5. Return Credit"),
+            GuestMaintenanceCommand::Exit => write!(f, "6. Exit"),
+        }
+    }
+}
+
+impl TryFrom<&str> for GuestMaintenanceCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(GuestMaintenanceCommand::Login),
+            "2" => Ok(GuestMaintenanceCommand::ListProducts),
+            "3" => Ok(GuestMaintenanceCommand::SearchProducts),
+            "4" => Ok(GuestMaintenanceCommand::ReprintReceipt),
+            "5" => Ok(GuestMaintenanceCommand::ReturnCredit),
+            "6" => Ok(GuestMaintenanceCommand::Exit),
             _ => Err(Box::from("Invalid command")),
         }
     }
@@ -90,7 +160,24 @@ pub(crate) enum AdminUnlockedCommand {
     Logout,
     ListProducts,
     ListSales,
+    VoidSale,
+    RecoveredPayments,
+    ViewHistory,
+    ViewSchedule,
+    EditSchedule,
+    ManageSuppliers,
+    ManageAccounts,
+    SensorReadings,
+    WriteOffs,
+    Faults,
+    RunDiagnostics,
+    CollectCash,
+    ReconciliationReport,
+    CloseDay,
+    ListZReports,
+    ExportZReport,
     Lock,
+    EnterMaintenance,
     Exit,
 }
 
@@ -100,8 +187,25 @@ impl Display for AdminUnlockedCommand {
             AdminUnlockedCommand::Logout => write!(f, "1. Logout"),
             AdminUnlockedCommand::ListProducts => write!(f, "2. List Products"),
             AdminUnlockedCommand::ListSales => write!(f, "3. List Sales"),
-            AdminUnlockedCommand::Lock => write!(f, "4. Lock"),
-            AdminUnlockedCommand::Exit => write!(f, "5. Exit"),
+            AdminUnlockedCommand::VoidSale => write!(f, "4. Void Sale"),
+            AdminUnlockedCommand::RecoveredPayments => write!(f, "5. Recovered Payments"),
+            AdminUnlockedCommand::ViewHistory => write!(f, "6. View History"),
+            AdminUnlockedCommand::ViewSchedule => write!(f, "7. View Schedule"),
+            AdminUnlockedCommand::EditSchedule => write!(f, "8. Edit Schedule"),
+            AdminUnlockedCommand::ManageSuppliers => write!(f, "9. Manage Suppliers"),
+            AdminUnlockedCommand::ManageAccounts => write!(f, "10. Manage Customer Accounts"),
+            AdminUnlockedCommand::SensorReadings => write!(f, "11. Sensor Readings"),
+            AdminUnlockedCommand::WriteOffs => write!(f, "12. Write-offs"),
+            AdminUnlockedCommand::Faults => write!(f, "13. Faults"),
+            AdminUnlockedCommand::RunDiagnostics => write!(f, "14. Run Diagnostics"),
+            AdminUnlockedCommand::CollectCash => write!(f, "15. Collect Cash"),
+            AdminUnlockedCommand::ReconciliationReport => write!(f, "16. Reconciliation Report"),
+            AdminUnlockedCommand::CloseDay => write!(f, "17. Close Day"),
+            AdminUnlockedCommand::ListZReports => write!(f, "18. List Z-Reports"),
+            AdminUnlockedCommand::ExportZReport => write!(f, "19. Export Z-Report"),
+            AdminUnlockedCommand::Lock => write!(f, "20. Lock"),
+            AdminUnlockedCommand::EnterMaintenance => write!(f, "21. Enter Maintenance"),
+            AdminUnlockedCommand::Exit => write!(f, "22. Exit"),
         }
     }
 }
@@ -114,8 +218,25 @@ impl TryFrom<&str> for AdminUnlockedCommand {
             "1" => Ok(AdminUnlockedCommand::Logout),
             "2" => Ok(AdminUnlockedCommand::ListProducts),
             "3" => Ok(AdminUnlockedCommand::ListSales),
-            "4" => Ok(AdminUnlockedCommand::Lock),
-            "5" => Ok(AdminUnlockedCommand::Exit),
+            "4" => Ok(AdminUnlockedCommand::VoidSale),
+            "5" => Ok(AdminUnlockedCommand::RecoveredPayments),
+            "6" => Ok(AdminUnlockedCommand::ViewHistory),
+            "7" => Ok(AdminUnlockedCommand::ViewSchedule),
+            "8" => Ok(AdminUnlockedCommand::EditSchedule),
+            "9" => Ok(AdminUnlockedCommand::ManageSuppliers),
+            "10" => Ok(AdminUnlockedCommand::ManageAccounts),
+            "11" => Ok(AdminUnlockedCommand::SensorReadings),
+            "12" => Ok(AdminUnlockedCommand::WriteOffs),
+            "13" => Ok(AdminUnlockedCommand::Faults),
+            "14" => Ok(AdminUnlockedCommand::RunDiagnostics),
+            "15" => Ok(AdminUnlockedCommand::CollectCash),
+            "16" => Ok(AdminUnlockedCommand::ReconciliationReport),
+            "17" => Ok(AdminUnlockedCommand::CloseDay),
+            "18" => Ok(AdminUnlockedCommand::ListZReports),
+            "19" => Ok(AdminUnlockedCommand::ExportZReport),
+            "20" => Ok(AdminUnlockedCommand::Lock),
+            "21" => Ok(AdminUnlockedCommand::EnterMaintenance),
+            "22" => Ok(AdminUnlockedCommand::Exit),
             _ => Err(Box::from("Invalid command")),
         }
     }
@@ -125,7 +246,24 @@ pub(crate) enum AdminLockedCommand {
     Logout,
     ListProducts,
     ListSales,
+    VoidSale,
+    RecoveredPayments,
+    ViewHistory,
+    ViewSchedule,
+    EditSchedule,
+    ManageSuppliers,
+    ManageAccounts,
+    SensorReadings,
+    WriteOffs,
+    Faults,
+    RunDiagnostics,
+    CollectCash,
+    ReconciliationReport,
+    CloseDay,
+    ListZReports,
+    ExportZReport,
     Unlock,
+    EnterMaintenance,
     Exit,
 }
 
@@ -135,8 +273,25 @@ impl Display for AdminLockedCommand {
             AdminLockedCommand::Logout => write!(f, "1. Logout"),
             AdminLockedCommand::ListProducts => write!(f, "2. List Products"),
             AdminLockedCommand::ListSales => write!(f, "3. List Sales"),
-            AdminLockedCommand::Unlock => write!(f, "4. Unlock"),
-            AdminLockedCommand::Exit => write!(f, "5. Exit"),
+            AdminLockedCommand::VoidSale => write!(f, "4. Void Sale"),
+            AdminLockedCommand::RecoveredPayments => write!(f, "5. Recovered Payments"),
+            AdminLockedCommand::ViewHistory => write!(f, "6. View History"),
+            AdminLockedCommand::ViewSchedule => write!(f, "7. View Schedule"),
+            AdminLockedCommand::EditSchedule => write!(f, "8. Edit Schedule"),
+            AdminLockedCommand::ManageSuppliers => write!(f, "9. Manage Suppliers"),
+            AdminLockedCommand::ManageAccounts => write!(f, "10. Manage Customer Accounts"),
+            AdminLockedCommand::SensorReadings => write!(f, "11. Sensor Readings"),
+            AdminLockedCommand::WriteOffs => write!(f, "12. Write-offs"),
+            AdminLockedCommand::Faults => write!(f, "13. Faults"),
+            AdminLockedCommand::RunDiagnostics => write!(f, "14. Run Diagnostics"),
+            AdminLockedCommand::CollectCash => write!(f, "15. Collect Cash"),
+            AdminLockedCommand::ReconciliationReport => write!(f, "16. Reconciliation Report"),
+            AdminLockedCommand::CloseDay => write!(f, "17. Close Day"),
+            AdminLockedCommand::ListZReports => write!(f, "18. List Z-Reports"),
+            AdminLockedCommand::ExportZReport => write!(f, "19. Export Z-Report"),
+            AdminLockedCommand::Unlock => write!(f, "20. Unlock"),
+            AdminLockedCommand::EnterMaintenance => write!(f, "21. Enter Maintenance"),
+            AdminLockedCommand::Exit => write!(f, "22. Exit"),
         }
     }
 }
@@ -149,8 +304,111 @@ impl TryFrom<&str> for AdminLockedCommand {
             "1" => Ok(AdminLockedCommand::Logout),
             "2" => Ok(AdminLockedCommand::ListProducts),
             "3" => Ok(AdminLockedCommand::ListSales),
-            "4" => Ok(AdminLockedCommand::Unlock),
-            "5" => Ok(AdminLockedCommand::Exit),
+            "4" => Ok(AdminLockedCommand::VoidSale),
+            "5" => Ok(AdminLockedCommand::RecoveredPayments),
+            "6" => Ok(AdminLockedCommand::ViewHistory),
+            "7" => Ok(AdminLockedCommand::ViewSchedule),
+            "8" => Ok(AdminLockedCommand::EditSchedule),
+            "9" => Ok(AdminLockedCommand::ManageSuppliers),
+            "10" => Ok(AdminLockedCommand::ManageAccounts),
+            "11" => Ok(AdminLockedCommand::SensorReadings),
+            "12" => Ok(AdminLockedCommand::WriteOffs),
+            "13" => Ok(AdminLockedCommand::Faults),
+            "14" => Ok(AdminLockedCommand::RunDiagnostics),
+            "15" => Ok(AdminLockedCommand::CollectCash),
+            "16" => Ok(AdminLockedCommand::ReconciliationReport),
+            "17" => Ok(AdminLockedCommand::CloseDay),
+            "18" => Ok(AdminLockedCommand::ListZReports),
+            "19" => Ok(AdminLockedCommand::ExportZReport),
+            "20" => Ok(AdminLockedCommand::Unlock),
+            "21" => Ok(AdminLockedCommand::EnterMaintenance),
+            "22" => Ok(AdminLockedCommand::Exit),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum AdminMaintenanceCommand {
+    Logout,
+    ListProducts,
+    ListSales,
+    VoidSale,
+    RecoveredPayments,
+    ViewHistory,
+    ViewSchedule,
+    EditSchedule,
+    ManageSuppliers,
+    ManageAccounts,
+    SensorReadings,
+    WriteOffs,
+    Faults,
+    RunDiagnostics,
+    CollectCash,
+    ReconciliationReport,
+    CloseDay,
+    ListZReports,
+    ExportZReport,
+    Unlock,
+    Lock,
+    Exit,
+}
+
+impl Display for AdminMaintenanceCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdminMaintenanceCommand::Logout => write!(f, "1. Logout"),
+            AdminMaintenanceCommand::ListProducts => write!(f, "2. List Products"),
+            AdminMaintenanceCommand::ListSales => write!(f, "3. List Sales"),
+            AdminMaintenanceCommand::VoidSale => write!(f, "4. Void Sale"),
+            AdminMaintenanceCommand::RecoveredPayments => write!(f, "5. Recovered Payments"),
+            AdminMaintenanceCommand::ViewHistory => write!(f, "6. View History"),
+            AdminMaintenanceCommand::ViewSchedule => write!(f, "7. View Schedule"),
+            AdminMaintenanceCommand::EditSchedule => write!(f, "8. Edit Schedule"),
+            AdminMaintenanceCommand::ManageSuppliers => write!(f, "9. Manage Suppliers"),
+            AdminMaintenanceCommand::ManageAccounts => write!(f, "10. Manage Customer Accounts"),
+            AdminMaintenanceCommand::SensorReadings => write!(f, "11. Sensor Readings"),
+            AdminMaintenanceCommand::WriteOffs => write!(f, "12. Write-offs"),
+            AdminMaintenanceCommand::Faults => write!(f, "13. Faults"),
+            AdminMaintenanceCommand::RunDiagnostics => write!(f, "14. Run Diagnostics"),
+            AdminMaintenanceCommand::CollectCash => write!(f, "15. Collect Cash"),
+            AdminMaintenanceCommand::ReconciliationReport => write!(f, "16. Reconciliation Report"),
+            AdminMaintenanceCommand::CloseDay => write!(f, "17. Close Day"),
+            AdminMaintenanceCommand::ListZReports => write!(f, "18. List Z-Reports"),
+            AdminMaintenanceCommand::ExportZReport => write!(f, "19. Export Z-Report"),
+            AdminMaintenanceCommand::Unlock => write!(f, "20. Unlock"),
+            AdminMaintenanceCommand::Lock => write!(f, "21. Lock"),
+            AdminMaintenanceCommand::Exit => write!(f, "22. Exit"),
+        }
+    }
+}
+
+impl TryFrom<&str> for AdminMaintenanceCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(AdminMaintenanceCommand::Logout),
+            "2" => Ok(AdminMaintenanceCommand::ListProducts),
+            "3" => Ok(AdminMaintenanceCommand::ListSales),
+            "4" => Ok(AdminMaintenanceCommand::VoidSale),
+            "5" => Ok(AdminMaintenanceCommand::RecoveredPayments),
+            "6" => Ok(AdminMaintenanceCommand::ViewHistory),
+            "7" => Ok(AdminMaintenanceCommand::ViewSchedule),
+            "8" => Ok(AdminMaintenanceCommand::EditSchedule),
+            "9" => Ok(AdminMaintenanceCommand::ManageSuppliers),
+            "10" => Ok(AdminMaintenanceCommand::ManageAccounts),
+            "11" => Ok(AdminMaintenanceCommand::SensorReadings),
+            "12" => Ok(AdminMaintenanceCommand::WriteOffs),
+            "13" => Ok(AdminMaintenanceCommand::Faults),
+            "14" => Ok(AdminMaintenanceCommand::RunDiagnostics),
+            "15" => Ok(AdminMaintenanceCommand::CollectCash),
+            "16" => Ok(AdminMaintenanceCommand::ReconciliationReport),
+            "17" => Ok(AdminMaintenanceCommand::CloseDay),
+            "18" => Ok(AdminMaintenanceCommand::ListZReports),
+            "19" => Ok(AdminMaintenanceCommand::ExportZReport),
+            "20" => Ok(AdminMaintenanceCommand::Unlock),
+            "21" => Ok(AdminMaintenanceCommand::Lock),
+            "22" => Ok(AdminMaintenanceCommand::Exit),
             _ => Err(Box::from("Invalid command")),
         }
     }
@@ -159,6 +417,7 @@ impl TryFrom<&str> for AdminLockedCommand {
 pub(crate) enum SupplierUnlockedCommand {
     Logout,
     ListProducts,
+    ViewReport,
     SupplyProduct,
     Exit,
 }
@@ -168,8 +427,9 @@ impl Display for SupplierUnlockedCommand {
         match self {
             SupplierUnlockedCommand::Logout => write!(f, "1. Logout"),
             SupplierUnlockedCommand::ListProducts => write!(f, "2. List Products"),
-            SupplierUnlockedCommand::SupplyProduct => write!(f, "3. Supply Product"),
-            SupplierUnlockedCommand::Exit => write!(f, "4. Exit"),
+            SupplierUnlockedCommand::ViewReport => write!(f, "3. View Report"),
+            SupplierUnlockedCommand::SupplyProduct => write!(f, "4. Supply Product"),
+            SupplierUnlockedCommand::Exit => write!(f, "5. Exit"),
         }
     }
 }
@@ -181,8 +441,9 @@ impl TryFrom<&str> for SupplierUnlockedCommand {
         match value {
             "1" => Ok(SupplierUnlockedCommand::Logout),
             "2" => Ok(SupplierUnlockedCommand::ListProducts),
-            "3" => Ok(SupplierUnlockedCommand::SupplyProduct),
-            "4" => Ok(SupplierUnlockedCommand::Exit),
+            "3" => Ok(SupplierUnlockedCommand::ViewReport),
+            "4" => Ok(SupplierUnlockedCommand::SupplyProduct),
+            "5" => Ok(SupplierUnlockedCommand::Exit),
             _ => Err(Box::from("Invalid command")),
         }
     }
@@ -191,6 +452,7 @@ impl TryFrom<&str> for SupplierUnlockedCommand {
 pub(crate) enum SupplierLockedCommand {
     Logout,
     ListProducts,
+    ViewReport,
     Exit,
 }
 
@@ -199,7 +461,8 @@ impl Display for SupplierLockedCommand {
         match self {
             SupplierLockedCommand::Logout => write!(f, "1. Logout"),
             SupplierLockedCommand::ListProducts => write!(f, "2. List Products"),
-            SupplierLockedCommand::Exit => write!(f, "3. Exit"),
+            SupplierLockedCommand::ViewReport => write!(f, "3. View Report"),
+            SupplierLockedCommand::Exit => write!(f, "4. Exit"),
         }
     }
 }
@@ -211,7 +474,326 @@ impl TryFrom<&str> for SupplierLockedCommand {
         match value {
             "1" => Ok(SupplierLockedCommand::Logout),
             "2" => Ok(SupplierLockedCommand::ListProducts),
-            "3" => Ok(SupplierLockedCommand::Exit),
+            "3" => Ok(SupplierLockedCommand::ViewReport),
+            "4" => Ok(SupplierLockedCommand::Exit),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum SupplierMaintenanceCommand {
+    Logout,
+    ListProducts,
+    ViewReport,
+    SupplyProduct,
+    AdjustStock,
+    Exit,
+}
+
+impl Display for SupplierMaintenanceCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SupplierMaintenanceCommand::Logout => write!(f, "1. Logout"),
+            SupplierMaintenanceCommand::ListProducts => write!(f, "2. List Products"),
+            SupplierMaintenanceCommand::ViewReport => write!(f, "3. View Report"),
+            SupplierMaintenanceCommand::SupplyProduct => write!(f, "4. Supply Product"),
+            SupplierMaintenanceCommand::AdjustStock => write!(f, "5. Adjust Stock"),
+            SupplierMaintenanceCommand::Exit => write!(f, "6. Exit"),
+        }
+    }
+}
+
+impl TryFrom<&str> for SupplierMaintenanceCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(SupplierMaintenanceCommand::Logout),
+            "2" => Ok(SupplierMaintenanceCommand::ListProducts),
+            "3" => Ok(SupplierMaintenanceCommand::ViewReport),
+            "4" => Ok(SupplierMaintenanceCommand::SupplyProduct),
+            "5" => Ok(SupplierMaintenanceCommand::AdjustStock),
+            "6" => Ok(SupplierMaintenanceCommand::Exit),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum ScheduleEditCommand {
+    AddWindow,
+    RemoveWindow,
+    AddException,
+    RemoveException,
+}
+
+impl Display for ScheduleEditCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduleEditCommand::AddWindow => write!(f, "1. Add Opening Window"),
+            ScheduleEditCommand::RemoveWindow => write!(f, "2. Remove Opening Window"),
+            ScheduleEditCommand::AddException => write!(f, "3. Add Exception Date"),
+            ScheduleEditCommand::RemoveException => write!(f, "4. Remove Exception Date"),
+        }
+    }
+}
+
+impl TryFrom<&str> for ScheduleEditCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(ScheduleEditCommand::AddWindow),
+            "2" => Ok(ScheduleEditCommand::RemoveWindow),
+            "3" => Ok(ScheduleEditCommand::AddException),
+            "4" => Ok(ScheduleEditCommand::RemoveException),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum TechnicianUnlockedCommand {
+    Logout,
+    ListProducts,
+    Faults,
+    RunDiagnostics,
+    TakeOutOfService,
+    Exit,
+}
+
+impl Display for TechnicianUnlockedCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TechnicianUnlockedCommand::Logout => write!(f, "1. Logout"),
+            TechnicianUnlockedCommand::ListProducts => write!(f, "2. List Products"),
+            TechnicianUnlockedCommand::Faults => write!(f, "3. Faults"),
+            TechnicianUnlockedCommand::RunDiagnostics => write!(f, "4. Run Diagnostics"),
+            TechnicianUnlockedCommand::TakeOutOfService => write!(f, "5. Take Out Of Service"),
+            TechnicianUnlockedCommand::Exit => write!(f, "6. Exit"),
+        }
+    }
+}
+
+impl TryFrom<&str> for TechnicianUnlockedCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(TechnicianUnlockedCommand::Logout),
+            "2" => Ok(TechnicianUnlockedCommand::ListProducts),
+            "3" => Ok(TechnicianUnlockedCommand::Faults),
+            "4" => Ok(TechnicianUnlockedCommand::RunDiagnostics),
+            "5" => Ok(TechnicianUnlockedCommand::TakeOutOfService),
+            "6" => Ok(TechnicianUnlockedCommand::Exit),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum TechnicianLockedCommand {
+    Logout,
+    ListProducts,
+    Faults,
+    RunDiagnostics,
+    Exit,
+}
+
+impl Display for TechnicianLockedCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TechnicianLockedCommand::Logout => write!(f, "1. Logout"),
+            TechnicianLockedCommand::ListProducts => write!(f, "2. List Products"),
+            TechnicianLockedCommand::Faults => write!(f, "3. Faults"),
+            TechnicianLockedCommand::RunDiagnostics => write!(f, "4. Run Diagnostics"),
+            TechnicianLockedCommand::Exit => write!(f, "5. Exit"),
+        }
+    }
+}
+
+impl TryFrom<&str> for TechnicianLockedCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(TechnicianLockedCommand::Logout),
+            "2" => Ok(TechnicianLockedCommand::ListProducts),
+            "3" => Ok(TechnicianLockedCommand::Faults),
+            "4" => Ok(TechnicianLockedCommand::RunDiagnostics),
+            "5" => Ok(TechnicianLockedCommand::Exit),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum TechnicianMaintenanceCommand {
+    Logout,
+    ListProducts,
+    Faults,
+    RunDiagnostics,
+    SupplyProduct,
+    AdjustStock,
+    ReturnToService,
+    Exit,
+}
+
+impl Display for TechnicianMaintenanceCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TechnicianMaintenanceCommand::Logout => write!(f, "1. Logout"),
+            TechnicianMaintenanceCommand::ListProducts => write!(f, "2. List Products"),
+            TechnicianMaintenanceCommand::Faults => write!(f, "3. Faults"),
+            TechnicianMaintenanceCommand::RunDiagnostics => write!(f, "4. Run Diagnostics"),
+            TechnicianMaintenanceCommand::SupplyProduct => write!(f, "5. Supply Product"),
+            TechnicianMaintenanceCommand::AdjustStock => write!(f, "6. Adjust Stock"),
+            TechnicianMaintenanceCommand::ReturnToService => write!(f, "7. Return To Service"),
+            TechnicianMaintenanceCommand::Exit => write!(f, "8. Exit"),
+        }
+    }
+}
+
+impl TryFrom<&str> for TechnicianMaintenanceCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(TechnicianMaintenanceCommand::Logout),
+            "2" => Ok(TechnicianMaintenanceCommand::ListProducts),
+            "3" => Ok(TechnicianMaintenanceCommand::Faults),
+            "4" => Ok(TechnicianMaintenanceCommand::RunDiagnostics),
+            "5" => Ok(TechnicianMaintenanceCommand::SupplyProduct),
+            "6" => Ok(TechnicianMaintenanceCommand::AdjustStock),
+            "7" => Ok(TechnicianMaintenanceCommand::ReturnToService),
+            "8" => Ok(TechnicianMaintenanceCommand::Exit),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum ManageSuppliersCommand {
+    ListSuppliers,
+    AddSupplier,
+    AssignColumn,
+    UnassignColumn,
+}
+
+impl Display for ManageSuppliersCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManageSuppliersCommand::ListSuppliers => write!(f, "1. List Suppliers"),
+            ManageSuppliersCommand::AddSupplier => write!(f, "2. Add Supplier"),
+            ManageSuppliersCommand::AssignColumn => write!(f, "3. Assign Column"),
+            ManageSuppliersCommand::UnassignColumn => write!(f, "4. Unassign Column"),
+        }
+    }
+}
+
+impl TryFrom<&str> for ManageSuppliersCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(ManageSuppliersCommand::ListSuppliers),
+            "2" => Ok(ManageSuppliersCommand::AddSupplier),
+            "3" => Ok(ManageSuppliersCommand::AssignColumn),
+            "4" => Ok(ManageSuppliersCommand::UnassignColumn),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum ManageAccountsCommand {
+    ListAccounts,
+    OpenAccount,
+    TopUp,
+    Refund,
+    SetPin,
+}
+
+impl Display for ManageAccountsCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManageAccountsCommand::ListAccounts => write!(f, "1. List Accounts"),
+            ManageAccountsCommand::OpenAccount => write!(f, "2. Open Account"),
+            ManageAccountsCommand::TopUp => write!(f, "3. Top Up"),
+            ManageAccountsCommand::Refund => write!(f, "4. Refund"),
+            ManageAccountsCommand::SetPin => write!(f, "5. Set PIN"),
+        }
+    }
+}
+
+impl TryFrom<&str> for ManageAccountsCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(ManageAccountsCommand::ListAccounts),
+            "2" => Ok(ManageAccountsCommand::OpenAccount),
+            "3" => Ok(ManageAccountsCommand::TopUp),
+            "4" => Ok(ManageAccountsCommand::Refund),
+            "5" => Ok(ManageAccountsCommand::SetPin),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum WriteOffsCommand {
+    ListFlags,
+    WriteOff,
+    Dismiss,
+}
+
+impl Display for WriteOffsCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteOffsCommand::ListFlags => write!(f, "1. List Flagged Columns"),
+            WriteOffsCommand::WriteOff => write!(f, "2. Write Off Column"),
+            WriteOffsCommand::Dismiss => write!(f, "3. Dismiss Flag"),
+        }
+    }
+}
+
+impl TryFrom<&str> for WriteOffsCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(WriteOffsCommand::ListFlags),
+            "2" => Ok(WriteOffsCommand::WriteOff),
+            "3" => Ok(WriteOffsCommand::Dismiss),
+            _ => Err(Box::from("Invalid command")),
+        }
+    }
+}
+
+pub(crate) enum FaultsCommand {
+    ListActive,
+    ListAll,
+    Acknowledge,
+    Clear,
+    ListCodes,
+}
+
+impl Display for FaultsCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FaultsCommand::ListActive => write!(f, "1. List Active Faults"),
+            FaultsCommand::ListAll => write!(f, "2. List All Faults"),
+            FaultsCommand::Acknowledge => write!(f, "3. Acknowledge Fault"),
+            FaultsCommand::Clear => write!(f, "4. Clear Fault"),
+            FaultsCommand::ListCodes => write!(f, "5. List Fault Codes"),
+        }
+    }
+}
+
+impl TryFrom<&str> for FaultsCommand {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(FaultsCommand::ListActive),
+            "2" => Ok(FaultsCommand::ListAll),
+            "3" => Ok(FaultsCommand::Acknowledge),
+            "4" => Ok(FaultsCommand::Clear),
+            "5" => Ok(FaultsCommand::ListCodes),
             _ => Err(Box::from("Invalid command")),
         }
     }
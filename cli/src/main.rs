@@ -1,10 +1,20 @@
-use infrastructure::sqlite::{DbConn, SqliteProductRepository, SqliteSaleRepository};
+use infrastructure::card::SimulatedCardProcessor;
+use infrastructure::dispenser::SimulatedDispenser;
+use infrastructure::jobs;
+use infrastructure::receipts::{EscPosReceiptPrinter, JsonReceiptPrinter, TextReceiptPrinter};
+use infrastructure::sqlite::{
+    DbConn, SqliteCashCollectionRepository, SqliteCreditRepository,
+    SqliteCustomerAccountRepository, SqliteFaultLogRepository, SqliteMachineHistoryRepository,
+    SqliteMachineStateRepository, SqlitePaymentJournalRepository, SqliteProductRepository,
+    SqliteSaleRepository, SqliteScheduleRepository, SqliteSensorReadingRepository,
+    SqliteStockMovementRepository, SqliteStorageDiagnostics, SqliteSupplierRepository,
+    SqliteWriteOffRepository, SqliteZReportRepository,
+};
 use vending_machine::application::states::{Guest, Unlocked};
 use vending_machine::application::VendingMachine;
 use yadir::core::primitives::{DIManager, Lifetime};
 
-use crate::contracts::PromptPerspective;
-use crate::terminals::{CliPaymentTerminal, CliTerminal};
+use crate::terminals::{CliPaymentTerminals, CliTerminal};
 
 mod contracts;
 mod terminals;
@@ -22,20 +32,82 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .await
         .register::<SqliteSaleRepository>(Some(Lifetime::Singleton))
         .await
-        .register::<CliPaymentTerminal>(Some(Lifetime::Singleton))
+        .register::<SqliteMachineStateRepository>(Some(Lifetime::Singleton))
         .await
+        .register::<SqliteMachineHistoryRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteScheduleRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteSupplierRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteCashCollectionRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteStockMovementRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteZReportRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteCustomerAccountRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteCreditRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqlitePaymentJournalRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteSensorReadingRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteWriteOffRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteFaultLogRepository>(Some(Lifetime::Singleton))
+        .await
+        .register::<SqliteStorageDiagnostics>(Some(Lifetime::Singleton))
+        .await
+        .register::<SimulatedDispenser>(Some(Lifetime::Singleton))
+        .await;
+
+    manager
+        .register::<SimulatedCardProcessor>(Some(Lifetime::Singleton))
+        .await
+        .register::<CliPaymentTerminals>(Some(Lifetime::Singleton))
+        .await;
+
+    match std::env::var("RECEIPT_PRINTER").as_deref() {
+        Ok("escpos") => {
+            manager
+                .register::<EscPosReceiptPrinter>(Some(Lifetime::Singleton))
+                .await
+        }
+        Ok("json") => {
+            manager
+                .register::<JsonReceiptPrinter>(Some(Lifetime::Singleton))
+                .await
+        }
+        _ => {
+            manager
+                .register::<TextReceiptPrinter>(Some(Lifetime::Singleton))
+                .await
+        }
+    };
+
+    manager
         .register::<VendingMachine<Guest, Unlocked>>(Some(Lifetime::Singleton))
         .await
         .register::<CliTerminal<Guest, Unlocked>>(Some(Lifetime::Singleton))
         .await;
 
+    let vending_machine = manager
+        .resolve::<VendingMachine<Guest, Unlocked>>()
+        .await
+        .unwrap()
+        .extract();
+
+    jobs::start(vending_machine).await?;
+
     let terminal = manager
         .resolve::<CliTerminal<Guest, Unlocked>>()
         .await
         .unwrap()
         .extract();
 
-    let mut terminal = PromptPerspective::GuestUnlocked(terminal);
+    let mut terminal = terminal.resume().await;
 
     loop {
         terminal = terminal.dispatch().await;
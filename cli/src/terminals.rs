@@ -1,40 +1,571 @@
 use crate::contracts::{
-    AdminLockedCommand, AdminUnlockedCommand, GuestLockedCommand, GuestUnlockedCommand,
-    PromptPerspective, SupplierLockedCommand, SupplierUnlockedCommand,
+    AdminLockedCommand, AdminMaintenanceCommand, AdminUnlockedCommand, FaultsCommand,
+    GuestLockedCommand, GuestMaintenanceCommand, GuestUnlockedCommand, ManageAccountsCommand,
+    ManageSuppliersCommand, PromptPerspective, ScheduleEditCommand, SupplierLockedCommand,
+    SupplierMaintenanceCommand, SupplierUnlockedCommand, TechnicianLockedCommand,
+    TechnicianMaintenanceCommand, TechnicianUnlockedCommand, WriteOffsCommand,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use infrastructure::mdb::{open_device, MdbPaymentTerminal};
 use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use vending_machine::application::states::{
-    Admin, AuthResult, Authenticated, Guest, LockStatus, Locked, Role, Supplier, Unlocked,
+    Admin, AuthResult, Authenticated, CanCloseDay, CanCollectCash, CanDiagnose, CanManageAccounts,
+    CanManageFaults, CanManageSchedule, CanManageUsers, CanMonitorSensors, CanRestock,
+    CanRestockOwnColumns, CanViewHistory, CanViewSales, CanVoidSales, Guest, LockStatus, Locked,
+    Maintenance, Restockable, Resumed, Role, Supplier, Technician, Unlocked,
 };
-use vending_machine::application::VendingMachine;
-use vending_machine::domain::entities::{Name, Password, Price, Product, Value};
-use vending_machine::domain::interfaces::{PaymentTerminal, Terminal};
+use vending_machine::application::{PaymentTerminals, VendingMachine};
+use vending_machine::domain::entities::{
+    CardAuthorization, CardNumber, CashCount, Category, ChangePolicy, CustomerAccount, Fault,
+    FaultCode, LockReason, LockState, Name, Password, PaymentMethod, Pin, Price, Product, Quantity,
+    Sale, ScheduleException, ScheduleWindow, SensorKind, Value, VoidReason, ZReport,
+};
+use vending_machine::domain::interfaces::{
+    CardProcessor, CustomerAccountRepository, PaymentTerminal, Terminal,
+};
+use vending_machine::domain::queries::{ProductQuery, SortOrder};
+use vending_machine::domain::views::CashReconciliation;
 use yadir::core::contracts::DIBuilder;
 use yadir::core::primitives::DIObj;
-use yadir::{deps, let_deps, DIBuilder};
+use yadir::{deps, let_deps};
+
+type LockDetails = (LockReason, Option<DateTime<Utc>>);
+
+fn format_sale(sale: &Sale) -> String {
+    let line = format!(
+        "#{} {} column {} {} x{} {:+.2} {}",
+        sale.id
+            .as_ref()
+            .map(|id| id.as_value().to_string())
+            .unwrap_or_default(),
+        sale.date.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+        sale.column_id.as_value(),
+        sale.product_name.as_ref(),
+        sale.quantity.as_value(),
+        sale.amount(),
+        sale.payment_method.as_ref()
+    );
+
+    match &sale.reversal {
+        Some(reversal) => format!(
+            "{} (void of #{}: {})",
+            line,
+            reversal.sale_id.as_value(),
+            reversal.reason.as_ref()
+        ),
+        None => line,
+    }
+}
+
+fn format_fault(fault: &Fault) -> String {
+    let handled_by = match (&fault.cleared_by, &fault.acknowledged_by) {
+        (Some(name), _) | (None, Some(name)) => format!(" by {}", name.as_ref()),
+        (None, None) => String::new(),
+    };
+
+    format!(
+        "#{} {} {} {} - {} [{}{}]",
+        fault
+            .id
+            .as_ref()
+            .map(|id| id.as_value().to_string())
+            .unwrap_or_default(),
+        fault.date.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+        fault.code.as_ref(),
+        fault.code.description(),
+        fault.context,
+        fault.status.as_ref(),
+        handled_by
+    )
+}
+
+fn payment_method_label(method: &PaymentMethod) -> &'static str {
+    match method {
+        PaymentMethod::Cash => "Cash",
+        PaymentMethod::Card => "Card",
+        PaymentMethod::Wallet => "Wallet",
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn z_report_to_csv(report: &ZReport) -> String {
+    let mut lines = vec![
+        format!("Z-report,{}", report.number.as_value()),
+        format!(
+            "Opened at,{}",
+            report
+                .opened_at
+                .map(|opened_at| opened_at.to_rfc3339())
+                .unwrap_or_default()
+        ),
+        format!("Closed at,{}", report.closed_at.to_rfc3339()),
+        format!(
+            "Closed by,{}",
+            csv_field(
+                report
+                    .closed_by
+                    .as_ref()
+                    .map(|closed_by| closed_by.as_ref())
+                    .unwrap_or_default()
+            )
+        ),
+        format!("Sales count,{}", report.sales_count),
+        format!("Revenue,{:.2}", report.revenue),
+        format!("Refunds,{:.2}", report.refunds),
+        format!("Cash collected,{:.2}", report.cash_collected),
+        String::new(),
+        "Product,Sales count,Revenue".to_string(),
+    ];
+
+    for entry in report.product_revenue.iter() {
+        lines.push(format!(
+            "{},{},{:.2}",
+            csv_field(entry.product_name.as_ref()),
+            entry.sales_count,
+            entry.revenue
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("Payment method,Sales count,Total".to_string());
+    for entry in report.payment_methods.iter() {
+        lines.push(format!(
+            "{},{},{:.2}",
+            csv_field(&entry.method),
+            entry.sales_count,
+            entry.total
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("Date,Column,Product,Kind,Delta".to_string());
+    for movement in report.stock_movements.iter() {
+        lines.push(format!(
+            "{},{},{},{},{}",
+            movement.date.to_rfc3339(),
+            movement.column_id.as_value(),
+            csv_field(movement.product_name.as_ref()),
+            movement.kind.as_ref(),
+            movement.delta
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub struct CliPaymentTerminals;
+
+#[async_trait]
+impl DIBuilder for CliPaymentTerminals {
+    type Input = deps!(Box<dyn CardProcessor>, Box<dyn CustomerAccountRepository>);
+    type Output = PaymentTerminals;
+
+    async fn build(input: Self::Input) -> Self::Output {
+        let_deps!(card_processor, customer_account_repository <- input);
+
+        let methods = std::env::var("PAYMENT_METHODS").unwrap_or("cash,card,wallet".to_string());
+
+        let mut terminals = PaymentTerminals::default();
+        for method in methods
+            .split(',')
+            .filter_map(|method| PaymentMethod::parse(method.trim()).ok())
+        {
+            let terminal: Box<dyn PaymentTerminal> = match method {
+                PaymentMethod::Cash => match std::env::var("MDB_DEVICE") {
+                    Ok(path) => match open_device(&path).await {
+                        Ok(stream) => Box::new(MdbPaymentTerminal::new(stream)),
+                        Err(e) => {
+                            eprintln!("MDB device {} is unavailable: {}", path, e);
+                            Box::new(CliPaymentTerminal)
+                        }
+                    },
+                    Err(_) => Box::new(CliPaymentTerminal),
+                },
+                PaymentMethod::Card => Box::new(CardPaymentTerminal::new(card_processor.clone())),
+                PaymentMethod::Wallet => Box::new(WalletPaymentTerminal::new(
+                    customer_account_repository.clone(),
+                )),
+            };
+            terminals = terminals.register(terminal);
+        }
+
+        terminals
+    }
+}
+
+fn read_payment_input(message: &str) -> Result<String, Box<dyn Error>> {
+    let mut input = String::new();
+    println!("{} (leave empty to cancel):", message);
+    std::io::stdin().read_line(&mut input)?;
+
+    match input.trim() {
+        "" => Err(Box::from("Payment cancelled")),
+        input => Ok(input.to_string()),
+    }
+}
 
-#[derive(Clone, DIBuilder)]
-#[build_as(Box<dyn PaymentTerminal>)]
+#[derive(Clone)]
 pub struct CliPaymentTerminal;
 
 impl Terminal for CliPaymentTerminal {}
 
+#[async_trait]
 impl PaymentTerminal for CliPaymentTerminal {
-    fn request(&self) -> Result<Price, Box<dyn Error>> {
-        let mut amount = String::new();
-        println!("Please insert the amount: ");
-        std::io::stdin().read_line(&mut amount)?;
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Cash
+    }
 
-        Price::parse(amount.trim())
+    async fn request(&self, _due: Price) -> Result<Price, Box<dyn Error>> {
+        loop {
+            let amount = read_payment_input("Please insert the amount")?;
+
+            match Price::parse(&amount) {
+                Ok(amount) => return Ok(amount),
+                Err(e) => println!("{}, please try again", e),
+            }
+        }
     }
 
-    fn refund(&self, amount: Price) -> Result<(), Box<dyn Error>> {
+    async fn refund(&self, amount: Price) -> Result<(), Box<dyn Error>> {
         println!("Here's your refund: {}", amount.as_value());
         Ok(())
     }
 }
 
+#[derive(Clone)]
+pub struct WalletPaymentTerminal {
+    customer_account_repository: Box<dyn CustomerAccountRepository>,
+    pending: Arc<Mutex<Option<(Name, Price)>>>,
+}
+
+impl WalletPaymentTerminal {
+    pub fn new(customer_account_repository: Box<dyn CustomerAccountRepository>) -> Self {
+        Self {
+            customer_account_repository,
+            pending: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn take_pending(&self) -> Result<Option<(Name, Price)>, Box<dyn Error>> {
+        Ok(self
+            .pending
+            .lock()
+            .map_err(|_| "Wallet terminal state is unavailable")?
+            .take())
+    }
+
+    async fn reverse_payment(&self, badge_id: &Name, amount: Price) -> Result<(), Box<dyn Error>> {
+        let mut repository = self.customer_account_repository.clone();
+        let mut account = repository
+            .find(badge_id)
+            .await
+            .ok_or("Customer account not found")?;
+
+        account.reverse_payment(amount);
+        repository.save(account.clone()).await?;
+
+        println!("Payment reversed, balance: {:.2}", account.balance());
+        Ok(())
+    }
+}
+
+impl Terminal for WalletPaymentTerminal {}
+
+#[async_trait]
+impl PaymentTerminal for WalletPaymentTerminal {
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Wallet
+    }
+
+    async fn is_available(&self) -> bool {
+        !self.customer_account_repository.find_all().await.is_empty()
+    }
+
+    async fn request(&self, due: Price) -> Result<Price, Box<dyn Error>> {
+        let mut repository = self.customer_account_repository.clone();
+
+        let mut account = loop {
+            let badge_id = read_payment_input("Please scan your badge")?;
+
+            let badge_id = match Name::parse(&badge_id) {
+                Ok(badge_id) => badge_id,
+                Err(e) => {
+                    println!("{}, please try again", e);
+                    continue;
+                }
+            };
+            let found = repository.find(&badge_id).await;
+
+            match found {
+                Some(account) if account.balance() - due.as_value() > -0.005 => break account,
+                Some(account) => println!(
+                    "Insufficient balance: {:.2}, please try another badge",
+                    account.balance()
+                ),
+                None => println!("Customer account not found, please try again"),
+            }
+        };
+
+        account.pay(due.clone())?;
+        repository.save(account.clone()).await?;
+        *self
+            .pending
+            .lock()
+            .map_err(|_| "Wallet terminal state is unavailable")? =
+            Some((account.badge_id.clone(), due.clone()));
+
+        println!("Remaining balance: {:.2}", account.balance());
+        Ok(due)
+    }
+
+    async fn refund(&self, _amount: Price) -> Result<(), Box<dyn Error>> {
+        Err(Box::from(
+            "Wallet payments are debited for the exact amount due",
+        ))
+    }
+
+    fn reference(&self) -> Option<String> {
+        self.pending
+            .lock()
+            .ok()?
+            .as_ref()
+            .map(|(badge_id, _)| badge_id.as_ref().to_string())
+    }
+
+    async fn reverse(&self, amount: Price, reference: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let badge_id = Name::parse(reference.ok_or("No wallet badge was recorded")?)?;
+
+        self.reverse_payment(&badge_id, amount).await
+    }
+
+    async fn confirm(&self) -> Result<(), Box<dyn Error>> {
+        self.take_pending()?;
+
+        Ok(())
+    }
+
+    async fn cancel(&self) -> Result<Option<Price>, Box<dyn Error>> {
+        let Some((badge_id, amount)) = self.take_pending()? else {
+            return Ok(None);
+        };
+
+        self.reverse_payment(&badge_id, amount.clone()).await?;
+
+        Ok(Some(amount))
+    }
+
+    async fn release(&self, amount: Price) -> Result<Option<Price>, Box<dyn Error>> {
+        let badge_id = {
+            let mut pending = self
+                .pending
+                .lock()
+                .map_err(|_| "Wallet terminal state is unavailable")?;
+            let Some((badge_id, debited)) = pending.clone() else {
+                return Ok(None);
+            };
+
+            let remaining = debited.as_value() - amount.as_value();
+            match remaining > 0.005 {
+                true => {
+                    *pending = Some((badge_id.clone(), Price::parse_f32(remaining)?));
+                    Some(badge_id)
+                }
+                false => None,
+            }
+        };
+        let Some(badge_id) = badge_id else {
+            return self.cancel().await;
+        };
+
+        self.reverse_payment(&badge_id, amount.clone()).await?;
+
+        Ok(Some(amount))
+    }
+}
+
+#[derive(Clone)]
+pub struct CardPaymentTerminal {
+    card_processor: Box<dyn CardProcessor>,
+    pending: Arc<Mutex<Option<CardAuthorization>>>,
+}
+
+impl CardPaymentTerminal {
+    const PROCESSOR_TIMEOUT: Duration = Duration::from_secs(10);
+    const TEST_CARD: &'static str = "4000000000000000";
+    const TEST_AMOUNT: f32 = 0.01;
+
+    pub fn new(card_processor: Box<dyn CardProcessor>) -> Self {
+        Self {
+            card_processor,
+            pending: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn take_pending(&self) -> Result<Option<CardAuthorization>, Box<dyn Error>> {
+        Ok(self
+            .pending
+            .lock()
+            .map_err(|_| "Card terminal state is unavailable")?
+            .take())
+    }
+}
+
+impl Terminal for CardPaymentTerminal {}
+
+#[async_trait]
+impl PaymentTerminal for CardPaymentTerminal {
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Card
+    }
+
+    async fn is_available(&self) -> bool {
+        self.card_processor.is_online().await
+    }
+
+    async fn request(&self, due: Price) -> Result<Price, Box<dyn Error>> {
+        let card = loop {
+            let card = read_payment_input("Please enter your card number")?;
+
+            match CardNumber::parse(&card) {
+                Ok(card) => break card,
+                Err(e) => println!("{}, please try again", e),
+            }
+        };
+
+        println!("Authorizing {:.2}...", due.as_value());
+        let authorization = tokio::time::timeout(
+            Self::PROCESSOR_TIMEOUT,
+            self.card_processor.authorize(&card, due.clone()),
+        )
+        .await
+        .map_err(|_| "Card processor timed out")??;
+
+        println!("Approved: {} ({})", authorization.card, authorization.id);
+        *self
+            .pending
+            .lock()
+            .map_err(|_| "Card terminal state is unavailable")? = Some(authorization);
+
+        Ok(due)
+    }
+
+    async fn refund(&self, _amount: Price) -> Result<(), Box<dyn Error>> {
+        Err(Box::from(
+            "Card payments are authorized for the exact amount due",
+        ))
+    }
+
+    fn reference(&self) -> Option<String> {
+        self.pending
+            .lock()
+            .ok()?
+            .as_ref()
+            .map(|authorization| authorization.id.clone())
+    }
+
+    async fn reverse(&self, amount: Price, reference: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let authorization = CardAuthorization {
+            id: reference
+                .ok_or("No card authorization was recorded")?
+                .to_string(),
+            card: String::new(),
+            amount,
+        };
+
+        tokio::time::timeout(
+            Self::PROCESSOR_TIMEOUT,
+            self.card_processor.void(&authorization),
+        )
+        .await
+        .map_err(|_| "Card processor timed out")?
+    }
+
+    async fn confirm(&self) -> Result<(), Box<dyn Error>> {
+        let Some(authorization) = self.take_pending()? else {
+            return Ok(());
+        };
+
+        tokio::time::timeout(
+            Self::PROCESSOR_TIMEOUT,
+            self.card_processor.capture(&authorization),
+        )
+        .await
+        .map_err(|_| "Card processor timed out")?
+    }
+
+    async fn cancel(&self) -> Result<Option<Price>, Box<dyn Error>> {
+        let Some(authorization) = self.take_pending()? else {
+            return Ok(None);
+        };
+
+        tokio::time::timeout(
+            Self::PROCESSOR_TIMEOUT,
+            self.card_processor.void(&authorization),
+        )
+        .await
+        .map_err(|_| "Card processor timed out")??;
+
+        println!("Authorization {} voided", authorization.id);
+        Ok(Some(authorization.amount))
+    }
+
+    async fn release(&self, amount: Price) -> Result<Option<Price>, Box<dyn Error>> {
+        {
+            let mut pending = self
+                .pending
+                .lock()
+                .map_err(|_| "Card terminal state is unavailable")?;
+            let Some(authorization) = pending.as_mut() else {
+                return Ok(None);
+            };
+
+            let remaining = authorization.amount.as_value() - amount.as_value();
+            if remaining > 0.005 {
+                authorization.amount = Price::parse_f32(remaining)?;
+                println!(
+                    "Authorization {} reduced to {:.2}",
+                    authorization.id, remaining
+                );
+                return Ok(Some(amount));
+            }
+        }
+
+        self.cancel().await
+    }
+
+    async fn self_test(&self) -> Result<(), Box<dyn Error>> {
+        if !self.card_processor.is_online().await {
+            return Err(Box::from("Card processor is offline"));
+        }
+
+        let card = CardNumber::parse(Self::TEST_CARD)?;
+        let amount = Price::parse_f32(Self::TEST_AMOUNT)?;
+        let authorization = tokio::time::timeout(
+            Self::PROCESSOR_TIMEOUT,
+            self.card_processor.authorize(&card, amount),
+        )
+        .await
+        .map_err(|_| "Card processor timed out")??;
+
+        tokio::time::timeout(
+            Self::PROCESSOR_TIMEOUT,
+            self.card_processor.void(&authorization),
+        )
+        .await
+        .map_err(|_| "Card processor timed out")?
+    }
+}
+
 #[derive(Clone)]
 pub struct CliTerminal<U: Role, L: LockStatus> {
     vending_machine: VendingMachine<U, L>,
@@ -77,6 +608,22 @@ impl<U: Role, L: LockStatus> CliTerminal<U, L> {
 }
 
 impl<L: LockStatus> CliTerminal<Guest, L> {
+    pub async fn resume(self) -> PromptPerspective {
+        match self.vending_machine.resume().await {
+            Resumed::Unlocked(vending_machine) => {
+                PromptPerspective::GuestUnlocked(CliTerminal::<Guest, Unlocked> { vending_machine })
+            }
+            Resumed::Locked(vending_machine) => {
+                PromptPerspective::GuestLocked(CliTerminal::<Guest, Locked> { vending_machine })
+            }
+            Resumed::Maintenance(vending_machine) => {
+                PromptPerspective::GuestMaintenance(CliTerminal::<Guest, Maintenance> {
+                    vending_machine,
+                })
+            }
+        }
+    }
+
     fn pre_login(&self) -> Result<(Name, Password), Box<dyn Error>> {
         self.prompt("Enter your username:");
         let mut username = String::new();
@@ -91,60 +638,1962 @@ impl<L: LockStatus> CliTerminal<Guest, L> {
 
         Ok((username, password))
     }
+
+    async fn show_out_of_service_banner(&self) {
+        let state = self.vending_machine.machine_state().await.ok().flatten();
+
+        let title = match state.as_ref().map(|state| &state.lock_state) {
+            Some(LockState::Maintenance) => "Under maintenance",
+            _ => "Out of service",
+        };
+        let reason = state.as_ref().and_then(|state| state.reason.as_ref());
+        let expected_back = state.as_ref().and_then(|state| state.expected_back);
+
+        let banner = match (reason, expected_back) {
+            (Some(reason), Some(expected_back)) => format!(
+                "*** {}: {}. Expected back at {}. ***",
+                title,
+                reason.as_ref(),
+                expected_back.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            ),
+            (Some(reason), None) => format!("*** {}: {}. ***", title, reason.as_ref()),
+            (None, _) => format!("*** {}. ***", title),
+        };
+
+        self.prompt(&banner);
+    }
+
+    fn reprint_receipt(&self) -> Result<(), Box<dyn Error>> {
+        self.vending_machine.reprint_last_receipt()?;
+
+        Ok(())
+    }
+
+    async fn return_credit(&mut self) -> Result<(), Box<dyn Error>> {
+        let credit = self.vending_machine.return_credit().await?;
+
+        self.prompt(&format!("Credit returned: {:.2}", credit.as_value()));
+
+        Ok(())
+    }
+
+    async fn search_products(&self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Enter a part of the product name (leave empty to skip):");
+        let mut name = String::new();
+        std::io::stdin().read_line(&mut name)?;
+
+        let name = match name.trim() {
+            "" => None,
+            name => Some(name.to_string()),
+        };
+
+        self.prompt("Enter the category (leave empty to skip):");
+        let mut category = String::new();
+        std::io::stdin().read_line(&mut category)?;
+
+        let category = match category.trim() {
+            "" => None,
+            category => Some(Category::parse(category)?),
+        };
+
+        self.prompt("Enter the minimum price (leave empty to skip):");
+        let mut min_price = String::new();
+        std::io::stdin().read_line(&mut min_price)?;
+
+        let min_price = match min_price.trim() {
+            "" => None,
+            min_price => Some(Price::parse(min_price)?),
+        };
+
+        self.prompt("Enter the maximum price (leave empty to skip):");
+        let mut max_price = String::new();
+        std::io::stdin().read_line(&mut max_price)?;
+
+        let max_price = match max_price.trim() {
+            "" => None,
+            max_price => Some(Price::parse(max_price)?),
+        };
+
+        self.prompt("Show only products in stock? (y/N):");
+        let mut in_stock_only = String::new();
+        std::io::stdin().read_line(&mut in_stock_only)?;
+
+        let in_stock_only = matches!(in_stock_only.trim(), "y" | "Y");
+
+        self.prompt("Sort by: 1. Column, 2. Name (A-Z), 3. Name (Z-A), 4. Price (low-high), 5. Price (high-low) (leave empty for column):");
+        let mut sort = String::new();
+        std::io::stdin().read_line(&mut sort)?;
+
+        let sort = match sort.trim() {
+            "" | "1" => SortOrder::Column,
+            "2" => SortOrder::NameAsc,
+            "3" => SortOrder::NameDesc,
+            "4" => SortOrder::PriceAsc,
+            "5" => SortOrder::PriceDesc,
+            _ => return Err(Box::from("Invalid sort order")),
+        };
+
+        let query = ProductQuery {
+            name,
+            category,
+            min_price,
+            max_price,
+            in_stock_only,
+            sort,
+        };
+
+        self.prompt("Products found:");
+        for product in self.vending_machine.search(&query).await {
+            self.prompt(&format!("{:?}", product));
+        }
+
+        Ok(())
+    }
 }
 
-impl<L: LockStatus> CliTerminal<Admin, L> {
+impl<U: CanViewSales, L: LockStatus> CliTerminal<U, L> {
     async fn list_sales(&self) -> Result<(), Box<dyn Error>> {
         self.prompt("Sales report:");
         for sale in self.vending_machine.list_sales_report().await {
-            self.prompt(&format!("{:?}", sale));
+            self.prompt(&format_sale(&sale));
+        }
+
+        Ok(())
+    }
+
+    async fn list_recovered_payments(&self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Recovered payments:");
+        for transaction in self.vending_machine.list_recovered_payments().await {
+            let started_at = transaction
+                .started_at()
+                .map(|date| {
+                    date.with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_default();
+
+            if let Some((action, amount)) = transaction.recovery() {
+                self.prompt(&format!(
+                    "#{} started {} - inserted {:.2}, recovered by {} of {:.2}",
+                    transaction.transaction_id.as_value(),
+                    started_at,
+                    transaction.inserted(),
+                    action.as_ref(),
+                    amount
+                ));
+            }
         }
 
         Ok(())
     }
 }
 
-impl<U: Authenticated> CliTerminal<U, Unlocked> {
-    fn logout(self) -> PromptPerspective {
-        PromptPerspective::GuestUnlocked(CliTerminal::<Guest, Unlocked>::new(
-            self.vending_machine.logout(),
-        ))
+impl<U: CanVoidSales, L: LockStatus> CliTerminal<U, L> {
+    async fn void_sale(&mut self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Enter the sale id:");
+        let mut sale_id = String::new();
+        std::io::stdin().read_line(&mut sale_id)?;
+
+        let sale_id = Value::parse(sale_id.trim())?;
+
+        self.prompt("Enter the reason for voiding the sale:");
+        let mut reason = String::new();
+        std::io::stdin().read_line(&mut reason)?;
+
+        let reason = VoidReason::parse(reason.trim())?;
+
+        self.prompt("Restore the stock of the voided items? (y/n):");
+        let mut restore_stock = String::new();
+        std::io::stdin().read_line(&mut restore_stock)?;
+
+        let restore_stock = restore_stock.trim().eq_ignore_ascii_case("y");
+
+        let reversal = self
+            .vending_machine
+            .void_sale(sale_id, reason, restore_stock)
+            .await?;
+
+        self.prompt("Sale voided successfully");
+        self.prompt(&format_sale(&reversal));
+
+        Ok(())
     }
 }
 
-impl<U: Authenticated> CliTerminal<U, Locked> {
-    fn logout(self) -> PromptPerspective {
-        PromptPerspective::GuestLocked(CliTerminal::<Guest, Locked> {
-            vending_machine: self.vending_machine.logout(),
-        })
+impl<U: CanViewHistory, L: LockStatus> CliTerminal<U, L> {
+    async fn list_history(&self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Machine history:");
+        for event in self.vending_machine.list_history().await {
+            self.prompt(&format!("{:?}", event));
+        }
+
+        Ok(())
+    }
+}
+
+impl<U: CanManageSchedule, L: LockStatus> CliTerminal<U, L> {
+    async fn view_schedule(&self) -> Result<(), Box<dyn Error>> {
+        let schedule = self.vending_machine.schedule().await;
+
+        if !schedule.is_enabled() {
+            self.prompt(
+                "No opening windows configured, the machine is never locked automatically.",
+            );
+        }
+
+        self.prompt("Opening windows:");
+        for (index, window) in schedule.windows.iter().enumerate() {
+            self.prompt(&format!(
+                "{}. {} {}-{}",
+                index + 1,
+                window.weekday,
+                window.opens_at.format("%H:%M"),
+                window.closes_at.format("%H:%M")
+            ));
+        }
+
+        self.prompt("Exception dates:");
+        for exception in schedule.exceptions.iter() {
+            self.prompt(&format!(
+                "{} {}",
+                exception.date.format("%Y-%m-%d"),
+                exception.description
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn edit_schedule(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut schedule = self.vending_machine.schedule().await;
+
+        self.prompt("Choose an action:");
+        self.prompt(&ScheduleEditCommand::AddWindow.to_string());
+        self.prompt(&ScheduleEditCommand::RemoveWindow.to_string());
+        self.prompt(&ScheduleEditCommand::AddException.to_string());
+        self.prompt(&ScheduleEditCommand::RemoveException.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        match ScheduleEditCommand::try_from(command.trim())? {
+            ScheduleEditCommand::AddWindow => {
+                self.prompt("Enter the weekday (e.g. Mon):");
+                let mut weekday = String::new();
+                std::io::stdin().read_line(&mut weekday)?;
+
+                self.prompt("Enter the opening time as HH:MM:");
+                let mut opens_at = String::new();
+                std::io::stdin().read_line(&mut opens_at)?;
+
+                self.prompt("Enter the closing time as HH:MM:");
+                let mut closes_at = String::new();
+                std::io::stdin().read_line(&mut closes_at)?;
+
+                let window =
+                    ScheduleWindow::parse(weekday.trim(), opens_at.trim(), closes_at.trim())?;
+
+                schedule.windows.push(window);
+            }
+            ScheduleEditCommand::RemoveWindow => {
+                self.prompt("Enter the number of the opening window to remove:");
+                let mut index = String::new();
+                std::io::stdin().read_line(&mut index)?;
+
+                let index = Value::parse(index.trim())?.as_value() as usize - 1;
+                if index >= schedule.windows.len() {
+                    return Err(Box::from("Opening window not found"));
+                }
+
+                schedule.windows.remove(index);
+            }
+            ScheduleEditCommand::AddException => {
+                self.prompt("Enter the date as YYYY-MM-DD:");
+                let mut date = String::new();
+                std::io::stdin().read_line(&mut date)?;
+
+                self.prompt("Enter a description (e.g. Christmas):");
+                let mut description = String::new();
+                std::io::stdin().read_line(&mut description)?;
+
+                let exception = ScheduleException::parse(date.trim(), description.trim())?;
+                if schedule
+                    .exceptions
+                    .iter()
+                    .any(|existing| existing.date == exception.date)
+                {
+                    return Err(Box::from("Exception date already exists"));
+                }
+
+                schedule.exceptions.push(exception);
+            }
+            ScheduleEditCommand::RemoveException => {
+                self.prompt("Enter the date to remove as YYYY-MM-DD:");
+                let mut date = String::new();
+                std::io::stdin().read_line(&mut date)?;
+
+                let removed = ScheduleException::parse(date.trim(), "")?;
+                let count = schedule.exceptions.len();
+                schedule
+                    .exceptions
+                    .retain(|exception| exception.date != removed.date);
+
+                if schedule.exceptions.len() == count {
+                    return Err(Box::from("Exception date not found"));
+                }
+            }
+        }
+
+        self.vending_machine.update_schedule(schedule).await?;
+
+        self.prompt("Schedule updated successfully");
+
+        Ok(())
+    }
+}
+
+impl<U: CanManageUsers, L: LockStatus> CliTerminal<U, L> {
+    async fn manage_suppliers(&mut self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Choose an action:");
+        self.prompt(&ManageSuppliersCommand::ListSuppliers.to_string());
+        self.prompt(&ManageSuppliersCommand::AddSupplier.to_string());
+        self.prompt(&ManageSuppliersCommand::AssignColumn.to_string());
+        self.prompt(&ManageSuppliersCommand::UnassignColumn.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        match ManageSuppliersCommand::try_from(command.trim())? {
+            ManageSuppliersCommand::ListSuppliers => {
+                self.prompt("Suppliers:");
+                for account in self.vending_machine.list_suppliers().await {
+                    let columns = account
+                        .columns
+                        .iter()
+                        .map(|column| column.as_value().to_string())
+                        .collect::<Vec<_>>();
+
+                    self.prompt(&format!(
+                        "{} (columns: {})",
+                        account.name.as_ref(),
+                        columns.join(", ")
+                    ));
+                }
+            }
+            ManageSuppliersCommand::AddSupplier => {
+                let (name, password) = self.pre_supplier()?;
+
+                self.vending_machine.add_supplier(name, password).await?;
+
+                self.prompt("Supplier added successfully");
+            }
+            ManageSuppliersCommand::AssignColumn => {
+                self.prompt("Enter the supplier username:");
+                let mut name = String::new();
+                std::io::stdin().read_line(&mut name)?;
+
+                let name = Name::parse(name.trim())?;
+
+                self.prompt("Enter the column id:");
+                let mut column_id = String::new();
+                std::io::stdin().read_line(&mut column_id)?;
+
+                let column_id = Value::parse(column_id.trim())?;
+
+                self.vending_machine.assign_column(&name, column_id).await?;
+
+                self.prompt("Column assigned successfully");
+            }
+            ManageSuppliersCommand::UnassignColumn => {
+                self.prompt("Enter the column id:");
+                let mut column_id = String::new();
+                std::io::stdin().read_line(&mut column_id)?;
+
+                let column_id = Value::parse(column_id.trim())?;
+
+                self.vending_machine.unassign_column(column_id).await?;
+
+                self.prompt("Column unassigned successfully");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pre_supplier(&self) -> Result<(Name, Password), Box<dyn Error>> {
+        self.prompt("Enter the supplier username:");
+        let mut name = String::new();
+        std::io::stdin().read_line(&mut name)?;
+
+        let name = Name::parse(name.trim())?;
+
+        self.prompt("Enter the supplier password:");
+        let mut password = String::new();
+        std::io::stdin().read_line(&mut password)?;
+
+        let password = Password::parse(password.trim())?;
+
+        Ok((name, password))
+    }
+}
+
+impl<U: CanMonitorSensors, L: LockStatus> CliTerminal<U, L> {
+    const RECENT_SENSOR_READINGS: usize = 50;
+
+    async fn list_sensor_readings(&self) -> Result<(), Box<dyn Error>> {
+        let readings = self.vending_machine.list_sensor_readings().await;
+        let skipped = readings.len().saturating_sub(Self::RECENT_SENSOR_READINGS);
+
+        self.prompt(&format!(
+            "Sensor readings (last {} of {}):",
+            readings.len() - skipped,
+            readings.len()
+        ));
+        for reading in readings.iter().skip(skipped) {
+            let value = match reading.kind {
+                SensorKind::Temperature => format!("{:.1} C", reading.value),
+                SensorKind::Door => match reading.value > 0.0 {
+                    true => "open".to_string(),
+                    false => "closed".to_string(),
+                },
+            };
+
+            self.prompt(&format!(
+                "{} {} {}{}",
+                reading
+                    .date
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+                reading.kind.as_ref(),
+                value,
+                match reading.alarm {
+                    true => " (alarm)",
+                    false => "",
+                }
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn manage_write_offs(&mut self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Choose an action:");
+        self.prompt(&WriteOffsCommand::ListFlags.to_string());
+        self.prompt(&WriteOffsCommand::WriteOff.to_string());
+        self.prompt(&WriteOffsCommand::Dismiss.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        match WriteOffsCommand::try_from(command.trim())? {
+            WriteOffsCommand::ListFlags => {
+                self.prompt("Columns flagged for write-off:");
+                for flag in self.vending_machine.list_write_off_flags().await {
+                    self.prompt(&format!(
+                        "Column {} {} x{} flagged {} - {}",
+                        flag.column_id.as_value(),
+                        flag.product_name.as_ref(),
+                        flag.quantity.as_value(),
+                        flag.flagged_at
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M"),
+                        flag.reason
+                    ));
+                }
+            }
+            WriteOffsCommand::WriteOff => {
+                self.prompt("Enter the column id:");
+                let mut column_id = String::new();
+                std::io::stdin().read_line(&mut column_id)?;
+
+                let column_id = Value::parse(column_id.trim())?;
+
+                let product = self.vending_machine.write_off(column_id).await?;
+
+                self.prompt(&format!(
+                    "Column {} ({}) written off",
+                    product.column_id.as_value(),
+                    product.name.as_ref()
+                ));
+            }
+            WriteOffsCommand::Dismiss => {
+                self.prompt("Enter the column id:");
+                let mut column_id = String::new();
+                std::io::stdin().read_line(&mut column_id)?;
+
+                let column_id = Value::parse(column_id.trim())?;
+
+                self.vending_machine.dismiss_write_off(column_id).await?;
+
+                self.prompt("Write-off flag dismissed");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<U: CanManageAccounts, L: LockStatus> CliTerminal<U, L> {
+    async fn manage_accounts(&mut self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Choose an action:");
+        self.prompt(&ManageAccountsCommand::ListAccounts.to_string());
+        self.prompt(&ManageAccountsCommand::OpenAccount.to_string());
+        self.prompt(&ManageAccountsCommand::TopUp.to_string());
+        self.prompt(&ManageAccountsCommand::Refund.to_string());
+        self.prompt(&ManageAccountsCommand::SetPin.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        match ManageAccountsCommand::try_from(command.trim())? {
+            ManageAccountsCommand::ListAccounts => {
+                self.prompt("Customer accounts:");
+                for account in self.vending_machine.list_customer_accounts().await {
+                    self.show_account(&account);
+                }
+            }
+            ManageAccountsCommand::OpenAccount => {
+                let badge_id = self.pre_badge_id()?;
+
+                self.prompt("Enter the account holder:");
+                let mut holder = String::new();
+                std::io::stdin().read_line(&mut holder)?;
+
+                let holder = Name::parse(holder.trim())?;
+
+                self.vending_machine
+                    .open_customer_account(badge_id, holder)
+                    .await?;
+
+                self.prompt("Account opened successfully");
+            }
+            ManageAccountsCommand::TopUp => {
+                let badge_id = self.pre_badge_id()?;
+                let amount = self.pre_account_amount()?;
+
+                let account = self
+                    .vending_machine
+                    .top_up_customer_account(badge_id, amount)
+                    .await?;
+
+                self.show_account(&account);
+            }
+            ManageAccountsCommand::Refund => {
+                let badge_id = self.pre_badge_id()?;
+                let amount = self.pre_account_amount()?;
+
+                let account = self
+                    .vending_machine
+                    .refund_customer_account(badge_id, amount)
+                    .await?;
+
+                self.show_account(&account);
+            }
+            ManageAccountsCommand::SetPin => {
+                let badge_id = self.pre_badge_id()?;
+
+                self.prompt("Enter the new PIN:");
+                let mut pin = String::new();
+                std::io::stdin().read_line(&mut pin)?;
+
+                let pin = Pin::parse(pin.trim())?;
+
+                self.vending_machine
+                    .set_customer_account_pin(badge_id, pin)
+                    .await?;
+
+                self.prompt("PIN updated successfully");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pre_badge_id(&self) -> Result<Name, Box<dyn Error>> {
+        self.prompt("Enter the badge id:");
+        let mut badge_id = String::new();
+        std::io::stdin().read_line(&mut badge_id)?;
+
+        Name::parse(badge_id.trim())
+    }
+
+    fn pre_account_amount(&self) -> Result<Price, Box<dyn Error>> {
+        self.prompt("Enter the amount:");
+        let mut amount = String::new();
+        std::io::stdin().read_line(&mut amount)?;
+
+        Price::parse(amount.trim())
+    }
+
+    fn show_account(&self, account: &CustomerAccount) {
+        self.prompt(&format!(
+            "{} ({}): balance {:.2}",
+            account.badge_id.as_ref(),
+            account.holder.as_ref(),
+            account.balance()
+        ));
+
+        for transaction in account.transactions.iter() {
+            self.prompt(&format!(
+                "  {} {} {:.2}",
+                transaction
+                    .date
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+                transaction.kind.as_ref(),
+                transaction.amount.as_value()
+            ));
+        }
+    }
+}
+
+impl<U: CanCollectCash, L: LockStatus> CliTerminal<U, L> {
+    async fn collect_cash(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut counts = Vec::new();
+
+        loop {
+            self.prompt("Enter the denomination (leave empty to finish):");
+            let mut denomination = String::new();
+            std::io::stdin().read_line(&mut denomination)?;
+
+            if denomination.trim().is_empty() {
+                break;
+            }
+
+            let denomination = Price::parse(denomination.trim())?;
+
+            self.prompt("Enter the count:");
+            let mut count = String::new();
+            std::io::stdin().read_line(&mut count)?;
+
+            let count = Quantity::parse(count.trim())?;
+
+            counts.push(CashCount {
+                denomination,
+                count,
+            });
+        }
+
+        let reconciliation = self.vending_machine.collect_cash(counts).await?;
+
+        self.prompt("Cash collected successfully");
+        self.show_reconciliation(&reconciliation);
+
+        Ok(())
+    }
+
+    async fn reconciliation_report(&self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Reconciliation report:");
+        for reconciliation in self.vending_machine.reconciliation_report().await {
+            self.show_reconciliation(&reconciliation);
+        }
+
+        Ok(())
+    }
+
+    fn show_reconciliation(&self, reconciliation: &CashReconciliation) {
+        let since = match reconciliation.since {
+            Some(since) => since
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            None => "the beginning".to_string(),
+        };
+        let status = match reconciliation.is_balanced() {
+            true => "OK".to_string(),
+            false => format!("DISCREPANCY {:+.2}", reconciliation.discrepancy()),
+        };
+
+        self.prompt(&format!(
+            "From {} to {}: expected {:.2}, counted {:.2} [{}]",
+            since,
+            reconciliation
+                .until
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M"),
+            reconciliation.expected,
+            reconciliation.counted,
+            status
+        ));
+    }
+}
+
+impl<U: CanCloseDay, L: LockStatus> CliTerminal<U, L> {
+    async fn close_day(&mut self) -> Result<(), Box<dyn Error>> {
+        let report = self.vending_machine.close_day().await?;
+
+        self.prompt("Day closed successfully");
+        self.show_z_report(&report);
+
+        Ok(())
+    }
+
+    async fn list_z_reports(&self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Z-reports:");
+        for report in self.vending_machine.list_z_reports().await {
+            self.show_z_report(&report);
+        }
+
+        Ok(())
+    }
+
+    async fn export_z_report(&self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Enter the Z-report number:");
+        let mut number = String::new();
+        std::io::stdin().read_line(&mut number)?;
+
+        let number = Value::parse(number.trim())?;
+
+        let report = self
+            .vending_machine
+            .list_z_reports()
+            .await
+            .into_iter()
+            .find(|report| report.number == number)
+            .ok_or("Z-report not found")?;
+
+        self.prompt("Enter the file path to export to:");
+        let mut path = String::new();
+        std::io::stdin().read_line(&mut path)?;
+
+        std::fs::write(path.trim(), z_report_to_csv(&report))?;
+
+        self.prompt("Z-report exported successfully");
+
+        Ok(())
+    }
+
+    fn show_z_report(&self, report: &ZReport) {
+        self.prompt(&format!(
+            "Z-report {} closed at {}: {} sale(s), revenue {:.2}, refunds {:.2}, cash collected {:.2}, {} stock movement(s)",
+            report.number.as_value(),
+            report.closed_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+            report.sales_count,
+            report.revenue,
+            report.refunds,
+            report.cash_collected,
+            report.stock_movements.len()
+        ));
+    }
+}
+
+impl<U: Authenticated, L: LockStatus> CliTerminal<U, L> {
+    fn pre_lock(&self) -> Result<LockDetails, Box<dyn Error>> {
+        self.prompt("Enter the reason for taking the machine out of service:");
+        let mut reason = String::new();
+        std::io::stdin().read_line(&mut reason)?;
+
+        let reason = LockReason::parse(reason.trim())?;
+
+        self.prompt("Enter the expected back time as YYYY-MM-DD HH:MM (leave empty to skip):");
+        let mut expected_back = String::new();
+        std::io::stdin().read_line(&mut expected_back)?;
+
+        let expected_back = match expected_back.trim() {
+            "" => None,
+            expected_back => {
+                let expected_back = NaiveDateTime::parse_from_str(expected_back, "%Y-%m-%d %H:%M")
+                    .map_err(|_| "Expected back time must be in the YYYY-MM-DD HH:MM format")?;
+                let expected_back = Local
+                    .from_local_datetime(&expected_back)
+                    .single()
+                    .ok_or("Expected back time is ambiguous in the local time zone")?;
+
+                Some(expected_back.with_timezone(&Utc))
+            }
+        };
+
+        Ok((reason, expected_back))
+    }
+}
+
+impl<U: Authenticated> CliTerminal<U, Unlocked> {
+    fn logout(self) -> PromptPerspective {
+        PromptPerspective::GuestUnlocked(CliTerminal::<Guest, Unlocked>::new(
+            self.vending_machine.logout(),
+        ))
+    }
+}
+
+impl<U: Authenticated> CliTerminal<U, Locked> {
+    fn logout(self) -> PromptPerspective {
+        PromptPerspective::GuestLocked(CliTerminal::<Guest, Locked> {
+            vending_machine: self.vending_machine.logout(),
+        })
+    }
+}
+
+impl<U: Authenticated> CliTerminal<U, Maintenance> {
+    fn logout(self) -> PromptPerspective {
+        PromptPerspective::GuestMaintenance(CliTerminal::<Guest, Maintenance> {
+            vending_machine: self.vending_machine.logout(),
+        })
+    }
+}
+
+impl CliTerminal<Guest, Unlocked> {
+    pub async fn run(mut self) -> PromptPerspective {
+        loop {
+            if self.vending_machine.lock_state_changed().await {
+                return self.resume().await;
+            }
+
+            match self.choose_command() {
+                Ok(GuestUnlockedCommand::Login) => match self.pre_login() {
+                    Ok((username, password)) => {
+                        return self.login(username, password).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestUnlockedCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestUnlockedCommand::SearchProducts) => match self.search_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestUnlockedCommand::BuyProduct) => match self.buy_product().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestUnlockedCommand::ReprintReceipt) => match self.reprint_receipt() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestUnlockedCommand::ReturnCredit) => match self.return_credit().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestUnlockedCommand::Exit) => match self.exit() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Err(e) => {
+                    self.prompt(&format!("Error: {}", e));
+                }
+            }
+            self.prompt("");
+        }
+    }
+
+    fn choose_command(&self) -> Result<GuestUnlockedCommand, Box<dyn Error>> {
+        self.prompt("Choose a command:");
+        self.prompt(&GuestUnlockedCommand::Login.to_string());
+        self.prompt(&GuestUnlockedCommand::ListProducts.to_string());
+        self.prompt(&GuestUnlockedCommand::SearchProducts.to_string());
+        self.prompt(&GuestUnlockedCommand::BuyProduct.to_string());
+        self.prompt(&GuestUnlockedCommand::ReprintReceipt.to_string());
+        self.prompt(&GuestUnlockedCommand::ReturnCredit.to_string());
+        self.prompt(&GuestUnlockedCommand::Exit.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        GuestUnlockedCommand::try_from(command.trim())
+    }
+
+    async fn login(self, username: Name, password: Password) -> PromptPerspective {
+        match self.vending_machine.login(&username, &password).await {
+            AuthResult::SuccessAdmin(vending_machine) => {
+                PromptPerspective::AdminUnlocked(CliTerminal::<Admin, Unlocked> { vending_machine })
+            }
+            AuthResult::SuccessSupplier(vending_machine) => {
+                PromptPerspective::SupplierUnlocked(CliTerminal::<Supplier, Unlocked> {
+                    vending_machine,
+                })
+            }
+            AuthResult::SuccessTechnician(vending_machine) => {
+                PromptPerspective::TechnicianUnlocked(CliTerminal::<Technician, Unlocked> {
+                    vending_machine,
+                })
+            }
+            AuthResult::Failure(vending_machine) => {
+                PromptPerspective::GuestUnlocked(CliTerminal::<Guest, Unlocked> { vending_machine })
+            }
+        }
+    }
+
+    async fn choose_payment_method(&self) -> Result<PaymentMethod, Box<dyn Error>> {
+        let mut methods = self.vending_machine.payment_methods().await;
+        if methods.len() <= 1 {
+            return methods
+                .pop()
+                .ok_or(Box::from("No payment method is currently available"));
+        }
+
+        self.prompt("Choose a payment method:");
+        for (index, method) in methods.iter().enumerate() {
+            self.prompt(&format!("{}. {}", index + 1, payment_method_label(method)));
+        }
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice)?;
+
+        choice
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|choice| choice.checked_sub(1))
+            .and_then(|index| methods.get(index).cloned())
+            .ok_or(Box::from("Invalid payment method"))
+    }
+
+    async fn buy_product(&mut self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Enter the product id:");
+        let mut product_id = String::new();
+        std::io::stdin().read_line(&mut product_id)?;
+
+        let product_id = Value::parse(product_id.trim())?;
+
+        self.prompt("Enter the amount:");
+        let mut amount = String::new();
+        std::io::stdin().read_line(&mut amount)?;
+
+        let amount = Value::parse(amount.trim())?;
+
+        let payment_method = self.choose_payment_method().await?;
+
+        self.prompt("Keep any change as credit for your next purchase? (y/N):");
+        let mut keep_credit = String::new();
+        std::io::stdin().read_line(&mut keep_credit)?;
+
+        let change_policy = match keep_credit.trim() {
+            "y" | "Y" => ChangePolicy::KeepAsCredit,
+            _ => ChangePolicy::Refund,
+        };
+
+        self.vending_machine
+            .buy(product_id, amount, payment_method, change_policy)
+            .await?;
+
+        self.prompt("Product bought successfully");
+
+        Ok(())
+    }
+}
+
+impl CliTerminal<Guest, Locked> {
+    pub async fn run(mut self) -> PromptPerspective {
+        loop {
+            if self.vending_machine.lock_state_changed().await {
+                return self.resume().await;
+            }
+
+            self.show_out_of_service_banner().await;
+
+            match self.choose_command() {
+                Ok(GuestLockedCommand::Login) => match self.pre_login() {
+                    Ok((username, password)) => {
+                        return self.login(username, password).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestLockedCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestLockedCommand::SearchProducts) => match self.search_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestLockedCommand::ReprintReceipt) => match self.reprint_receipt() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestLockedCommand::ReturnCredit) => match self.return_credit().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestLockedCommand::Exit) => match self.exit() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Err(e) => {
+                    self.prompt(&format!("Error: {}", e));
+                }
+            }
+            self.prompt("");
+        }
+    }
+
+    fn choose_command(&self) -> Result<GuestLockedCommand, Box<dyn Error>> {
+        self.prompt("Choose a command:");
+        self.prompt(&GuestLockedCommand::Login.to_string());
+        self.prompt(&GuestLockedCommand::ListProducts.to_string());
+        self.prompt(&GuestLockedCommand::SearchProducts.to_string());
+        self.prompt(&GuestLockedCommand::ReprintReceipt.to_string());
+        self.prompt(&GuestLockedCommand::ReturnCredit.to_string());
+        self.prompt(&GuestLockedCommand::Exit.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        GuestLockedCommand::try_from(command.trim())
+    }
+
+    async fn login(self, username: Name, password: Password) -> PromptPerspective {
+        match self.vending_machine.login(&username, &password).await {
+            AuthResult::SuccessAdmin(vending_machine) => {
+                PromptPerspective::AdminLocked(CliTerminal::<Admin, Locked> { vending_machine })
+            }
+            AuthResult::SuccessSupplier(vending_machine) => {
+                PromptPerspective::SupplierLocked(CliTerminal::<Supplier, Locked> {
+                    vending_machine,
+                })
+            }
+            AuthResult::SuccessTechnician(vending_machine) => {
+                PromptPerspective::TechnicianLocked(CliTerminal::<Technician, Locked> {
+                    vending_machine,
+                })
+            }
+            AuthResult::Failure(vending_machine) => {
+                PromptPerspective::GuestLocked(CliTerminal::<Guest, Locked> { vending_machine })
+            }
+        }
+    }
+}
+
+impl CliTerminal<Guest, Maintenance> {
+    pub async fn run(mut self) -> PromptPerspective {
+        loop {
+            if self.vending_machine.lock_state_changed().await {
+                return self.resume().await;
+            }
+
+            self.show_out_of_service_banner().await;
+
+            match self.choose_command() {
+                Ok(GuestMaintenanceCommand::Login) => match self.pre_login() {
+                    Ok((username, password)) => {
+                        return self.login(username, password).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestMaintenanceCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestMaintenanceCommand::SearchProducts) => match self.search_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestMaintenanceCommand::ReprintReceipt) => match self.reprint_receipt() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestMaintenanceCommand::ReturnCredit) => match self.return_credit().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(GuestMaintenanceCommand::Exit) => match self.exit() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Err(e) => {
+                    self.prompt(&format!("Error: {}", e));
+                }
+            }
+            self.prompt("");
+        }
+    }
+
+    fn choose_command(&self) -> Result<GuestMaintenanceCommand, Box<dyn Error>> {
+        self.prompt("Choose a command:");
+        self.prompt(&GuestMaintenanceCommand::Login.to_string());
+        self.prompt(&GuestMaintenanceCommand::ListProducts.to_string());
+        self.prompt(&GuestMaintenanceCommand::SearchProducts.to_string());
+        self.prompt(&GuestMaintenanceCommand::ReprintReceipt.to_string());
+        self.prompt(&GuestMaintenanceCommand::ReturnCredit.to_string());
+        self.prompt(&GuestMaintenanceCommand::Exit.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        GuestMaintenanceCommand::try_from(command.trim())
+    }
+
+    async fn login(self, username: Name, password: Password) -> PromptPerspective {
+        match self.vending_machine.login(&username, &password).await {
+            AuthResult::SuccessAdmin(vending_machine) => {
+                PromptPerspective::AdminMaintenance(CliTerminal::<Admin, Maintenance> {
+                    vending_machine,
+                })
+            }
+            AuthResult::SuccessSupplier(vending_machine) => {
+                PromptPerspective::SupplierMaintenance(CliTerminal::<Supplier, Maintenance> {
+                    vending_machine,
+                })
+            }
+            AuthResult::SuccessTechnician(vending_machine) => {
+                PromptPerspective::TechnicianMaintenance(CliTerminal::<Technician, Maintenance> {
+                    vending_machine,
+                })
+            }
+            AuthResult::Failure(vending_machine) => {
+                PromptPerspective::GuestMaintenance(CliTerminal::<Guest, Maintenance> {
+                    vending_machine,
+                })
+            }
+        }
+    }
+}
+
+impl CliTerminal<Admin, Unlocked> {
+    pub async fn run(mut self) -> PromptPerspective {
+        loop {
+            match self.choose_command() {
+                Ok(AdminUnlockedCommand::Logout) => {
+                    return self.logout();
+                }
+                Ok(AdminUnlockedCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::ListSales) => match self.list_sales().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::VoidSale) => match self.void_sale().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::RecoveredPayments) => {
+                    match self.list_recovered_payments().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(AdminUnlockedCommand::ViewHistory) => match self.list_history().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::ViewSchedule) => match self.view_schedule().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::EditSchedule) => match self.edit_schedule().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::ManageSuppliers) => match self.manage_suppliers().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::ManageAccounts) => match self.manage_accounts().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::SensorReadings) => match self.list_sensor_readings().await
+                {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::WriteOffs) => match self.manage_write_offs().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::Faults) => match self.manage_faults().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::RunDiagnostics) => match self.run_diagnostics().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::CollectCash) => match self.collect_cash().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::ReconciliationReport) => {
+                    match self.reconciliation_report().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(AdminUnlockedCommand::Lock) => match self.pre_lock() {
+                    Ok((reason, expected_back)) => {
+                        return self.lock(reason, expected_back).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::CloseDay) => match self.close_day().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::ListZReports) => match self.list_z_reports().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::ExportZReport) => match self.export_z_report().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::EnterMaintenance) => match self.pre_lock() {
+                    Ok((reason, expected_back)) => {
+                        return self.enter_maintenance(reason, expected_back).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminUnlockedCommand::Exit) => match self.exit() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Err(e) => {
+                    self.prompt(&format!("Error: {}", e));
+                }
+            }
+            self.prompt("");
+        }
+    }
+
+    fn choose_command(&self) -> Result<AdminUnlockedCommand, Box<dyn Error>> {
+        self.prompt("Choose a command:");
+        self.prompt(&AdminUnlockedCommand::Logout.to_string());
+        self.prompt(&AdminUnlockedCommand::ListProducts.to_string());
+        self.prompt(&AdminUnlockedCommand::ListSales.to_string());
+        self.prompt(&AdminUnlockedCommand::VoidSale.to_string());
+        self.prompt(&AdminUnlockedCommand::RecoveredPayments.to_string());
+        self.prompt(&AdminUnlockedCommand::ViewHistory.to_string());
+        self.prompt(&AdminUnlockedCommand::ViewSchedule.to_string());
+        self.prompt(&AdminUnlockedCommand::EditSchedule.to_string());
+        self.prompt(&AdminUnlockedCommand::ManageSuppliers.to_string());
+        self.prompt(&AdminUnlockedCommand::ManageAccounts.to_string());
+        self.prompt(&AdminUnlockedCommand::SensorReadings.to_string());
+        self.prompt(&AdminUnlockedCommand::WriteOffs.to_string());
+        self.prompt(&AdminUnlockedCommand::Faults.to_string());
+        self.prompt(&AdminUnlockedCommand::RunDiagnostics.to_string());
+        self.prompt(&AdminUnlockedCommand::CollectCash.to_string());
+        self.prompt(&AdminUnlockedCommand::ReconciliationReport.to_string());
+        self.prompt(&AdminUnlockedCommand::CloseDay.to_string());
+        self.prompt(&AdminUnlockedCommand::ListZReports.to_string());
+        self.prompt(&AdminUnlockedCommand::ExportZReport.to_string());
+        self.prompt(&AdminUnlockedCommand::Lock.to_string());
+        self.prompt(&AdminUnlockedCommand::EnterMaintenance.to_string());
+        self.prompt(&AdminUnlockedCommand::Exit.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        AdminUnlockedCommand::try_from(command.trim())
+    }
+
+    async fn lock(
+        self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> PromptPerspective {
+        match self
+            .vending_machine
+            .clone()
+            .lock(reason, expected_back)
+            .await
+        {
+            Ok(vending_machine) => {
+                PromptPerspective::AdminLocked(CliTerminal::<Admin, Locked> { vending_machine })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::AdminUnlocked(self)
+            }
+        }
+    }
+
+    async fn enter_maintenance(
+        self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> PromptPerspective {
+        match self
+            .vending_machine
+            .clone()
+            .enter_maintenance(reason, expected_back)
+            .await
+        {
+            Ok(vending_machine) => {
+                PromptPerspective::AdminMaintenance(CliTerminal::<Admin, Maintenance> {
+                    vending_machine,
+                })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::AdminUnlocked(self)
+            }
+        }
+    }
+}
+
+impl CliTerminal<Admin, Locked> {
+    pub async fn run(mut self) -> PromptPerspective {
+        loop {
+            match self.choose_command() {
+                Ok(AdminLockedCommand::Logout) => {
+                    return self.logout();
+                }
+                Ok(AdminLockedCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::ListSales) => match self.list_sales().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::VoidSale) => match self.void_sale().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::RecoveredPayments) => {
+                    match self.list_recovered_payments().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(AdminLockedCommand::ViewHistory) => match self.list_history().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::ViewSchedule) => match self.view_schedule().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::EditSchedule) => match self.edit_schedule().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::ManageSuppliers) => match self.manage_suppliers().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::ManageAccounts) => match self.manage_accounts().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::SensorReadings) => match self.list_sensor_readings().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::WriteOffs) => match self.manage_write_offs().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::Faults) => match self.manage_faults().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::RunDiagnostics) => match self.run_diagnostics().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::CollectCash) => match self.collect_cash().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::ReconciliationReport) => {
+                    match self.reconciliation_report().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(AdminLockedCommand::Unlock) => {
+                    return self.unlock().await;
+                }
+                Ok(AdminLockedCommand::EnterMaintenance) => match self.pre_lock() {
+                    Ok((reason, expected_back)) => {
+                        return self.enter_maintenance(reason, expected_back).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::CloseDay) => match self.close_day().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::ListZReports) => match self.list_z_reports().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::ExportZReport) => match self.export_z_report().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminLockedCommand::Exit) => match self.exit() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Err(e) => {
+                    self.prompt(&format!("Error: {}", e));
+                }
+            }
+            self.prompt("");
+        }
+    }
+
+    fn choose_command(&self) -> Result<AdminLockedCommand, Box<dyn Error>> {
+        self.prompt("Choose a command:");
+        self.prompt(&AdminLockedCommand::Logout.to_string());
+        self.prompt(&AdminLockedCommand::ListProducts.to_string());
+        self.prompt(&AdminLockedCommand::ListSales.to_string());
+        self.prompt(&AdminLockedCommand::VoidSale.to_string());
+        self.prompt(&AdminLockedCommand::RecoveredPayments.to_string());
+        self.prompt(&AdminLockedCommand::ViewHistory.to_string());
+        self.prompt(&AdminLockedCommand::ViewSchedule.to_string());
+        self.prompt(&AdminLockedCommand::EditSchedule.to_string());
+        self.prompt(&AdminLockedCommand::ManageSuppliers.to_string());
+        self.prompt(&AdminLockedCommand::ManageAccounts.to_string());
+        self.prompt(&AdminLockedCommand::SensorReadings.to_string());
+        self.prompt(&AdminLockedCommand::WriteOffs.to_string());
+        self.prompt(&AdminLockedCommand::Faults.to_string());
+        self.prompt(&AdminLockedCommand::RunDiagnostics.to_string());
+        self.prompt(&AdminLockedCommand::CollectCash.to_string());
+        self.prompt(&AdminLockedCommand::ReconciliationReport.to_string());
+        self.prompt(&AdminLockedCommand::CloseDay.to_string());
+        self.prompt(&AdminLockedCommand::ListZReports.to_string());
+        self.prompt(&AdminLockedCommand::ExportZReport.to_string());
+        self.prompt(&AdminLockedCommand::Unlock.to_string());
+        self.prompt(&AdminLockedCommand::EnterMaintenance.to_string());
+        self.prompt(&AdminLockedCommand::Exit.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        AdminLockedCommand::try_from(command.trim())
+    }
+
+    async fn unlock(self) -> PromptPerspective {
+        match self.vending_machine.clone().unlock().await {
+            Ok(vending_machine) => {
+                PromptPerspective::AdminUnlocked(CliTerminal::<Admin, Unlocked> { vending_machine })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::AdminLocked(self)
+            }
+        }
+    }
+
+    async fn enter_maintenance(
+        self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> PromptPerspective {
+        match self
+            .vending_machine
+            .clone()
+            .enter_maintenance(reason, expected_back)
+            .await
+        {
+            Ok(vending_machine) => {
+                PromptPerspective::AdminMaintenance(CliTerminal::<Admin, Maintenance> {
+                    vending_machine,
+                })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::AdminLocked(self)
+            }
+        }
+    }
+}
+
+impl CliTerminal<Admin, Maintenance> {
+    pub async fn run(mut self) -> PromptPerspective {
+        loop {
+            match self.choose_command() {
+                Ok(AdminMaintenanceCommand::Logout) => {
+                    return self.logout();
+                }
+                Ok(AdminMaintenanceCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::ListSales) => match self.list_sales().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::VoidSale) => match self.void_sale().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::RecoveredPayments) => {
+                    match self.list_recovered_payments().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(AdminMaintenanceCommand::ViewHistory) => match self.list_history().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::ViewSchedule) => match self.view_schedule().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::EditSchedule) => match self.edit_schedule().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::ManageSuppliers) => match self.manage_suppliers().await
+                {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::ManageAccounts) => match self.manage_accounts().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::SensorReadings) => {
+                    match self.list_sensor_readings().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(AdminMaintenanceCommand::WriteOffs) => match self.manage_write_offs().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::Faults) => match self.manage_faults().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::RunDiagnostics) => match self.run_diagnostics().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::CollectCash) => match self.collect_cash().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::ReconciliationReport) => {
+                    match self.reconciliation_report().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(AdminMaintenanceCommand::Unlock) => {
+                    return self.unlock().await;
+                }
+                Ok(AdminMaintenanceCommand::Lock) => match self.pre_lock() {
+                    Ok((reason, expected_back)) => {
+                        return self.lock(reason, expected_back).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::CloseDay) => match self.close_day().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::ListZReports) => match self.list_z_reports().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::ExportZReport) => match self.export_z_report().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(AdminMaintenanceCommand::Exit) => match self.exit() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Err(e) => {
+                    self.prompt(&format!("Error: {}", e));
+                }
+            }
+            self.prompt("");
+        }
+    }
+
+    fn choose_command(&self) -> Result<AdminMaintenanceCommand, Box<dyn Error>> {
+        self.prompt("Choose a command:");
+        self.prompt(&AdminMaintenanceCommand::Logout.to_string());
+        self.prompt(&AdminMaintenanceCommand::ListProducts.to_string());
+        self.prompt(&AdminMaintenanceCommand::ListSales.to_string());
+        self.prompt(&AdminMaintenanceCommand::VoidSale.to_string());
+        self.prompt(&AdminMaintenanceCommand::RecoveredPayments.to_string());
+        self.prompt(&AdminMaintenanceCommand::ViewHistory.to_string());
+        self.prompt(&AdminMaintenanceCommand::ViewSchedule.to_string());
+        self.prompt(&AdminMaintenanceCommand::EditSchedule.to_string());
+        self.prompt(&AdminMaintenanceCommand::ManageSuppliers.to_string());
+        self.prompt(&AdminMaintenanceCommand::ManageAccounts.to_string());
+        self.prompt(&AdminMaintenanceCommand::SensorReadings.to_string());
+        self.prompt(&AdminMaintenanceCommand::WriteOffs.to_string());
+        self.prompt(&AdminMaintenanceCommand::Faults.to_string());
+        self.prompt(&AdminMaintenanceCommand::RunDiagnostics.to_string());
+        self.prompt(&AdminMaintenanceCommand::CollectCash.to_string());
+        self.prompt(&AdminMaintenanceCommand::ReconciliationReport.to_string());
+        self.prompt(&AdminMaintenanceCommand::CloseDay.to_string());
+        self.prompt(&AdminMaintenanceCommand::ListZReports.to_string());
+        self.prompt(&AdminMaintenanceCommand::ExportZReport.to_string());
+        self.prompt(&AdminMaintenanceCommand::Unlock.to_string());
+        self.prompt(&AdminMaintenanceCommand::Lock.to_string());
+        self.prompt(&AdminMaintenanceCommand::Exit.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        AdminMaintenanceCommand::try_from(command.trim())
+    }
+
+    async fn unlock(self) -> PromptPerspective {
+        match self.vending_machine.clone().unlock().await {
+            Ok(vending_machine) => {
+                PromptPerspective::AdminUnlocked(CliTerminal::<Admin, Unlocked> { vending_machine })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::AdminMaintenance(self)
+            }
+        }
+    }
+
+    async fn lock(
+        self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> PromptPerspective {
+        match self
+            .vending_machine
+            .clone()
+            .lock(reason, expected_back)
+            .await
+        {
+            Ok(vending_machine) => {
+                PromptPerspective::AdminLocked(CliTerminal::<Admin, Locked> { vending_machine })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::AdminMaintenance(self)
+            }
+        }
+    }
+}
+
+impl<U: CanRestockOwnColumns, L: LockStatus> CliTerminal<U, L> {
+    async fn view_report(&self) -> Result<(), Box<dyn Error>> {
+        let report = self.vending_machine.supplier_report().await?;
+
+        self.prompt(&format!("Report for {}:", report.supplier.as_ref()));
+        self.prompt("Stock:");
+        for product in report.products {
+            self.prompt(&format!("{:?}", product));
+        }
+
+        self.prompt("Sales:");
+        for sale in report.sales {
+            self.prompt(&format_sale(&sale));
+        }
+
+        Ok(())
+    }
+}
+
+impl<U: Role, L: LockStatus> CliTerminal<U, L> {
+    fn pre_supplied_product(&self) -> Result<Product, Box<dyn Error>> {
+        self.prompt("Enter the product id:");
+        let mut product_id = String::new();
+        std::io::stdin().read_line(&mut product_id)?;
+
+        let product_id = Value::parse(product_id.trim())?;
+
+        self.prompt("Enter the product name:");
+        let mut product_name = String::new();
+        std::io::stdin().read_line(&mut product_name)?;
+
+        let product_name = Name::parse(product_name.trim())?;
+
+        self.prompt("Enter the category:");
+        let mut category = String::new();
+        std::io::stdin().read_line(&mut category)?;
+
+        let category = Category::parse(category.trim())?;
+
+        self.prompt("Enter the price:");
+        let mut price = String::new();
+        std::io::stdin().read_line(&mut price)?;
+
+        let price = Price::parse(price.trim())?;
+
+        self.prompt("Enter the quantity:");
+        let mut quantity = String::new();
+        std::io::stdin().read_line(&mut quantity)?;
+
+        let quantity = Quantity::parse(quantity.trim())?;
+
+        self.prompt("Is the product refrigerated? (y/N):");
+        let mut refrigerated = String::new();
+        std::io::stdin().read_line(&mut refrigerated)?;
+
+        let refrigerated = matches!(refrigerated.trim(), "y" | "Y");
+
+        Ok(Product {
+            column_id: product_id,
+            name: product_name,
+            category,
+            price,
+            quantity,
+            refrigerated,
+        })
+    }
+
+    fn pre_stock_count(&self) -> Result<(Value, Quantity), Box<dyn Error>> {
+        self.prompt("Enter the product id:");
+        let mut product_id = String::new();
+        std::io::stdin().read_line(&mut product_id)?;
+
+        let product_id = Value::parse(product_id.trim())?;
+
+        self.prompt("Enter the counted quantity:");
+        let mut quantity = String::new();
+        std::io::stdin().read_line(&mut quantity)?;
+
+        let quantity = Quantity::parse(quantity.trim())?;
+
+        Ok((product_id, quantity))
+    }
+}
+
+impl<U: CanRestock, L: Restockable> CliTerminal<U, L> {
+    async fn supply_product(&mut self) -> Result<(), Box<dyn Error>> {
+        let product = self.pre_supplied_product()?;
+
+        self.vending_machine.supply_product(product.clone()).await?;
+
+        self.prompt(&format!("Product supplied successfully: {:?}", product));
+
+        Ok(())
+    }
+}
+
+impl<U: CanRestockOwnColumns, L: Restockable> CliTerminal<U, L> {
+    async fn supply_own_product(&mut self) -> Result<(), Box<dyn Error>> {
+        let product = self.pre_supplied_product()?;
+
+        self.vending_machine
+            .supply_own_product(product.clone())
+            .await?;
+
+        self.prompt(&format!("Product supplied successfully: {:?}", product));
+
+        Ok(())
+    }
+}
+
+impl<U: CanRestock> CliTerminal<U, Maintenance> {
+    async fn adjust_stock(&mut self) -> Result<(), Box<dyn Error>> {
+        let (product_id, quantity) = self.pre_stock_count()?;
+
+        let product = self
+            .vending_machine
+            .adjust_stock(product_id, quantity)
+            .await?;
+
+        self.prompt(&format!("Stock adjusted successfully: {:?}", product));
+
+        Ok(())
+    }
+}
+
+impl<U: CanRestockOwnColumns> CliTerminal<U, Maintenance> {
+    async fn adjust_own_stock(&mut self) -> Result<(), Box<dyn Error>> {
+        let (product_id, quantity) = self.pre_stock_count()?;
+
+        let product = self
+            .vending_machine
+            .adjust_own_stock(product_id, quantity)
+            .await?;
+
+        self.prompt(&format!("Stock adjusted successfully: {:?}", product));
+
+        Ok(())
     }
 }
 
-impl CliTerminal<Guest, Unlocked> {
+impl CliTerminal<Supplier, Unlocked> {
     pub async fn run(mut self) -> PromptPerspective {
         loop {
             match self.choose_command() {
-                Ok(GuestUnlockedCommand::Login) => match self.pre_login() {
-                    Ok((username, password)) => {
-                        return self.login(username, password);
-                    }
+                Ok(SupplierUnlockedCommand::Logout) => {
+                    return self.logout();
+                }
+                Ok(SupplierUnlockedCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(GuestUnlockedCommand::ListProducts) => match self.list_products().await {
+                Ok(SupplierUnlockedCommand::ViewReport) => match self.view_report().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(GuestUnlockedCommand::BuyProduct) => match self.buy_product().await {
+                Ok(SupplierUnlockedCommand::SupplyProduct) => match self.supply_own_product().await
+                {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(GuestUnlockedCommand::Exit) => match self.exit() {
+                Ok(SupplierUnlockedCommand::Exit) => match self.exit() {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
@@ -158,75 +2607,41 @@ impl CliTerminal<Guest, Unlocked> {
         }
     }
 
-    fn choose_command(&self) -> Result<GuestUnlockedCommand, Box<dyn Error>> {
+    fn choose_command(&self) -> Result<SupplierUnlockedCommand, Box<dyn Error>> {
         self.prompt("Choose a command:");
-        self.prompt(&GuestUnlockedCommand::Login.to_string());
-        self.prompt(&GuestUnlockedCommand::ListProducts.to_string());
-        self.prompt(&GuestUnlockedCommand::BuyProduct.to_string());
-        self.prompt(&GuestUnlockedCommand::Exit.to_string());
+        self.prompt(&SupplierUnlockedCommand::Logout.to_string());
+        self.prompt(&SupplierUnlockedCommand::ListProducts.to_string());
+        self.prompt(&SupplierUnlockedCommand::ViewReport.to_string());
+        self.prompt(&SupplierUnlockedCommand::SupplyProduct.to_string());
+        self.prompt(&SupplierUnlockedCommand::Exit.to_string());
 
         let mut command = String::new();
         std::io::stdin().read_line(&mut command)?;
 
-        GuestUnlockedCommand::try_from(command.trim())
-    }
-
-    fn login(self, username: Name, password: Password) -> PromptPerspective {
-        match self.vending_machine.login(&username, &password) {
-            AuthResult::SuccessAdmin(vending_machine) => {
-                PromptPerspective::AdminUnlocked(CliTerminal::<Admin, Unlocked> { vending_machine })
-            }
-            AuthResult::SuccessSupplier(vending_machine) => {
-                PromptPerspective::SupplierUnlocked(CliTerminal::<Supplier, Unlocked> {
-                    vending_machine,
-                })
-            }
-            AuthResult::Failure(vending_machine) => {
-                PromptPerspective::GuestUnlocked(CliTerminal::<Guest, Unlocked> { vending_machine })
-            }
-        }
-    }
-
-    async fn buy_product(&mut self) -> Result<(), Box<dyn Error>> {
-        self.prompt("Enter the product id:");
-        let mut product_id = String::new();
-        std::io::stdin().read_line(&mut product_id)?;
-
-        let product_id = Value::parse(product_id.trim())?;
-
-        self.prompt("Enter the amount:");
-        let mut amount = String::new();
-        std::io::stdin().read_line(&mut amount)?;
-
-        let amount = Value::parse(amount.trim())?;
-
-        let product = self.vending_machine.buy(product_id, amount).await?;
-
-        self.prompt(&format!("Product bought successfully: {:?}", product));
-
-        Ok(())
+        SupplierUnlockedCommand::try_from(command.trim())
     }
 }
 
-impl CliTerminal<Guest, Locked> {
+impl CliTerminal<Supplier, Locked> {
     pub async fn run(self) -> PromptPerspective {
         loop {
             match self.choose_command() {
-                Ok(GuestLockedCommand::Login) => match self.pre_login() {
-                    Ok((username, password)) => {
-                        return self.login(username, password);
-                    }
+                Ok(SupplierLockedCommand::Logout) => {
+                    return self.logout();
+                }
+                Ok(SupplierLockedCommand::ListProducts) => match self.list_products().await {
+                    Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(GuestLockedCommand::ListProducts) => match self.list_products().await {
+                Ok(SupplierLockedCommand::ViewReport) => match self.view_report().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(GuestLockedCommand::Exit) => match self.exit() {
+                Ok(SupplierLockedCommand::Exit) => match self.exit() {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
@@ -240,58 +2655,56 @@ impl CliTerminal<Guest, Locked> {
         }
     }
 
-    fn choose_command(&self) -> Result<GuestLockedCommand, Box<dyn Error>> {
+    fn choose_command(&self) -> Result<SupplierLockedCommand, Box<dyn Error>> {
         self.prompt("Choose a command:");
-        self.prompt(&GuestLockedCommand::Login.to_string());
-        self.prompt(&GuestLockedCommand::ListProducts.to_string());
-        self.prompt(&GuestLockedCommand::Exit.to_string());
+        self.prompt(&SupplierLockedCommand::Logout.to_string());
+        self.prompt(&SupplierLockedCommand::ListProducts.to_string());
+        self.prompt(&SupplierLockedCommand::ViewReport.to_string());
+        self.prompt(&SupplierLockedCommand::Exit.to_string());
 
         let mut command = String::new();
         std::io::stdin().read_line(&mut command)?;
 
-        GuestLockedCommand::try_from(command.trim())
-    }
-
-    fn login(self, username: Name, password: Password) -> PromptPerspective {
-        match self.vending_machine.login(&username, &password) {
-            AuthResult::SuccessAdmin(vending_machine) => {
-                PromptPerspective::AdminLocked(CliTerminal::<Admin, Locked> { vending_machine })
-            }
-            AuthResult::SuccessSupplier(vending_machine) => {
-                PromptPerspective::SupplierLocked(CliTerminal::<Supplier, Locked> {
-                    vending_machine,
-                })
-            }
-            AuthResult::Failure(vending_machine) => {
-                PromptPerspective::GuestLocked(CliTerminal::<Guest, Locked> { vending_machine })
-            }
-        }
+        SupplierLockedCommand::try_from(command.trim())
     }
 }
 
-impl CliTerminal<Admin, Unlocked> {
-    pub async fn run(self) -> PromptPerspective {
+impl CliTerminal<Supplier, Maintenance> {
+    pub async fn run(mut self) -> PromptPerspective {
         loop {
             match self.choose_command() {
-                Ok(AdminUnlockedCommand::Logout) => {
+                Ok(SupplierMaintenanceCommand::Logout) => {
                     return self.logout();
                 }
-                Ok(AdminUnlockedCommand::ListProducts) => match self.list_products().await {
+                Ok(SupplierMaintenanceCommand::ListProducts) => match self.list_products().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(AdminUnlockedCommand::ListSales) => match self.list_sales().await {
+                Ok(SupplierMaintenanceCommand::ViewReport) => match self.view_report().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(AdminUnlockedCommand::Lock) => {
-                    return self.lock();
+                Ok(SupplierMaintenanceCommand::SupplyProduct) => {
+                    match self.supply_own_product().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
                 }
-                Ok(AdminUnlockedCommand::Exit) => match self.exit() {
+                Ok(SupplierMaintenanceCommand::AdjustStock) => {
+                    match self.adjust_own_stock().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(SupplierMaintenanceCommand::Exit) => match self.exit() {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
@@ -305,50 +2718,150 @@ impl CliTerminal<Admin, Unlocked> {
         }
     }
 
-    fn choose_command(&self) -> Result<AdminUnlockedCommand, Box<dyn Error>> {
+    fn choose_command(&self) -> Result<SupplierMaintenanceCommand, Box<dyn Error>> {
         self.prompt("Choose a command:");
-        self.prompt(&AdminUnlockedCommand::Logout.to_string());
-        self.prompt(&AdminUnlockedCommand::ListProducts.to_string());
-        self.prompt(&AdminUnlockedCommand::ListSales.to_string());
-        self.prompt(&AdminUnlockedCommand::Lock.to_string());
-        self.prompt(&AdminUnlockedCommand::Exit.to_string());
+        self.prompt(&SupplierMaintenanceCommand::Logout.to_string());
+        self.prompt(&SupplierMaintenanceCommand::ListProducts.to_string());
+        self.prompt(&SupplierMaintenanceCommand::ViewReport.to_string());
+        self.prompt(&SupplierMaintenanceCommand::SupplyProduct.to_string());
+        self.prompt(&SupplierMaintenanceCommand::AdjustStock.to_string());
+        self.prompt(&SupplierMaintenanceCommand::Exit.to_string());
 
         let mut command = String::new();
         std::io::stdin().read_line(&mut command)?;
 
-        AdminUnlockedCommand::try_from(command.trim())
+        SupplierMaintenanceCommand::try_from(command.trim())
     }
+}
 
-    fn lock(self) -> PromptPerspective {
-        PromptPerspective::AdminLocked(CliTerminal::<Admin, Locked> {
-            vending_machine: self.vending_machine.lock(),
-        })
+impl<U: CanManageFaults, L: LockStatus> CliTerminal<U, L> {
+    async fn manage_faults(&mut self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Choose an action:");
+        self.prompt(&FaultsCommand::ListActive.to_string());
+        self.prompt(&FaultsCommand::ListAll.to_string());
+        self.prompt(&FaultsCommand::Acknowledge.to_string());
+        self.prompt(&FaultsCommand::Clear.to_string());
+        self.prompt(&FaultsCommand::ListCodes.to_string());
+
+        let mut command = String::new();
+        std::io::stdin().read_line(&mut command)?;
+
+        match FaultsCommand::try_from(command.trim())? {
+            FaultsCommand::ListActive => {
+                self.prompt("Active faults:");
+                for fault in self.vending_machine.list_faults().await {
+                    if fault.is_active() {
+                        self.prompt(&format_fault(&fault));
+                    }
+                }
+            }
+            FaultsCommand::ListAll => {
+                self.prompt("Fault log:");
+                for fault in self.vending_machine.list_faults().await {
+                    self.prompt(&format_fault(&fault));
+                }
+            }
+            FaultsCommand::Acknowledge => {
+                let fault_id = self.pre_fault()?;
+                let fault = self.vending_machine.acknowledge_fault(fault_id).await?;
+
+                self.prompt(&format!("Fault acknowledged: {}", format_fault(&fault)));
+            }
+            FaultsCommand::Clear => {
+                let fault_id = self.pre_fault()?;
+                let fault = self.vending_machine.clear_fault(fault_id).await?;
+
+                self.prompt(&format!("Fault cleared: {}", format_fault(&fault)));
+            }
+            FaultsCommand::ListCodes => {
+                self.prompt("Fault codes:");
+                for code in FaultCode::ALL {
+                    self.prompt(&format!(
+                        "{} [{}] {}",
+                        code.as_ref(),
+                        code.category().as_ref(),
+                        code.description()
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pre_fault(&self) -> Result<Value, Box<dyn Error>> {
+        self.prompt("Enter the fault id:");
+        let mut fault_id = String::new();
+        std::io::stdin().read_line(&mut fault_id)?;
+
+        Value::parse(fault_id.trim())
     }
 }
 
-impl CliTerminal<Admin, Locked> {
-    pub async fn run(self) -> PromptPerspective {
+impl<U: CanDiagnose, L: LockStatus> CliTerminal<U, L> {
+    async fn run_diagnostics(&mut self) -> Result<(), Box<dyn Error>> {
+        self.prompt("Running diagnostics...");
+        let checks = self.vending_machine.run_diagnostics().await?;
+
+        self.prompt("Diagnostics:");
+        for check in checks.iter() {
+            let status = match check.passed {
+                true => "PASS",
+                false => "FAIL",
+            };
+            self.prompt(&format!("[{}] {}: {}", status, check.name, check.details));
+        }
+
+        let passed = checks.iter().filter(|check| check.passed).count();
+        self.prompt(&format!(
+            "Result: {} ({}/{} checks passed)",
+            match passed == checks.len() {
+                true => "PASS",
+                false => "FAIL",
+            },
+            passed,
+            checks.len()
+        ));
+
+        Ok(())
+    }
+}
+
+impl CliTerminal<Technician, Unlocked> {
+    pub async fn run(mut self) -> PromptPerspective {
         loop {
             match self.choose_command() {
-                Ok(AdminLockedCommand::Logout) => {
+                Ok(TechnicianUnlockedCommand::Logout) => {
                     return self.logout();
                 }
-                Ok(AdminLockedCommand::ListProducts) => match self.list_products().await {
+                Ok(TechnicianUnlockedCommand::ListProducts) => match self.list_products().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(AdminLockedCommand::ListSales) => match self.list_sales().await {
+                Ok(TechnicianUnlockedCommand::Faults) => match self.manage_faults().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(AdminLockedCommand::Unlock) => {
-                    return self.unlock();
-                }
-                Ok(AdminLockedCommand::Exit) => match self.exit() {
+                Ok(TechnicianUnlockedCommand::RunDiagnostics) => match self.run_diagnostics().await
+                {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(TechnicianUnlockedCommand::TakeOutOfService) => match self.pre_lock() {
+                    Ok((reason, expected_back)) => {
+                        return self.take_out_of_service(reason, expected_back).await;
+                    }
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(TechnicianUnlockedCommand::Exit) => match self.exit() {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
@@ -362,47 +2875,71 @@ impl CliTerminal<Admin, Locked> {
         }
     }
 
-    fn choose_command(&self) -> Result<AdminLockedCommand, Box<dyn Error>> {
+    fn choose_command(&self) -> Result<TechnicianUnlockedCommand, Box<dyn Error>> {
         self.prompt("Choose a command:");
-        self.prompt(&AdminLockedCommand::Logout.to_string());
-        self.prompt(&AdminLockedCommand::ListProducts.to_string());
-        self.prompt(&AdminLockedCommand::ListSales.to_string());
-        self.prompt(&AdminLockedCommand::Unlock.to_string());
-        self.prompt(&AdminLockedCommand::Exit.to_string());
+        self.prompt(&TechnicianUnlockedCommand::Logout.to_string());
+        self.prompt(&TechnicianUnlockedCommand::ListProducts.to_string());
+        self.prompt(&TechnicianUnlockedCommand::Faults.to_string());
+        self.prompt(&TechnicianUnlockedCommand::RunDiagnostics.to_string());
+        self.prompt(&TechnicianUnlockedCommand::TakeOutOfService.to_string());
+        self.prompt(&TechnicianUnlockedCommand::Exit.to_string());
 
         let mut command = String::new();
         std::io::stdin().read_line(&mut command)?;
 
-        AdminLockedCommand::try_from(command.trim())
+        TechnicianUnlockedCommand::try_from(command.trim())
     }
 
-    fn unlock(self) -> PromptPerspective {
-        PromptPerspective::AdminUnlocked(CliTerminal::<Admin, Unlocked> {
-            vending_machine: self.vending_machine.unlock(),
-        })
+    async fn take_out_of_service(
+        self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> PromptPerspective {
+        match self
+            .vending_machine
+            .clone()
+            .take_out_of_service(reason, expected_back)
+            .await
+        {
+            Ok(vending_machine) => {
+                PromptPerspective::TechnicianMaintenance(CliTerminal::<Technician, Maintenance> {
+                    vending_machine,
+                })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::TechnicianUnlocked(self)
+            }
+        }
     }
 }
 
-impl CliTerminal<Supplier, Unlocked> {
+impl CliTerminal<Technician, Locked> {
     pub async fn run(mut self) -> PromptPerspective {
         loop {
             match self.choose_command() {
-                Ok(SupplierUnlockedCommand::Logout) => {
+                Ok(TechnicianLockedCommand::Logout) => {
                     return self.logout();
                 }
-                Ok(SupplierUnlockedCommand::ListProducts) => match self.list_products().await {
+                Ok(TechnicianLockedCommand::ListProducts) => match self.list_products().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(SupplierUnlockedCommand::SupplyProduct) => match self.supply_product().await {
+                Ok(TechnicianLockedCommand::Faults) => match self.manage_faults().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(SupplierUnlockedCommand::Exit) => match self.exit() {
+                Ok(TechnicianLockedCommand::RunDiagnostics) => match self.run_diagnostics().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(TechnicianLockedCommand::Exit) => match self.exit() {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
@@ -416,73 +2953,68 @@ impl CliTerminal<Supplier, Unlocked> {
         }
     }
 
-    fn choose_command(&self) -> Result<SupplierUnlockedCommand, Box<dyn Error>> {
+    fn choose_command(&self) -> Result<TechnicianLockedCommand, Box<dyn Error>> {
         self.prompt("Choose a command:");
-        self.prompt(&SupplierUnlockedCommand::Logout.to_string());
-        self.prompt(&SupplierUnlockedCommand::ListProducts.to_string());
-        self.prompt(&SupplierUnlockedCommand::SupplyProduct.to_string());
-        self.prompt(&SupplierUnlockedCommand::Exit.to_string());
+        self.prompt(&TechnicianLockedCommand::Logout.to_string());
+        self.prompt(&TechnicianLockedCommand::ListProducts.to_string());
+        self.prompt(&TechnicianLockedCommand::Faults.to_string());
+        self.prompt(&TechnicianLockedCommand::RunDiagnostics.to_string());
+        self.prompt(&TechnicianLockedCommand::Exit.to_string());
 
         let mut command = String::new();
         std::io::stdin().read_line(&mut command)?;
 
-        SupplierUnlockedCommand::try_from(command.trim())
-    }
-
-    async fn supply_product(&mut self) -> Result<(), Box<dyn Error>> {
-        self.prompt("Enter the product id:");
-        let mut product_id = String::new();
-        std::io::stdin().read_line(&mut product_id)?;
-
-        let product_id = Value::parse(product_id.trim())?;
-
-        self.prompt("Enter the product name:");
-        let mut product_name = String::new();
-        std::io::stdin().read_line(&mut product_name)?;
-
-        let product_name = Name::parse(product_name.trim())?;
-
-        self.prompt("Enter the price:");
-        let mut price = String::new();
-        std::io::stdin().read_line(&mut price)?;
-
-        let price = Price::parse(price.trim())?;
-
-        self.prompt("Enter the quantity:");
-        let mut quantity = String::new();
-        std::io::stdin().read_line(&mut quantity)?;
-
-        let quantity = Value::parse(quantity.trim())?;
-
-        let product = Product {
-            column_id: product_id,
-            name: product_name,
-            price,
-            quantity,
-        };
-
-        self.vending_machine.supply_product(product.clone()).await?;
-
-        self.prompt(&format!("Product supplied successfully: {:?}", product));
-
-        Ok(())
+        TechnicianLockedCommand::try_from(command.trim())
     }
 }
 
-impl CliTerminal<Supplier, Locked> {
-    pub async fn run(self) -> PromptPerspective {
+impl CliTerminal<Technician, Maintenance> {
+    pub async fn run(mut self) -> PromptPerspective {
         loop {
             match self.choose_command() {
-                Ok(SupplierLockedCommand::Logout) => {
+                Ok(TechnicianMaintenanceCommand::Logout) => {
                     return self.logout();
                 }
-                Ok(SupplierLockedCommand::ListProducts) => match self.list_products().await {
+                Ok(TechnicianMaintenanceCommand::ListProducts) => {
+                    match self.list_products().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(TechnicianMaintenanceCommand::Faults) => match self.manage_faults().await {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
                     }
                 },
-                Ok(SupplierLockedCommand::Exit) => match self.exit() {
+                Ok(TechnicianMaintenanceCommand::RunDiagnostics) => {
+                    match self.run_diagnostics().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(TechnicianMaintenanceCommand::SupplyProduct) => {
+                    match self.supply_product().await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.prompt(&format!("Error: {}", e));
+                        }
+                    }
+                }
+                Ok(TechnicianMaintenanceCommand::AdjustStock) => match self.adjust_stock().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.prompt(&format!("Error: {}", e));
+                    }
+                },
+                Ok(TechnicianMaintenanceCommand::ReturnToService) => {
+                    return self.return_to_service().await;
+                }
+                Ok(TechnicianMaintenanceCommand::Exit) => match self.exit() {
                     Ok(_) => {}
                     Err(e) => {
                         self.prompt(&format!("Error: {}", e));
@@ -496,15 +3028,34 @@ impl CliTerminal<Supplier, Locked> {
         }
     }
 
-    fn choose_command(&self) -> Result<SupplierLockedCommand, Box<dyn Error>> {
+    fn choose_command(&self) -> Result<TechnicianMaintenanceCommand, Box<dyn Error>> {
         self.prompt("Choose a command:");
-        self.prompt(&SupplierLockedCommand::Logout.to_string());
-        self.prompt(&SupplierLockedCommand::ListProducts.to_string());
-        self.prompt(&SupplierLockedCommand::Exit.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::Logout.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::ListProducts.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::Faults.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::RunDiagnostics.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::SupplyProduct.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::AdjustStock.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::ReturnToService.to_string());
+        self.prompt(&TechnicianMaintenanceCommand::Exit.to_string());
 
         let mut command = String::new();
         std::io::stdin().read_line(&mut command)?;
 
-        SupplierLockedCommand::try_from(command.trim())
+        TechnicianMaintenanceCommand::try_from(command.trim())
+    }
+
+    async fn return_to_service(self) -> PromptPerspective {
+        match self.vending_machine.clone().return_to_service().await {
+            Ok(vending_machine) => {
+                PromptPerspective::TechnicianUnlocked(CliTerminal::<Technician, Unlocked> {
+                    vending_machine,
+                })
+            }
+            Err(e) => {
+                self.prompt(&format!("Error: {}", e));
+                PromptPerspective::TechnicianMaintenance(self)
+            }
+        }
     }
 }
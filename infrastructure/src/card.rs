@@ -0,0 +1,118 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use yadir::core::contracts::DIBuilder;
+use yadir::{deps, DIBuilder};
+
+use vending_machine::domain::entities::{CardAuthorization, CardNumber, Price};
+use vending_machine::domain::interfaces::CardProcessor;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum AuthorizationStatus {
+    Authorized,
+    Captured,
+    Voided,
+}
+
+#[derive(Clone, DIBuilder)]
+#[build_as(Box<dyn CardProcessor>)]
+#[build_method("default")]
+pub struct SimulatedCardProcessor {
+    online: bool,
+    authorizations: Arc<Mutex<Vec<(String, AuthorizationStatus)>>>,
+}
+
+impl Default for SimulatedCardProcessor {
+    fn default() -> Self {
+        Self {
+            online: std::env::var("CARD_PROCESSOR_ONLINE").as_deref() != Ok("false"),
+            authorizations: Arc::default(),
+        }
+    }
+}
+
+impl SimulatedCardProcessor {
+    const DECLINED_SUFFIX: &'static str = "0002";
+    const TIMEOUT_SUFFIX: &'static str = "0003";
+    const LATENCY: Duration = Duration::from_millis(200);
+    const TIMEOUT_LATENCY: Duration = Duration::from_secs(60);
+
+    fn transition(
+        &self,
+        authorization: &CardAuthorization,
+        status: AuthorizationStatus,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut authorizations = self
+            .authorizations
+            .lock()
+            .map_err(|_| "Card processor state is unavailable")?;
+
+        let (_, current) = authorizations
+            .iter_mut()
+            .find(|(id, _)| id == &authorization.id)
+            .ok_or("Unknown authorization")?;
+
+        if *current != AuthorizationStatus::Authorized {
+            return Err(Box::from("Authorization was already settled"));
+        }
+
+        *current = status;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CardProcessor for SimulatedCardProcessor {
+    async fn is_online(&self) -> bool {
+        self.online
+    }
+
+    async fn authorize(
+        &self,
+        card: &CardNumber,
+        amount: Price,
+    ) -> Result<CardAuthorization, Box<dyn std::error::Error>> {
+        if card.as_ref().ends_with(Self::TIMEOUT_SUFFIX) {
+            tokio::time::sleep(Self::TIMEOUT_LATENCY).await;
+        } else {
+            tokio::time::sleep(Self::LATENCY).await;
+        }
+
+        if card.as_ref().ends_with(Self::DECLINED_SUFFIX) {
+            return Err(Box::from("Card declined"));
+        }
+
+        let mut authorizations = self
+            .authorizations
+            .lock()
+            .map_err(|_| "Card processor state is unavailable")?;
+
+        let id = format!("auth-{}", authorizations.len() + 1);
+        authorizations.push((id.clone(), AuthorizationStatus::Authorized));
+
+        Ok(CardAuthorization {
+            id,
+            card: card.masked(),
+            amount,
+        })
+    }
+
+    async fn capture(
+        &self,
+        authorization: &CardAuthorization,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        tokio::time::sleep(Self::LATENCY).await;
+
+        self.transition(authorization, AuthorizationStatus::Captured)
+    }
+
+    async fn void(
+        &self,
+        authorization: &CardAuthorization,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        tokio::time::sleep(Self::LATENCY).await;
+
+        self.transition(authorization, AuthorizationStatus::Voided)
+    }
+}
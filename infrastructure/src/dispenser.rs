@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rand::Rng;
+use yadir::core::contracts::DIBuilder;
+use yadir::{deps, DIBuilder};
+
+use vending_machine::domain::entities::{FaultCode, Value, VendOutcome};
+use vending_machine::domain::interfaces::Dispenser;
+
+#[derive(Clone, DIBuilder)]
+#[build_as(Box<dyn Dispenser>)]
+#[build_method("default")]
+pub struct SimulatedDispenser {
+    jam_rate: f64,
+    no_drop_rate: f64,
+}
+
+impl Default for SimulatedDispenser {
+    fn default() -> Self {
+        Self::new(
+            Self::rate_from_env("DISPENSER_JAM_RATE"),
+            Self::rate_from_env("DISPENSER_NO_DROP_RATE"),
+        )
+    }
+}
+
+impl SimulatedDispenser {
+    const MOTOR_LATENCY: Duration = Duration::from_millis(300);
+
+    pub fn new(jam_rate: f64, no_drop_rate: f64) -> Self {
+        Self {
+            jam_rate: jam_rate.clamp(0.0, 1.0),
+            no_drop_rate: no_drop_rate.clamp(0.0, 1.0),
+        }
+    }
+
+    fn rate_from_env(key: &str) -> f64 {
+        std::env::var(key)
+            .ok()
+            .and_then(|rate| rate.parse::<f64>().ok())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Dispenser for SimulatedDispenser {
+    async fn vend(
+        &self,
+        _column_id: &Value,
+        quantity: &Value,
+    ) -> Result<VendOutcome, Box<dyn std::error::Error>> {
+        for dropped in 0..quantity.as_value() {
+            tokio::time::sleep(Self::MOTOR_LATENCY).await;
+
+            let roll = rand::thread_rng().gen::<f64>();
+            let fault = match roll {
+                roll if roll < self.jam_rate => FaultCode::MotorJammed,
+                roll if roll < self.jam_rate + self.no_drop_rate => FaultCode::NoDropDetected,
+                _ => continue,
+            };
+
+            return Ok(match (dropped, fault) {
+                (0, FaultCode::MotorJammed) => VendOutcome::MotorJammed,
+                (0, _) => VendOutcome::NoDropDetected,
+                (dropped, fault) => VendOutcome::PartiallyDispensed(dropped, fault),
+            });
+        }
+
+        Ok(VendOutcome::Dispensed)
+    }
+
+    async fn self_test(&self, _column_id: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        tokio::time::sleep(Self::MOTOR_LATENCY).await;
+
+        if rand::thread_rng().gen_bool(self.jam_rate) {
+            return Err(Box::from("Motor jammed"));
+        }
+
+        Ok(())
+    }
+}
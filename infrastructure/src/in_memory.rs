@@ -1,43 +1,98 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
 use async_trait::async_trait;
 use yadir::core::contracts::DIBuilder;
 use yadir::deps;
 use yadir::DIBuilder;
 
-use vending_machine::domain::entities::{Product, Sale, Value};
-use vending_machine::domain::interfaces::{ProductRepository, SaleRepository};
+use chrono::{DateTime, Utc};
+use vending_machine::domain::entities::{
+    CashCollection, CustomerAccount, Fault, MachineEvent, MachineState, Name, PaymentJournalEntry,
+    Product, Quantity, Sale, Schedule, SensorKind, SensorReading, StockMovement, SupplierAccount,
+    Value, WriteOffFlag, ZReport,
+};
+use vending_machine::domain::interfaces::{
+    CashCollectionRepository, CreditRepository, CustomerAccountRepository, FaultLogRepository,
+    MachineHistoryRepository, MachineStateRepository, PaymentJournalRepository, ProductRepository,
+    SaleRepository, ScheduleRepository, SensorReadingRepository, StockMovementRepository,
+    SupplierRepository, WriteOffRepository, ZReportRepository,
+};
+use vending_machine::domain::queries::ProductQuery;
+
+/// State behind an in-memory repository. Clones share it, the same way clones
+/// of a SQLite repository share the connection pool.
+struct Shared<T>(Arc<Mutex<T>>);
+
+impl<T: Default> Default for Shared<T> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(T::default())))
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> Result<MutexGuard<'_, T>, Box<dyn std::error::Error>> {
+        self.0
+            .lock()
+            .map_err(|_| Box::from("In-memory store is unavailable"))
+    }
+
+    fn read<R: Default>(&self, read: impl FnOnce(&T) -> R) -> R {
+        self.0.lock().map(|state| read(&state)).unwrap_or_default()
+    }
+}
 
 #[derive(Default, Clone, DIBuilder)]
 #[build_as(Box<dyn ProductRepository>)]
 #[build_method("default")]
 pub struct InMemoryProductRepository {
-    products: Vec<Product>,
+    products: Shared<Vec<Product>>,
 }
 
 #[async_trait]
 impl ProductRepository for InMemoryProductRepository {
     async fn find(&self, column_id: Value) -> Option<Product> {
-        self.products
-            .iter()
-            .find(|product| product.column_id == column_id)
-            .cloned()
+        self.products.read(|products| {
+            products
+                .iter()
+                .find(|product| product.column_id == column_id)
+                .cloned()
+        })
     }
 
     async fn save(&mut self, product: Product) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(index) = self
-            .products
+        let mut products = self.products.lock()?;
+        if let Some(index) = products
             .iter()
             .position(|p| p.column_id == product.column_id)
         {
-            self.products[index] = product;
+            products[index] = product;
         } else {
-            self.products.push(product);
+            products.push(product);
         }
 
         Ok(())
     }
 
     async fn find_all(&self) -> Vec<Product> {
-        self.products.clone()
+        self.products.read(Vec::clone)
+    }
+
+    async fn search(&self, query: &ProductQuery) -> Vec<Product> {
+        let mut products: Vec<Product> = self
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|product| query.matches(product))
+            .collect();
+        query.sort(&mut products);
+
+        products
     }
 }
 
@@ -45,17 +100,452 @@ impl ProductRepository for InMemoryProductRepository {
 #[build_as(Box<dyn SaleRepository>)]
 #[build_method("default")]
 pub struct InMemorySaleRepository {
-    sales: Vec<Sale>,
+    sales: Shared<Vec<Sale>>,
+    products: InMemoryProductRepository,
+    stock_movements: InMemoryStockMovementRepository,
+}
+
+impl InMemorySaleRepository {
+    pub fn new(
+        products: InMemoryProductRepository,
+        stock_movements: InMemoryStockMovementRepository,
+    ) -> Self {
+        Self {
+            sales: Shared::default(),
+            products,
+            stock_movements,
+        }
+    }
 }
 
 #[async_trait]
 impl SaleRepository for InMemorySaleRepository {
-    async fn save(&mut self, sale: Sale) -> Result<(), Box<dyn std::error::Error>> {
-        self.sales.push(sale);
-        Ok(())
+    async fn find(&self, id: Value) -> Option<Sale> {
+        self.sales.read(|sales| {
+            sales
+                .iter()
+                .find(|sale| sale.id.as_ref() == Some(&id))
+                .cloned()
+        })
+    }
+
+    async fn save(&mut self, sale: Sale) -> Result<Sale, Box<dyn std::error::Error>> {
+        let mut sales = self.sales.lock()?;
+        let sale = Sale {
+            id: Some(Value::parse_i32(sales.len() as i32 + 1)?),
+            ..sale
+        };
+
+        sales.push(sale.clone());
+        Ok(sale)
+    }
+
+    async fn record(
+        &mut self,
+        sale: Sale,
+        movement: StockMovement,
+    ) -> Result<Sale, Box<dyn std::error::Error>> {
+        {
+            let mut products = self.products.products.lock()?;
+            let product = products
+                .iter_mut()
+                .find(|product| product.column_id == sale.column_id)
+                .ok_or("Product not found")?;
+
+            product.quantity =
+                Quantity::parse_i32(product.quantity.as_value() as i32 + movement.delta)
+                    .map_err(|_| "Insufficient quantity in stock")?;
+        }
+        self.stock_movements.save(movement).await?;
+
+        self.save(sale).await
     }
 
     async fn find_all(&self) -> Vec<Sale> {
-        self.sales.clone()
+        self.sales.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn MachineStateRepository>)]
+#[build_method("default")]
+pub struct InMemoryMachineStateRepository {
+    state: Shared<Option<MachineState>>,
+}
+
+#[async_trait]
+impl MachineStateRepository for InMemoryMachineStateRepository {
+    async fn load(&self) -> Result<Option<MachineState>, Box<dyn std::error::Error>> {
+        Ok(self.state.lock()?.clone())
+    }
+
+    async fn save(&mut self, state: MachineState) -> Result<(), Box<dyn std::error::Error>> {
+        *self.state.lock()? = Some(state);
+        Ok(())
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn MachineHistoryRepository>)]
+#[build_method("default")]
+pub struct InMemoryMachineHistoryRepository {
+    events: Shared<Vec<MachineEvent>>,
+}
+
+#[async_trait]
+impl MachineHistoryRepository for InMemoryMachineHistoryRepository {
+    async fn save(&mut self, event: MachineEvent) -> Result<(), Box<dyn std::error::Error>> {
+        self.events.lock()?.push(event);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<MachineEvent> {
+        self.events.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn ScheduleRepository>)]
+#[build_method("default")]
+pub struct InMemoryScheduleRepository {
+    schedule: Shared<Schedule>,
+}
+
+#[async_trait]
+impl ScheduleRepository for InMemoryScheduleRepository {
+    async fn load(&self) -> Schedule {
+        self.schedule.read(Schedule::clone)
+    }
+
+    async fn save(&mut self, schedule: Schedule) -> Result<(), Box<dyn std::error::Error>> {
+        *self.schedule.lock()? = schedule;
+        Ok(())
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn SupplierRepository>)]
+#[build_method("default")]
+pub struct InMemorySupplierRepository {
+    suppliers: Shared<Vec<SupplierAccount>>,
+}
+
+#[async_trait]
+impl SupplierRepository for InMemorySupplierRepository {
+    async fn find(&self, name: &Name) -> Option<SupplierAccount> {
+        self.suppliers.read(|suppliers| {
+            suppliers
+                .iter()
+                .find(|account| account.name.as_ref() == name.as_ref())
+                .cloned()
+        })
+    }
+
+    async fn save(&mut self, account: SupplierAccount) -> Result<(), Box<dyn std::error::Error>> {
+        let mut suppliers = self.suppliers.lock()?;
+        suppliers.retain(|existing| existing.name.as_ref() != account.name.as_ref());
+        suppliers.push(account);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<SupplierAccount> {
+        self.suppliers.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn CashCollectionRepository>)]
+#[build_method("default")]
+pub struct InMemoryCashCollectionRepository {
+    collections: Shared<Vec<CashCollection>>,
+}
+
+#[async_trait]
+impl CashCollectionRepository for InMemoryCashCollectionRepository {
+    async fn save(&mut self, collection: CashCollection) -> Result<(), Box<dyn std::error::Error>> {
+        self.collections.lock()?.push(collection);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<CashCollection> {
+        self.collections.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn StockMovementRepository>)]
+#[build_method("default")]
+pub struct InMemoryStockMovementRepository {
+    movements: Shared<Vec<StockMovement>>,
+}
+
+#[async_trait]
+impl StockMovementRepository for InMemoryStockMovementRepository {
+    async fn save(&mut self, movement: StockMovement) -> Result<(), Box<dyn std::error::Error>> {
+        self.movements.lock()?.push(movement);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<StockMovement> {
+        self.movements.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn ZReportRepository>)]
+#[build_method("default")]
+pub struct InMemoryZReportRepository {
+    reports: Shared<Vec<ZReport>>,
+}
+
+#[async_trait]
+impl ZReportRepository for InMemoryZReportRepository {
+    async fn save(&mut self, report: ZReport) -> Result<(), Box<dyn std::error::Error>> {
+        let mut reports = self.reports.lock()?;
+        if reports
+            .iter()
+            .any(|existing| existing.number == report.number)
+        {
+            return Err(Box::from("Z-reports are immutable"));
+        }
+
+        reports.push(report);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<ZReport> {
+        self.reports.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn CustomerAccountRepository>)]
+#[build_method("default")]
+pub struct InMemoryCustomerAccountRepository {
+    accounts: Shared<Vec<CustomerAccount>>,
+}
+
+#[async_trait]
+impl CustomerAccountRepository for InMemoryCustomerAccountRepository {
+    async fn find(&self, badge_id: &Name) -> Option<CustomerAccount> {
+        self.accounts.read(|accounts| {
+            accounts
+                .iter()
+                .find(|account| account.badge_id.as_ref() == badge_id.as_ref())
+                .cloned()
+        })
+    }
+
+    async fn save(&mut self, account: CustomerAccount) -> Result<(), Box<dyn std::error::Error>> {
+        let mut accounts = self.accounts.lock()?;
+        accounts.retain(|existing| existing.badge_id.as_ref() != account.badge_id.as_ref());
+        accounts.push(account);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<CustomerAccount> {
+        self.accounts.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn CreditRepository>)]
+#[build_method("default")]
+pub struct InMemoryCreditRepository {
+    credits: Shared<Vec<(Value, f32)>>,
+}
+
+#[async_trait]
+impl CreditRepository for InMemoryCreditRepository {
+    async fn load(&self, transaction_id: &Value) -> f32 {
+        self.credits.read(|credits| {
+            credits
+                .iter()
+                .find(|(id, _)| id == transaction_id)
+                .map(|(_, credit)| *credit)
+                .unwrap_or(0.0)
+        })
+    }
+
+    async fn save(
+        &mut self,
+        transaction_id: &Value,
+        credit: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut credits = self.credits.lock()?;
+        credits.retain(|(id, _)| id != transaction_id);
+        if credit > 0.0 {
+            credits.push((transaction_id.clone(), credit));
+        }
+        Ok(())
+    }
+
+    async fn carry_over(
+        &mut self,
+        from: &Value,
+        to: &Value,
+        credit: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut credits = self.credits.lock()?;
+        if credits.iter().any(|(id, _)| id == to) {
+            return Err(Box::from("The transaction already holds credit"));
+        }
+
+        credits.retain(|(id, _)| id != from);
+        if credit > 0.0 {
+            credits.push((to.clone(), credit));
+        }
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<(Value, f32)> {
+        self.credits.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn PaymentJournalRepository>)]
+#[build_method("default")]
+pub struct InMemoryPaymentJournalRepository {
+    entries: Shared<Vec<PaymentJournalEntry>>,
+    last_transaction_id: Shared<i32>,
+}
+
+#[async_trait]
+impl PaymentJournalRepository for InMemoryPaymentJournalRepository {
+    async fn next_transaction_id(&self) -> Result<Value, Box<dyn std::error::Error>> {
+        let mut last = self.last_transaction_id.lock()?;
+        *last += 1;
+
+        Value::parse_i32(*last)
+    }
+
+    async fn save(&mut self, entry: PaymentJournalEntry) -> Result<(), Box<dyn std::error::Error>> {
+        self.entries.lock()?.push(entry);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<PaymentJournalEntry> {
+        self.entries.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn SensorReadingRepository>)]
+#[build_method("default")]
+pub struct InMemorySensorReadingRepository {
+    readings: Shared<Vec<SensorReading>>,
+}
+
+#[async_trait]
+impl SensorReadingRepository for InMemorySensorReadingRepository {
+    async fn save(&mut self, reading: SensorReading) -> Result<(), Box<dyn std::error::Error>> {
+        self.readings.lock()?.push(reading);
+        Ok(())
+    }
+
+    async fn latest(&self, kind: &SensorKind) -> Option<SensorReading> {
+        self.readings.read(|readings| {
+            readings
+                .iter()
+                .rev()
+                .find(|reading| reading.kind == *kind)
+                .cloned()
+        })
+    }
+
+    async fn excursion_started_at(&self, kind: &SensorKind) -> Option<DateTime<Utc>> {
+        self.readings.read(|readings| {
+            readings
+                .iter()
+                .rev()
+                .filter(|reading| reading.kind == *kind)
+                .take_while(|reading| reading.alarm)
+                .last()
+                .map(|reading| reading.date)
+        })
+    }
+
+    async fn find_all(&self) -> Vec<SensorReading> {
+        self.readings.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn WriteOffRepository>)]
+#[build_method("default")]
+pub struct InMemoryWriteOffRepository {
+    flags: Shared<Vec<WriteOffFlag>>,
+}
+
+#[async_trait]
+impl WriteOffRepository for InMemoryWriteOffRepository {
+    async fn find(&self, column_id: &Value) -> Option<WriteOffFlag> {
+        self.flags.read(|flags| {
+            flags
+                .iter()
+                .find(|flag| flag.column_id == *column_id)
+                .cloned()
+        })
+    }
+
+    async fn save(&mut self, flag: WriteOffFlag) -> Result<(), Box<dyn std::error::Error>> {
+        let mut flags = self.flags.lock()?;
+        flags.retain(|existing| existing.column_id != flag.column_id);
+        flags.push(flag);
+        Ok(())
+    }
+
+    async fn remove(&mut self, column_id: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        self.flags
+            .lock()?
+            .retain(|flag| flag.column_id != *column_id);
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<WriteOffFlag> {
+        self.flags.read(Vec::clone)
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn FaultLogRepository>)]
+#[build_method("default")]
+pub struct InMemoryFaultLogRepository {
+    faults: Shared<Vec<Fault>>,
+}
+
+#[async_trait]
+impl FaultLogRepository for InMemoryFaultLogRepository {
+    async fn find(&self, id: &Value) -> Option<Fault> {
+        self.faults.read(|faults| {
+            faults
+                .iter()
+                .find(|fault| fault.id.as_ref() == Some(id))
+                .cloned()
+        })
+    }
+
+    async fn save(&mut self, fault: Fault) -> Result<Fault, Box<dyn std::error::Error>> {
+        let mut faults = self.faults.lock()?;
+        if let Some(existing) = faults
+            .iter_mut()
+            .find(|existing| existing.id.is_some() && existing.id == fault.id)
+        {
+            *existing = fault.clone();
+            return Ok(fault);
+        }
+
+        let fault = Fault {
+            id: Some(Value::parse_i32(faults.len() as i32 + 1)?),
+            ..fault
+        };
+
+        faults.push(fault.clone());
+        Ok(fault)
+    }
+
+    async fn find_all(&self) -> Vec<Fault> {
+        self.faults.read(Vec::clone)
     }
 }
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use chrono::{Local, Utc};
+use vending_machine::application::states::{Guest, Unlocked};
+use vending_machine::application::VendingMachine;
+use vending_machine::domain::entities::{ColdChainPolicy, RecoveryAction};
+
+use crate::sensors::{SimulatedDoorSensor, SimulatedTemperatureSensor};
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(30);
+const SENSOR_INTERVAL: Duration = Duration::from_secs(10);
+
+pub async fn start(
+    vending_machine: VendingMachine<Guest, Unlocked>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let recovery_action =
+        RecoveryAction::parse(&std::env::var("PAYMENT_RECOVERY").unwrap_or("refund".to_string()))?;
+    for transaction in vending_machine
+        .clone()
+        .into_payment_recovery()?
+        .run(recovery_action)
+        .await?
+    {
+        if let Some((action, amount)) = transaction.recovery() {
+            println!(
+                "Recovered incomplete payment #{} by {} of {:.2}",
+                transaction.transaction_id.as_value(),
+                action.as_ref(),
+                amount
+            );
+        }
+    }
+
+    let policy = ColdChainPolicy::new(
+        env_or("COLD_CHAIN_MIN_TEMPERATURE", 2.0),
+        env_or("COLD_CHAIN_MAX_TEMPERATURE", 8.0),
+        chrono::Duration::minutes(env_or("COLD_CHAIN_WRITE_OFF_MINUTES", 120)),
+    )?;
+    let sensor_monitor = vending_machine.clone().into_sensor_monitor(
+        vec![
+            Box::new(SimulatedTemperatureSensor::default()),
+            Box::new(SimulatedDoorSensor::default()),
+        ],
+        policy,
+    )?;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SENSOR_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sensor_monitor.tick(Utc::now()).await {
+                eprintln!("Sensor monitor error: {}", e);
+            }
+        }
+    });
+
+    let scheduler = vending_machine.into_scheduler()?;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = scheduler.tick(Local::now()).await {
+                eprintln!("Scheduler error: {}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.parse::<T>().ok())
+        .unwrap_or(default)
+}
@@ -1,2 +1,8 @@
+pub mod card;
+pub mod dispenser;
 pub mod in_memory;
+pub mod jobs;
+pub mod mdb;
+pub mod receipts;
+pub mod sensors;
 pub mod sqlite;
@@ -0,0 +1,769 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use vending_machine::domain::entities::{PaymentMethod, Price};
+use vending_machine::domain::interfaces::{PaymentTerminal, Terminal};
+
+pub const COIN_CHANGER: u8 = 0x08;
+pub const BILL_VALIDATOR: u8 = 0x30;
+
+const RESET: u8 = 0x00;
+const SETUP: u8 = 0x01;
+const TUBE_STATUS: u8 = 0x02;
+const POLL: u8 = 0x03;
+const TYPE: u8 = 0x04;
+const DISPENSE: u8 = 0x05;
+const ESCROW: u8 = 0x05;
+
+const ACK: u8 = 0x00;
+const NAK: u8 = 0xFF;
+
+const COIN_JUST_RESET: u8 = 0x0B;
+const COIN_CASH_BOX: u8 = 0b00;
+const COIN_TUBES: u8 = 0b01;
+const BILL_JUST_RESET: u8 = 0x06;
+const BILL_STACKED: u8 = 0b000;
+const BILL_ESCROWED: u8 = 0b001;
+const BILL_RETURNED: u8 = 0b010;
+
+const TUBE_CAPACITY: u8 = 50;
+const MAX_DISPENSE_COUNT: u8 = 15;
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+fn encode_frame(bytes: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(bytes.len() + 2);
+    frame.push(bytes.len() as u8);
+    frame.extend_from_slice(bytes);
+    frame.push(checksum(bytes));
+    frame
+}
+
+async fn read_frame_body<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    length: u8,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut body = vec![0u8; length as usize + 1];
+    stream.read_exact(&mut body).await?;
+
+    let received = body.pop().unwrap_or_default();
+    if checksum(&body) != received {
+        return Err(Box::from("MDB frame checksum mismatch"));
+    }
+
+    Ok(body)
+}
+
+fn value_to_cents(credit: u8, scaling: u16, decimal_places: u8) -> u32 {
+    let cents = credit as u32 * scaling as u32;
+    match decimal_places {
+        0 => cents * 100,
+        1 => cents * 10,
+        2 => cents,
+        places => cents / 10u32.pow(places as u32 - 2),
+    }
+}
+
+pub trait MdbStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<S: AsyncRead + AsyncWrite + Unpin + Send> MdbStream for S {}
+
+pub async fn open_device(path: &str) -> std::io::Result<impl MdbStream> {
+    let reader = tokio::fs::File::open(path).await?;
+    let writer = tokio::fs::OpenOptions::new().write(true).open(path).await?;
+
+    Ok(tokio::io::join(reader, writer))
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Response {
+    Ack,
+    Data(Vec<u8>),
+}
+
+#[derive(Clone, Debug, Default)]
+struct CoinChangerSetup {
+    coin_values: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct BillValidatorSetup {
+    bill_values: Vec<u32>,
+}
+
+struct MdbBus {
+    stream: Box<dyn MdbStream>,
+    coin_changer: Option<CoinChangerSetup>,
+    bill_validator: Option<BillValidatorSetup>,
+}
+
+impl MdbBus {
+    const RESPONSE_TIMEOUT: Duration = Duration::from_millis(500);
+    const RETRIES: usize = 3;
+
+    async fn exchange(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: &[u8],
+    ) -> Result<Response, Box<dyn std::error::Error>> {
+        let mut bytes = vec![address | command];
+        bytes.extend_from_slice(data);
+        let frame = encode_frame(&bytes);
+
+        let mut last_error = String::from("MDB peripheral did not respond");
+        for _ in 0..Self::RETRIES {
+            self.stream.write_all(&frame).await?;
+            self.stream.flush().await?;
+
+            match tokio::time::timeout(Self::RESPONSE_TIMEOUT, self.read_response()).await {
+                Ok(Ok(Some(response))) => return Ok(response),
+                Ok(Ok(None)) => last_error = String::from("MDB peripheral rejected the command"),
+                Ok(Err(e)) => last_error = e.to_string(),
+                Err(_) => last_error = String::from("MDB peripheral did not respond"),
+            }
+        }
+
+        Err(Box::from(last_error))
+    }
+
+    async fn read_response(&mut self) -> Result<Option<Response>, Box<dyn std::error::Error>> {
+        match self.stream.read_u8().await? {
+            ACK => Ok(Some(Response::Ack)),
+            NAK => Ok(None),
+            length => {
+                let data = read_frame_body(&mut self.stream, length).await?;
+                self.stream.write_u8(ACK).await?;
+                self.stream.flush().await?;
+                Ok(Some(Response::Data(data)))
+            }
+        }
+    }
+
+    async fn command(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match self.exchange(address, command, data).await? {
+            Response::Ack => Ok(()),
+            Response::Data(_) => Err(Box::from("Unexpected MDB response")),
+        }
+    }
+
+    async fn query(
+        &mut self,
+        address: u8,
+        command: u8,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        match self.exchange(address, command, &[]).await? {
+            Response::Data(data) => Ok(data),
+            Response::Ack => Err(Box::from("MDB peripheral returned no data")),
+        }
+    }
+
+    async fn poll(&mut self, address: u8) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        match self.exchange(address, POLL, &[]).await? {
+            Response::Data(data) => Ok(data),
+            Response::Ack => Ok(vec![]),
+        }
+    }
+
+    async fn ensure_ready(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.coin_changer.is_none() {
+            self.command(COIN_CHANGER, RESET, &[]).await?;
+            self.poll(COIN_CHANGER).await?;
+
+            let setup = self.query(COIN_CHANGER, SETUP).await?;
+            if setup.len() < 7 {
+                return Err(Box::from("Invalid coin changer setup"));
+            }
+
+            let (scaling, decimal_places) = (setup[3] as u16, setup[4]);
+            self.coin_changer = Some(CoinChangerSetup {
+                coin_values: setup[7..]
+                    .iter()
+                    .map(|credit| value_to_cents(*credit, scaling, decimal_places))
+                    .collect(),
+            });
+        }
+
+        if self.bill_validator.is_none() {
+            self.command(BILL_VALIDATOR, RESET, &[]).await?;
+            self.poll(BILL_VALIDATOR).await?;
+
+            let setup = self.query(BILL_VALIDATOR, SETUP).await?;
+            if setup.len() < 11 {
+                return Err(Box::from("Invalid bill validator setup"));
+            }
+
+            let (scaling, decimal_places) = (u16::from_be_bytes([setup[3], setup[4]]), setup[5]);
+            self.bill_validator = Some(BillValidatorSetup {
+                bill_values: setup[11..]
+                    .iter()
+                    .map(|credit| value_to_cents(*credit, scaling, decimal_places))
+                    .collect(),
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn enable(&mut self, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let mask = match enabled {
+            true => 0xFFFFu16.to_be_bytes(),
+            false => [0, 0],
+        };
+
+        self.command(COIN_CHANGER, TYPE, &[mask[0], mask[1], 0, 0])
+            .await?;
+        self.command(BILL_VALIDATOR, TYPE, &[mask[0], mask[1], mask[0], mask[1]])
+            .await
+    }
+
+    async fn tube_counts(&mut self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let status = self.query(COIN_CHANGER, TUBE_STATUS).await?;
+        Ok(status.get(2..).unwrap_or_default().to_vec())
+    }
+
+    async fn poll_credit(&mut self) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+        let mut credit = 0;
+
+        let activity = self.poll(COIN_CHANGER).await?;
+        let mut index = 0;
+        while index < activity.len() {
+            let status = activity[index];
+            let routing = (status >> 4) & 0b11;
+            if status & 0b1100_0000 == 0b0100_0000 && matches!(routing, COIN_CASH_BOX | COIN_TUBES)
+            {
+                let coin_type = (status & 0x0F) as usize;
+                credit += self
+                    .coin_changer
+                    .as_ref()
+                    .and_then(|setup| setup.coin_values.get(coin_type))
+                    .ok_or("Unknown coin type")?;
+            }
+
+            index += match status & 0b1100_0000 {
+                0b0000_0000 => 1,
+                _ => 2,
+            };
+        }
+
+        let activity = self.poll(BILL_VALIDATOR).await?;
+        for activity in activity {
+            if activity & 0b1000_0000 == 0 {
+                continue;
+            }
+
+            let bill_type = (activity & 0x0F) as usize;
+            match (activity >> 4) & 0b111 {
+                BILL_ESCROWED => self.command(BILL_VALIDATOR, ESCROW, &[0x01]).await?,
+                BILL_STACKED => {
+                    credit += self
+                        .bill_validator
+                        .as_ref()
+                        .and_then(|setup| setup.bill_values.get(bill_type))
+                        .ok_or("Unknown bill type")?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(match credit {
+            0 => None,
+            credit => Some(credit),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct MdbPaymentTerminal {
+    bus: Arc<Mutex<MdbBus>>,
+}
+
+impl MdbPaymentTerminal {
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    const INSERT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub fn new(stream: impl MdbStream + 'static) -> Self {
+        Self {
+            bus: Arc::new(Mutex::new(MdbBus {
+                stream: Box::new(stream),
+                coin_changer: None,
+                bill_validator: None,
+            })),
+        }
+    }
+
+    async fn await_credit(bus: &mut MdbBus) -> Result<u32, Box<dyn std::error::Error>> {
+        let deadline = tokio::time::Instant::now() + Self::INSERT_TIMEOUT;
+
+        loop {
+            if let Some(credit) = bus.poll_credit().await? {
+                return Ok(credit);
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(Box::from("No coins or bills were inserted"));
+            }
+
+            tokio::time::sleep(Self::POLL_INTERVAL).await;
+        }
+    }
+
+    fn payout_plan(
+        coin_values: &[u32],
+        tube_counts: &[u8],
+        cents: u32,
+    ) -> Result<Vec<(u8, u8)>, Box<dyn std::error::Error>> {
+        let mut coin_types = coin_values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value > 0)
+            .collect::<Vec<_>>();
+        coin_types.sort_by(|a, b| b.1.cmp(a.1));
+
+        let mut remaining = cents;
+        let mut plan = Vec::new();
+        for (coin_type, value) in coin_types {
+            let available = tube_counts.get(coin_type).copied().unwrap_or_default() as u32;
+            let count = (remaining / value).min(available);
+            if count > 0 {
+                plan.push((coin_type as u8, count as u8));
+                remaining -= count * value;
+            }
+        }
+
+        match remaining {
+            0 => Ok(plan),
+            _ => Err(Box::from("Not enough coins in the tubes to pay out")),
+        }
+    }
+}
+
+impl Terminal for MdbPaymentTerminal {}
+
+#[async_trait]
+impl PaymentTerminal for MdbPaymentTerminal {
+    fn method(&self) -> PaymentMethod {
+        PaymentMethod::Cash
+    }
+
+    async fn is_available(&self) -> bool {
+        self.bus.lock().await.ensure_ready().await.is_ok()
+    }
+
+    async fn request(&self, due: Price) -> Result<Price, Box<dyn std::error::Error>> {
+        let mut bus = self.bus.lock().await;
+        bus.ensure_ready().await?;
+
+        self.prompt(&format!(
+            "Please insert coins or bills ({:.2} due)",
+            due.as_value()
+        ));
+        bus.enable(true).await?;
+        let credit = Self::await_credit(&mut bus)
+            .await
+            .map_err(|e| e.to_string());
+        bus.enable(false).await?;
+
+        Price::parse_f32(credit? as f32 / 100.0)
+    }
+
+    async fn refund(&self, amount: Price) -> Result<(), Box<dyn std::error::Error>> {
+        let mut bus = self.bus.lock().await;
+        bus.ensure_ready().await?;
+
+        let coin_values = bus
+            .coin_changer
+            .as_ref()
+            .map(|setup| setup.coin_values.clone())
+            .unwrap_or_default();
+        let tube_counts = bus.tube_counts().await?;
+        let cents = (amount.as_value() * 100.0).round() as u32;
+
+        let plan = Self::payout_plan(&coin_values, &tube_counts, cents)?;
+        for (coin_type, count) in plan {
+            let mut left = count;
+            while left > 0 {
+                let batch = left.min(MAX_DISPENSE_COUNT);
+                bus.command(COIN_CHANGER, DISPENSE, &[(batch << 4) | coin_type])
+                    .await?;
+                left -= batch;
+            }
+        }
+
+        self.prompt(&format!("Here's your refund: {:.2}", amount.as_value()));
+        Ok(())
+    }
+
+    async fn self_test(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut bus = self.bus.lock().await;
+        bus.ensure_ready().await?;
+
+        bus.enable(true).await?;
+        bus.enable(false).await?;
+        bus.tube_counts().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct SimulatorState {
+    coin_credits: Vec<u8>,
+    bill_credits: Vec<u8>,
+    tube_counts: Vec<u8>,
+    coins_enabled: u16,
+    bills_enabled: u16,
+    coin_activity: VecDeque<Vec<u8>>,
+    bill_activity: VecDeque<u8>,
+    escrowed: Option<u8>,
+    dispensed: Vec<(u8, u8)>,
+}
+
+#[derive(Clone)]
+pub struct MdbPeripheralSimulator {
+    state: Arc<std::sync::Mutex<SimulatorState>>,
+}
+
+impl Default for MdbPeripheralSimulator {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(std::sync::Mutex::new(SimulatorState {
+                coin_credits: vec![1, 2, 4, 10, 20, 40],
+                bill_credits: vec![5, 10, 20],
+                tube_counts: vec![20, 20, 20, 20, 10, 10],
+                coins_enabled: 0,
+                bills_enabled: 0,
+                coin_activity: VecDeque::new(),
+                bill_activity: VecDeque::new(),
+                escrowed: None,
+                dispensed: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl MdbPeripheralSimulator {
+    const COIN_SCALING: u8 = 5;
+    const COIN_DECIMAL_PLACES: u8 = 2;
+    const BILL_SCALING: u16 = 100;
+    const BILL_DECIMAL_PLACES: u8 = 2;
+    const COUNTRY_CODE: [u8; 2] = [0x19, 0x78];
+
+    fn state(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, SimulatorState>, Box<dyn std::error::Error>> {
+        self.state
+            .lock()
+            .map_err(|_| Box::from("MDB simulator state is unavailable"))
+    }
+
+    pub fn insert_coin(&self, coin_type: u8) -> Result<(), Box<dyn std::error::Error>> {
+        let mut state = self.state()?;
+        if coin_type as usize >= state.coin_credits.len() {
+            return Err(Box::from("Unknown coin type"));
+        }
+        if state.coins_enabled & (1 << coin_type) == 0 {
+            return Err(Box::from("Coin returned: acceptance is disabled"));
+        }
+
+        let count = state.tube_counts[coin_type as usize];
+        let routing = match count < TUBE_CAPACITY {
+            true => {
+                state.tube_counts[coin_type as usize] += 1;
+                COIN_TUBES
+            }
+            false => COIN_CASH_BOX,
+        };
+
+        let count = state.tube_counts[coin_type as usize];
+        state
+            .coin_activity
+            .push_back(vec![0b0100_0000 | (routing << 4) | coin_type, count]);
+        Ok(())
+    }
+
+    pub fn insert_bill(&self, bill_type: u8) -> Result<(), Box<dyn std::error::Error>> {
+        let mut state = self.state()?;
+        if bill_type as usize >= state.bill_credits.len() {
+            return Err(Box::from("Unknown bill type"));
+        }
+        if state.bills_enabled & (1 << bill_type) == 0 {
+            return Err(Box::from("Bill returned: acceptance is disabled"));
+        }
+        if state.escrowed.is_some() {
+            return Err(Box::from("Bill returned: escrow is occupied"));
+        }
+
+        state.escrowed = Some(bill_type);
+        state
+            .bill_activity
+            .push_back(0b1000_0000 | (BILL_ESCROWED << 4) | bill_type);
+        Ok(())
+    }
+
+    pub fn tube_counts(&self) -> Vec<u8> {
+        self.state()
+            .map(|state| state.tube_counts.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn dispensed(&self) -> Vec<(u8, u8)> {
+        self.state()
+            .map(|state| state.dispensed.clone())
+            .unwrap_or_default()
+    }
+
+    fn handle(&self, bytes: &[u8]) -> Result<Response, Box<dyn std::error::Error>> {
+        let (header, data) = bytes.split_first().ok_or("Empty MDB frame")?;
+        let (address, command) = (header & 0xF8, header & 0x07);
+        let mut state = self.state()?;
+
+        match (address, command) {
+            (COIN_CHANGER, RESET) => {
+                state.coins_enabled = 0;
+                state.coin_activity.clear();
+                state.coin_activity.push_back(vec![COIN_JUST_RESET]);
+                Ok(Response::Ack)
+            }
+            (COIN_CHANGER, SETUP) => {
+                let mut setup = vec![3, Self::COUNTRY_CODE[0], Self::COUNTRY_CODE[1]];
+                setup.push(Self::COIN_SCALING);
+                setup.push(Self::COIN_DECIMAL_PLACES);
+                setup.extend_from_slice(&0xFFFFu16.to_be_bytes());
+                setup.extend_from_slice(&state.coin_credits);
+                Ok(Response::Data(setup))
+            }
+            (COIN_CHANGER, TUBE_STATUS) => {
+                let full = state
+                    .tube_counts
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, count)| **count >= TUBE_CAPACITY)
+                    .fold(0u16, |mask, (coin_type, _)| mask | (1 << coin_type));
+                let mut status = full.to_be_bytes().to_vec();
+                status.extend_from_slice(&state.tube_counts);
+                Ok(Response::Data(status))
+            }
+            (COIN_CHANGER, POLL) => Ok(match state.coin_activity.is_empty() {
+                true => Response::Ack,
+                false => Response::Data(state.coin_activity.drain(..).flatten().collect()),
+            }),
+            (COIN_CHANGER, TYPE) if data.len() == 4 => {
+                state.coins_enabled = u16::from_be_bytes([data[0], data[1]]);
+                Ok(Response::Ack)
+            }
+            (COIN_CHANGER, DISPENSE) if data.len() == 1 => {
+                let (count, coin_type) = (data[0] >> 4, data[0] & 0x0F);
+                let tube = state
+                    .tube_counts
+                    .get_mut(coin_type as usize)
+                    .ok_or("Unknown coin type")?;
+                if *tube < count {
+                    return Err(Box::from("Not enough coins in the tube"));
+                }
+                *tube -= count;
+                state.dispensed.push((coin_type, count));
+                Ok(Response::Ack)
+            }
+            (BILL_VALIDATOR, RESET) => {
+                state.bills_enabled = 0;
+                state.bill_activity.clear();
+                state.escrowed = None;
+                state.bill_activity.push_back(BILL_JUST_RESET);
+                Ok(Response::Ack)
+            }
+            (BILL_VALIDATOR, SETUP) => {
+                let mut setup = vec![1, Self::COUNTRY_CODE[0], Self::COUNTRY_CODE[1]];
+                setup.extend_from_slice(&Self::BILL_SCALING.to_be_bytes());
+                setup.push(Self::BILL_DECIMAL_PLACES);
+                setup.extend_from_slice(&500u16.to_be_bytes());
+                setup.extend_from_slice(&[0, 0]);
+                setup.push(0xFF);
+                setup.extend_from_slice(&state.bill_credits);
+                Ok(Response::Data(setup))
+            }
+            (BILL_VALIDATOR, POLL) => Ok(match state.bill_activity.is_empty() {
+                true => Response::Ack,
+                false => Response::Data(state.bill_activity.drain(..).collect()),
+            }),
+            (BILL_VALIDATOR, TYPE) if data.len() == 4 => {
+                state.bills_enabled = u16::from_be_bytes([data[0], data[1]]);
+                Ok(Response::Ack)
+            }
+            (BILL_VALIDATOR, ESCROW) if data.len() == 1 => {
+                let bill_type = state.escrowed.take().ok_or("No bill in escrow")?;
+                let routing = match data[0] {
+                    0x01 => BILL_STACKED,
+                    _ => BILL_RETURNED,
+                };
+                state
+                    .bill_activity
+                    .push_back(0b1000_0000 | (routing << 4) | bill_type);
+                Ok(Response::Ack)
+            }
+            _ => Err(Box::from("Unsupported MDB command")),
+        }
+    }
+
+    pub async fn serve(
+        &self,
+        mut stream: impl MdbStream,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        loop {
+            let length = match stream.read_u8().await {
+                Ok(ACK) => continue,
+                Ok(length) => length,
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+                Err(e) => return Err(Box::new(e)),
+            };
+
+            let reply = match read_frame_body(&mut stream, length).await {
+                Ok(bytes) => match self.handle(&bytes) {
+                    Ok(Response::Ack) => vec![ACK],
+                    Ok(Response::Data(data)) => encode_frame(&data),
+                    Err(_) => vec![NAK],
+                },
+                Err(_) => vec![NAK],
+            };
+
+            stream.write_all(&reply).await?;
+            stream.flush().await?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COIN_REJECTED: u8 = 0b11;
+
+    fn connect() -> (MdbPaymentTerminal, MdbPeripheralSimulator) {
+        let (controller, peripheral) = tokio::io::duplex(256);
+        let simulator = MdbPeripheralSimulator::default();
+
+        let server = simulator.clone();
+        tokio::spawn(async move { server.serve(peripheral).await.map_err(|e| e.to_string()) });
+
+        (MdbPaymentTerminal::new(controller), simulator)
+    }
+
+    async fn until_accepted(insert: impl Fn() -> Result<(), Box<dyn std::error::Error>>) {
+        for _ in 0..50 {
+            if insert().is_ok() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("the terminal never enabled acceptance");
+    }
+
+    fn price(value: f32) -> Price {
+        Price::parse_f32(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn credits_a_coin_routed_to_the_tubes() {
+        let (terminal, simulator) = connect();
+
+        let request = tokio::spawn({
+            let terminal = terminal.clone();
+            async move {
+                terminal
+                    .request(price(1.0))
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+        });
+        until_accepted(|| simulator.insert_coin(3)).await;
+
+        assert_eq!(request.await.unwrap().unwrap().as_value(), 0.5);
+        assert_eq!(simulator.tube_counts()[3], 21);
+    }
+
+    #[tokio::test]
+    async fn ignores_rejected_coins() {
+        let (terminal, simulator) = connect();
+
+        let request = tokio::spawn({
+            let terminal = terminal.clone();
+            async move {
+                terminal
+                    .request(price(1.0))
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+        });
+        until_accepted(|| {
+            let mut state = simulator.state()?;
+            if state.coins_enabled == 0 {
+                return Err(Box::from("Coin acceptance is disabled"));
+            }
+            state
+                .coin_activity
+                .push_back(vec![0b0100_0000 | (COIN_REJECTED << 4) | 5, 0]);
+            Ok(())
+        })
+        .await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        simulator.insert_coin(0).unwrap();
+
+        assert_eq!(request.await.unwrap().unwrap().as_value(), 0.05);
+    }
+
+    #[tokio::test]
+    async fn stacks_an_escrowed_bill_before_crediting_it() {
+        let (terminal, simulator) = connect();
+
+        let request = tokio::spawn({
+            let terminal = terminal.clone();
+            async move {
+                terminal
+                    .request(price(2.0))
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+        });
+        until_accepted(|| simulator.insert_bill(0)).await;
+
+        assert_eq!(request.await.unwrap().unwrap().as_value(), 5.0);
+        assert!(simulator.state().unwrap().escrowed.is_none());
+    }
+
+    #[tokio::test]
+    async fn refunds_with_the_largest_coins_first() {
+        let (terminal, simulator) = connect();
+
+        terminal.refund(price(2.55)).await.unwrap();
+
+        assert_eq!(simulator.dispensed(), vec![(5, 1), (3, 1), (0, 1)]);
+        assert_eq!(simulator.tube_counts(), vec![19, 20, 20, 19, 10, 9]);
+    }
+
+    #[test]
+    fn payout_plan_falls_back_to_smaller_coins_when_tubes_run_low() {
+        let plan =
+            MdbPaymentTerminal::payout_plan(&[5, 10, 20, 50, 100, 200], &[4, 5, 0, 1, 0, 0], 95);
+
+        assert_eq!(plan.unwrap(), vec![(3, 1), (1, 4), (0, 1)]);
+    }
+
+    #[test]
+    fn payout_plan_fails_when_the_tubes_cannot_cover_the_amount() {
+        let plan = MdbPaymentTerminal::payout_plan(&[5, 10], &[1, 1], 20);
+
+        assert!(plan.is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::Local;
+use serde_json::json;
+use yadir::core::contracts::DIBuilder;
+use yadir::{deps, DIBuilder};
+
+use vending_machine::domain::entities::Receipt;
+use vending_machine::domain::interfaces::ReceiptPrinter;
+
+const RECEIPT_WIDTH: usize = 32;
+
+fn receipt_line(label: &str, amount: f32) -> String {
+    let amount = format!("{:.2}", amount);
+    let padding = RECEIPT_WIDTH
+        .saturating_sub(label.len() + amount.len())
+        .max(1);
+
+    format!("{}{}{}", label, " ".repeat(padding), amount)
+}
+
+fn render_text(receipt: &Receipt) -> Vec<String> {
+    let mut lines = vec![
+        format!("Receipt #{}", receipt.transaction_id.as_value()),
+        receipt
+            .date
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        "-".repeat(RECEIPT_WIDTH),
+    ];
+
+    for item in receipt.items.iter() {
+        lines.push(receipt_line(
+            &format!(
+                "{} x{} @ {:.2}",
+                item.product_name.as_ref(),
+                item.quantity.as_value(),
+                item.unit_price.as_value()
+            ),
+            item.unit_price.as_value() * item.quantity.as_value() as f32,
+        ));
+
+        if item.discount > 0.0 {
+            lines.push(receipt_line("  Discount", -item.discount));
+        }
+    }
+
+    lines.push("-".repeat(RECEIPT_WIDTH));
+    lines.push(receipt_line("Total", receipt.total()));
+    lines.push(receipt_line("Paid", receipt.amount_paid));
+    lines.push(receipt_line("Change", receipt.change));
+
+    if receipt.credit > 0.0 {
+        lines.push(receipt_line("Credit", receipt.credit));
+    }
+
+    lines
+}
+
+#[derive(Clone, DIBuilder)]
+#[build_as(Box<dyn ReceiptPrinter>)]
+pub struct TextReceiptPrinter;
+
+impl ReceiptPrinter for TextReceiptPrinter {
+    fn print(&self, receipt: &Receipt) -> Result<(), Box<dyn std::error::Error>> {
+        for line in render_text(receipt) {
+            println!("{}", line);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, DIBuilder)]
+#[build_as(Box<dyn ReceiptPrinter>)]
+#[build_method("default")]
+pub struct EscPosReceiptPrinter {
+    path: PathBuf,
+}
+
+impl EscPosReceiptPrinter {
+    const INITIALIZE: [u8; 2] = [0x1B, 0x40];
+    const BOLD_ON: [u8; 3] = [0x1B, 0x45, 0x01];
+    const BOLD_OFF: [u8; 3] = [0x1B, 0x45, 0x00];
+    const FEED_LINES: [u8; 3] = [0x1B, 0x64, 0x04];
+    const CUT: [u8; 3] = [0x1D, 0x56, 0x00];
+
+    pub fn new(path: PathBuf) -> Self {
+        EscPosReceiptPrinter { path }
+    }
+
+    fn encode(receipt: &Receipt) -> Vec<u8> {
+        let mut bytes = Self::INITIALIZE.to_vec();
+
+        for (index, line) in render_text(receipt).into_iter().enumerate() {
+            if index == 0 {
+                bytes.extend_from_slice(&Self::BOLD_ON);
+            }
+
+            bytes.extend(
+                line.chars()
+                    .map(|c| if c.is_ascii() { c as u8 } else { b'?' }),
+            );
+            bytes.push(b'\n');
+
+            if index == 0 {
+                bytes.extend_from_slice(&Self::BOLD_OFF);
+            }
+        }
+
+        bytes.extend_from_slice(&Self::FEED_LINES);
+        bytes.extend_from_slice(&Self::CUT);
+
+        bytes
+    }
+}
+
+impl Default for EscPosReceiptPrinter {
+    fn default() -> Self {
+        let path = std::env::var("RECEIPT_PRINTER_PATH").unwrap_or("receipts.escpos".to_string());
+
+        EscPosReceiptPrinter::new(PathBuf::from(path))
+    }
+}
+
+impl ReceiptPrinter for EscPosReceiptPrinter {
+    fn print(&self, receipt: &Receipt) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        file.write_all(&Self::encode(receipt))?;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, DIBuilder)]
+#[build_as(Box<dyn ReceiptPrinter>)]
+pub struct JsonReceiptPrinter;
+
+impl ReceiptPrinter for JsonReceiptPrinter {
+    fn print(&self, receipt: &Receipt) -> Result<(), Box<dyn std::error::Error>> {
+        let items = receipt
+            .items
+            .iter()
+            .map(|item| {
+                json!({
+                    "product_name": item.product_name.as_ref(),
+                    "quantity": item.quantity.as_value(),
+                    "unit_price": item.unit_price.as_value(),
+                    "discount": item.discount,
+                    "total": item.total(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let receipt = json!({
+            "transaction_id": receipt.transaction_id.as_value(),
+            "date": receipt.date.to_rfc3339(),
+            "items": items,
+            "discount": receipt.discount(),
+            "total": receipt.total(),
+            "amount_paid": receipt.amount_paid,
+            "change": receipt.change,
+            "credit": receipt.credit,
+        });
+
+        println!("{}", serde_json::to_string_pretty(&receipt)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use rand::Rng;
+
+use vending_machine::domain::entities::SensorKind;
+use vending_machine::domain::interfaces::Sensor;
+
+#[derive(Clone)]
+pub struct SimulatedTemperatureSensor {
+    temperature: f32,
+    noise: f32,
+}
+
+impl Default for SimulatedTemperatureSensor {
+    fn default() -> Self {
+        Self::new(
+            std::env::var("SIMULATED_TEMPERATURE")
+                .ok()
+                .and_then(|temperature| temperature.parse::<f32>().ok())
+                .unwrap_or(Self::DEFAULT_TEMPERATURE),
+            Self::DEFAULT_NOISE,
+        )
+    }
+}
+
+impl SimulatedTemperatureSensor {
+    const DEFAULT_TEMPERATURE: f32 = 5.0;
+    const DEFAULT_NOISE: f32 = 0.3;
+
+    pub fn new(temperature: f32, noise: f32) -> Self {
+        Self {
+            temperature,
+            noise: noise.abs(),
+        }
+    }
+}
+
+#[async_trait]
+impl Sensor for SimulatedTemperatureSensor {
+    fn kind(&self) -> SensorKind {
+        SensorKind::Temperature
+    }
+
+    async fn read(&self) -> Result<f32, Box<dyn std::error::Error>> {
+        let offset = match self.noise > 0.0 {
+            true => rand::thread_rng().gen_range(-self.noise..=self.noise),
+            false => 0.0,
+        };
+
+        Ok(((self.temperature + offset) * 10.0).round() / 10.0)
+    }
+}
+
+#[derive(Clone)]
+pub struct SimulatedDoorSensor {
+    open_rate: f64,
+}
+
+impl Default for SimulatedDoorSensor {
+    fn default() -> Self {
+        Self::new(
+            std::env::var("SIMULATED_DOOR_OPEN_RATE")
+                .ok()
+                .and_then(|rate| rate.parse::<f64>().ok())
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl SimulatedDoorSensor {
+    pub fn new(open_rate: f64) -> Self {
+        Self {
+            open_rate: open_rate.clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[async_trait]
+impl Sensor for SimulatedDoorSensor {
+    fn kind(&self) -> SensorKind {
+        SensorKind::Door
+    }
+
+    async fn read(&self) -> Result<f32, Box<dyn std::error::Error>> {
+        let open = rand::thread_rng().gen_bool(self.open_rate);
+
+        Ok(match open {
+            true => 1.0,
+            false => 0.0,
+        })
+    }
+}
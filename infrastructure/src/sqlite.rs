@@ -1,12 +1,26 @@
 use async_trait::async_trait;
-use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
-use sqlx::SqlitePool;
+use chrono::Weekday;
+use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
 use yadir::core::contracts::DIBuilder;
 use yadir::core::primitives::DIObj;
 use yadir::{deps, DIBuilder};
 
-use vending_machine::domain::entities::{Name, Price, Product, Sale, Value};
-use vending_machine::domain::interfaces::{ProductRepository, SaleRepository};
+use vending_machine::domain::entities::{
+    AccountTransaction, AccountTransactionKind, CashCollection, CashCount, Category,
+    CustomerAccount, Fault, FaultCode, FaultStatus, HashedPassword, LockReason, LockState,
+    MachineEvent, MachineEventKind, MachineState, Name, PaymentJournalEntry, PaymentMethod,
+    PaymentMethodTotal, PaymentPhase, Price, Product, ProductRevenue, Quantity, Reversal, Sale,
+    Schedule, ScheduleException, ScheduleWindow, SchemaVersion, SensorKind, SensorReading,
+    StockMovement, StockMovementKind, SupplierAccount, Value, VoidReason, WriteOffFlag, ZReport,
+};
+use vending_machine::domain::interfaces::{
+    CashCollectionRepository, CreditRepository, CustomerAccountRepository, FaultLogRepository,
+    MachineHistoryRepository, MachineStateRepository, PaymentJournalRepository, ProductRepository,
+    SaleRepository, ScheduleRepository, SensorReadingRepository, StockMovementRepository,
+    StorageDiagnostics, SupplierRepository, WriteOffRepository, ZReportRepository,
+};
+use vending_machine::domain::queries::{ProductQuery, SortOrder};
 
 #[derive(Clone, DIBuilder)]
 #[build_method("default")]
@@ -31,11 +45,14 @@ pub struct SqliteProductRepository {
     pool: DbConn,
 }
 
+#[derive(sqlx::FromRow)]
 struct RawProduct {
     column_id: i64,
     name: String,
+    category: String,
     price: f64,
     quantity: i64,
+    refrigerated: bool,
 }
 
 impl TryFrom<RawProduct> for Product {
@@ -45,8 +62,10 @@ impl TryFrom<RawProduct> for Product {
         Ok(Product {
             column_id: Value::parse_i32(raw.column_id as i32)?,
             name: Name::parse(&raw.name)?,
+            category: Category::parse(&raw.category)?,
             price: Price::parse_f32(raw.price as f32)?,
-            quantity: Value::parse_i32(raw.quantity as i32)?,
+            quantity: Quantity::parse_i32(raw.quantity as i32)?,
+            refrigerated: raw.refrigerated,
         })
     }
 }
@@ -58,7 +77,7 @@ impl ProductRepository for SqliteProductRepository {
 
         let product = sqlx::query_as!(
             RawProduct,
-            r#"SELECT column_id, name, price, quantity FROM product WHERE column_id = ?"#,
+            r#"SELECT column_id, name, category, price, quantity, refrigerated FROM product WHERE column_id = ?"#,
             column_id
         )
         .fetch_one(&self.pool.0)
@@ -73,15 +92,18 @@ impl ProductRepository for SqliteProductRepository {
         match existing_product {
             Some(_) => {
                 let name = product.name.clone().as_ref().to_string();
+                let category = product.category.clone().as_ref().to_string();
                 let price = product.price.clone().as_value();
                 let quantity = product.quantity.clone().as_value() as i32;
                 let column_id = product.column_id.clone().as_value() as i32;
 
                 sqlx::query!(
-                    r#"UPDATE product SET name = ?, price = ?, quantity = ? WHERE column_id = ?"#,
+                    r#"UPDATE product SET name = ?, category = ?, price = ?, quantity = ?, refrigerated = ? WHERE column_id = ?"#,
                     name,
+                    category,
                     price,
                     quantity,
+                    product.refrigerated,
                     column_id
                 )
                 .execute(&self.pool.0)
@@ -89,16 +111,19 @@ impl ProductRepository for SqliteProductRepository {
             }
             None => {
                 let name = product.name.clone().as_ref().to_string();
+                let category = product.category.clone().as_ref().to_string();
                 let price = product.price.clone().as_value();
                 let quantity = product.quantity.clone().as_value() as i32;
                 let column_id = product.column_id.clone().as_value() as i32;
 
                 sqlx::query!(
-                    r#"INSERT INTO product (column_id, name, price, quantity) VALUES (?, ?, ?, ?)"#,
+                    r#"INSERT INTO product (column_id, name, category, price, quantity, refrigerated) VALUES (?, ?, ?, ?, ?, ?)"#,
                     column_id,
                     name,
+                    category,
                     price,
-                    quantity
+                    quantity,
+                    product.refrigerated
                 )
                 .execute(&self.pool.0)
                 .await?;
@@ -111,7 +136,7 @@ impl ProductRepository for SqliteProductRepository {
     async fn find_all(&self) -> Vec<Product> {
         let products = sqlx::query_as!(
             RawProduct,
-            r#"SELECT column_id, name, price, quantity FROM product"#
+            r#"SELECT column_id, name, category, price, quantity, refrigerated FROM product"#
         )
         .fetch_all(&self.pool.0)
         .await
@@ -123,6 +148,66 @@ impl ProductRepository for SqliteProductRepository {
             .filter_map(Result::ok)
             .collect()
     }
+
+    async fn search(&self, query: &ProductQuery) -> Vec<Product> {
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "SELECT column_id, name, category, price, quantity, refrigerated FROM product WHERE 1 = 1",
+        );
+
+        if let Some(name) = &query.name {
+            let pattern = name
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            builder
+                .push(" AND name LIKE ")
+                .push_bind(format!("%{}%", pattern))
+                .push(" ESCAPE '\\'");
+        }
+
+        if let Some(category) = &query.category {
+            builder
+                .push(" AND category = ")
+                .push_bind(category.as_ref().to_string())
+                .push(" COLLATE NOCASE");
+        }
+
+        if let Some(min_price) = &query.min_price {
+            builder
+                .push(" AND price >= ")
+                .push_bind(min_price.as_value());
+        }
+
+        if let Some(max_price) = &query.max_price {
+            builder
+                .push(" AND price <= ")
+                .push_bind(max_price.as_value());
+        }
+
+        if query.in_stock_only {
+            builder.push(" AND quantity > 0");
+        }
+
+        builder.push(match query.sort {
+            SortOrder::Column => " ORDER BY column_id ASC",
+            SortOrder::NameAsc => " ORDER BY name COLLATE NOCASE ASC",
+            SortOrder::NameDesc => " ORDER BY name COLLATE NOCASE DESC",
+            SortOrder::PriceAsc => " ORDER BY price ASC",
+            SortOrder::PriceDesc => " ORDER BY price DESC",
+        });
+
+        let products = builder
+            .build_query_as::<RawProduct>()
+            .fetch_all(&self.pool.0)
+            .await
+            .unwrap_or(vec![]);
+
+        products
+            .into_iter()
+            .map(|product| product.try_into())
+            .filter_map(Result::ok)
+            .collect()
+    }
 }
 
 #[derive(Default, Clone, DIBuilder)]
@@ -138,6 +223,11 @@ struct RawSale {
     date: NaiveDateTime,
     price: f64,
     product_id: i64,
+    quantity: i64,
+    payment_method: String,
+    paid_from_credit: f64,
+    reversal_of: Option<i64>,
+    void_reason: Option<String>,
 }
 
 struct ProductSalePair(RawProduct, RawSale);
@@ -147,67 +237,1642 @@ impl TryFrom<ProductSalePair> for Sale {
 
     fn try_from(pair: ProductSalePair) -> Result<Self, Self::Error> {
         let (product, sale) = (pair.0, pair.1);
+        let reversal = match (sale.reversal_of, sale.void_reason) {
+            (Some(sale_id), Some(reason)) => Some(Reversal {
+                sale_id: Value::parse_i32(sale_id as i32)?,
+                reason: VoidReason::parse(reason.as_str())?,
+            }),
+            _ => None,
+        };
+
         Ok(Sale {
+            id: Some(Value::parse_i32(sale.id as i32)?),
             date: DateTime::<Utc>::from_naive_utc_and_offset(sale.date, Utc),
+            column_id: Value::parse_i32(sale.product_id as i32)?,
             product_name: Name::parse(product.name.as_str())?,
+            quantity: Value::parse_i32(sale.quantity as i32)?,
             price: Price::parse_f32(sale.price as f32)?,
+            payment_method: PaymentMethod::parse(sale.payment_method.as_str())?,
+            paid_from_credit: sale.paid_from_credit as f32,
+            reversal,
         })
     }
 }
 
-#[async_trait]
-impl SaleRepository for SqliteSaleRepository {
-    async fn save(&mut self, sale: Sale) -> Result<(), Box<dyn std::error::Error>> {
-        let product_name = sale.product_name.clone().as_ref().to_string();
-
+impl SqliteSaleRepository {
+    async fn to_sale(&self, sale: RawSale) -> Option<Sale> {
         let product = sqlx::query_as!(
             RawProduct,
-            r#"SELECT column_id, name, price, quantity FROM product WHERE name = ?"#,
-            product_name
+            r#"SELECT column_id, name, category, price, quantity, refrigerated FROM product WHERE column_id = ?"#,
+            sale.product_id
         )
         .fetch_one(&self.pool.0)
-        .await?;
+        .await
+        .ok()?;
 
-        let product_id = product.column_id;
+        Sale::try_from(ProductSalePair(product, sale)).ok()
+    }
+}
+
+#[async_trait]
+impl SaleRepository for SqliteSaleRepository {
+    async fn find(&self, id: Value) -> Option<Sale> {
+        let id = id.as_value();
+
+        let sale = sqlx::query_as!(
+            RawSale,
+            r#"SELECT id AS "id!", date, price, product_id, quantity, payment_method, paid_from_credit, reversal_of, void_reason FROM sale WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool.0)
+        .await
+        .ok()??;
+
+        self.to_sale(sale).await
+    }
+
+    async fn save(&mut self, sale: Sale) -> Result<Sale, Box<dyn std::error::Error>> {
+        let product_id = sale.column_id.as_value();
+        let quantity = sale.quantity.as_value();
         let price = sale.price.clone().as_value();
+        let payment_method = sale.payment_method.as_ref();
+        let paid_from_credit = sale.paid_from_credit;
+        let reversal_of = sale
+            .reversal
+            .as_ref()
+            .map(|reversal| reversal.sale_id.as_value());
+        let void_reason = sale
+            .reversal
+            .as_ref()
+            .map(|reversal| reversal.reason.as_ref().to_string());
 
-        sqlx::query!(
-            r#"INSERT INTO sale (date, price, product_id) VALUES (?, ?, ?)"#,
+        let id = sqlx::query!(
+            r#"INSERT INTO sale (date, price, product_id, quantity, payment_method, paid_from_credit, reversal_of, void_reason) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
             sale.date,
             price,
-            product_id
+            product_id,
+            quantity,
+            payment_method,
+            paid_from_credit,
+            reversal_of,
+            void_reason
         )
         .execute(&self.pool.0)
+        .await?
+        .last_insert_rowid();
+
+        Ok(Sale {
+            id: Some(Value::parse_i32(id as i32)?),
+            ..sale
+        })
+    }
+
+    async fn record(
+        &mut self,
+        sale: Sale,
+        movement: StockMovement,
+    ) -> Result<Sale, Box<dyn std::error::Error>> {
+        let product_id = sale.column_id.as_value();
+        let quantity = sale.quantity.as_value();
+        let price = sale.price.clone().as_value();
+        let payment_method = sale.payment_method.as_ref();
+        let paid_from_credit = sale.paid_from_credit;
+        let product_name = movement.product_name.as_ref();
+        let kind = movement.kind.as_ref();
+
+        let mut transaction = self.pool.0.begin().await?;
+
+        let updated = sqlx::query!(
+            r#"UPDATE product SET quantity = quantity + ? WHERE column_id = ? AND quantity + ? >= 0"#,
+            movement.delta,
+            product_id,
+            movement.delta
+        )
+        .execute(&mut *transaction)
         .await?;
+        if updated.rows_affected() == 0 {
+            return Err(Box::from("Insufficient quantity in stock"));
+        }
 
-        Ok(())
+        sqlx::query!(
+            r#"INSERT INTO stock_movement (date, column_id, product_name, kind, delta) VALUES (?, ?, ?, ?, ?)"#,
+            movement.date,
+            product_id,
+            product_name,
+            kind,
+            movement.delta
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let id = sqlx::query!(
+            r#"INSERT INTO sale (date, price, product_id, quantity, payment_method, paid_from_credit) VALUES (?, ?, ?, ?, ?, ?)"#,
+            sale.date,
+            price,
+            product_id,
+            quantity,
+            payment_method,
+            paid_from_credit
+        )
+        .execute(&mut *transaction)
+        .await?
+        .last_insert_rowid();
+
+        transaction.commit().await?;
+
+        Ok(Sale {
+            id: Some(Value::parse_i32(id as i32)?),
+            ..sale
+        })
     }
 
     async fn find_all(&self) -> Vec<Sale> {
-        let raw_sales = sqlx::query_as!(RawSale, r#"SELECT id, date, price, product_id FROM sale"#)
-            .fetch_all(&self.pool.0)
-            .await
-            .unwrap_or(vec![]);
+        let raw_sales = sqlx::query_as!(
+            RawSale,
+            r#"SELECT id AS "id!", date, price, product_id, quantity, payment_method, paid_from_credit, reversal_of, void_reason FROM sale ORDER BY id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
 
         let mut sales = Vec::<Sale>::new();
         for sale in raw_sales {
-            let product = sqlx::query_as!(
-                RawProduct,
-                r#"SELECT column_id, name, price, quantity FROM product WHERE column_id = ?"#,
-                sale.product_id
-            )
-            .fetch_one(&self.pool.0)
-            .await;
-
-            if let Ok(product) = product {
-                let pair = ProductSalePair(product, sale);
-                match Sale::try_from(pair) {
-                    Ok(sale) => sales.push(sale),
-                    Err(_) => continue,
-                }
+            if let Some(sale) = self.to_sale(sale).await {
+                sales.push(sale);
             }
         }
 
         sales
     }
 }
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn MachineStateRepository>)]
+#[build_method("default")]
+pub struct SqliteMachineStateRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawMachineState {
+    lock_state: String,
+    changed_by: Option<String>,
+    reason: Option<String>,
+    expected_back: Option<NaiveDateTime>,
+    changed_at: NaiveDateTime,
+}
+
+impl TryFrom<RawMachineState> for MachineState {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawMachineState) -> Result<Self, Self::Error> {
+        Ok(MachineState {
+            lock_state: LockState::parse(&raw.lock_state)?,
+            changed_by: raw.changed_by.as_deref().map(Name::parse).transpose()?,
+            reason: raw.reason.as_deref().map(LockReason::parse).transpose()?,
+            expected_back: raw.expected_back.map(|expected_back| {
+                DateTime::<Utc>::from_naive_utc_and_offset(expected_back, Utc)
+            }),
+            changed_at: DateTime::<Utc>::from_naive_utc_and_offset(raw.changed_at, Utc),
+        })
+    }
+}
+
+#[async_trait]
+impl MachineStateRepository for SqliteMachineStateRepository {
+    async fn load(&self) -> Result<Option<MachineState>, Box<dyn std::error::Error>> {
+        sqlx::query_as!(
+            RawMachineState,
+            r#"SELECT lock_state, changed_by, reason, expected_back, changed_at FROM machine_state WHERE id = 1"#
+        )
+        .fetch_optional(&self.pool.0)
+        .await?
+        .map(MachineState::try_from)
+        .transpose()
+    }
+
+    async fn save(&mut self, state: MachineState) -> Result<(), Box<dyn std::error::Error>> {
+        let lock_state = state.lock_state.as_ref().to_string();
+        let changed_by = state
+            .changed_by
+            .as_ref()
+            .map(|name| name.as_ref().to_string());
+        let reason = state
+            .reason
+            .as_ref()
+            .map(|reason| reason.as_ref().to_string());
+
+        sqlx::query!(
+            r#"INSERT INTO machine_state (id, lock_state, changed_by, reason, expected_back, changed_at) VALUES (1, ?, ?, ?, ?, ?)
+               ON CONFLICT (id) DO UPDATE SET lock_state = excluded.lock_state, changed_by = excluded.changed_by, reason = excluded.reason, expected_back = excluded.expected_back, changed_at = excluded.changed_at"#,
+            lock_state,
+            changed_by,
+            reason,
+            state.expected_back,
+            state.changed_at
+        )
+        .execute(&self.pool.0)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn MachineHistoryRepository>)]
+#[build_method("default")]
+pub struct SqliteMachineHistoryRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawMachineEvent {
+    date: NaiveDateTime,
+    kind: String,
+    actor: Option<String>,
+    details: String,
+}
+
+impl TryFrom<RawMachineEvent> for MachineEvent {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawMachineEvent) -> Result<Self, Self::Error> {
+        Ok(MachineEvent {
+            date: DateTime::<Utc>::from_naive_utc_and_offset(raw.date, Utc),
+            kind: MachineEventKind::parse(&raw.kind)?,
+            actor: raw.actor.as_deref().map(Name::parse).transpose()?,
+            details: raw.details,
+        })
+    }
+}
+
+#[async_trait]
+impl MachineHistoryRepository for SqliteMachineHistoryRepository {
+    async fn save(&mut self, event: MachineEvent) -> Result<(), Box<dyn std::error::Error>> {
+        let kind = event.kind.as_ref().to_string();
+        let actor = event.actor.as_ref().map(|actor| actor.as_ref().to_string());
+
+        sqlx::query!(
+            r#"INSERT INTO machine_history (date, kind, actor, details) VALUES (?, ?, ?, ?)"#,
+            event.date,
+            kind,
+            actor,
+            event.details
+        )
+        .execute(&self.pool.0)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<MachineEvent> {
+        let events = sqlx::query_as!(
+            RawMachineEvent,
+            r#"SELECT date, kind, actor, details FROM machine_history ORDER BY id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        events
+            .into_iter()
+            .map(|event| event.try_into())
+            .filter_map(Result::ok)
+            .collect()
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn ScheduleRepository>)]
+#[build_method("default")]
+pub struct SqliteScheduleRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawScheduleWindow {
+    weekday: i64,
+    opens_at: NaiveTime,
+    closes_at: NaiveTime,
+}
+
+impl TryFrom<RawScheduleWindow> for ScheduleWindow {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawScheduleWindow) -> Result<Self, Self::Error> {
+        let weekday = Weekday::try_from(raw.weekday as u8)?;
+
+        ScheduleWindow::new(weekday, raw.opens_at, raw.closes_at)
+    }
+}
+
+struct RawScheduleException {
+    date: NaiveDate,
+    description: String,
+}
+
+impl From<RawScheduleException> for ScheduleException {
+    fn from(raw: RawScheduleException) -> Self {
+        ScheduleException {
+            date: raw.date,
+            description: raw.description,
+        }
+    }
+}
+
+#[async_trait]
+impl ScheduleRepository for SqliteScheduleRepository {
+    async fn load(&self) -> Schedule {
+        let windows = sqlx::query_as!(
+            RawScheduleWindow,
+            r#"SELECT weekday, opens_at AS "opens_at: NaiveTime", closes_at AS "closes_at: NaiveTime" FROM schedule_window ORDER BY weekday, opens_at"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        let exceptions = sqlx::query_as!(
+            RawScheduleException,
+            r#"SELECT date AS "date!: NaiveDate", description FROM schedule_exception ORDER BY date"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        Schedule {
+            windows: windows
+                .into_iter()
+                .map(|window| window.try_into())
+                .filter_map(Result::ok)
+                .collect(),
+            exceptions: exceptions
+                .into_iter()
+                .map(|exception| exception.into())
+                .collect(),
+        }
+    }
+
+    async fn save(&mut self, schedule: Schedule) -> Result<(), Box<dyn std::error::Error>> {
+        let mut transaction = self.pool.0.begin().await?;
+
+        sqlx::query!(r#"DELETE FROM schedule_window"#)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query!(r#"DELETE FROM schedule_exception"#)
+            .execute(&mut *transaction)
+            .await?;
+
+        for window in schedule.windows {
+            let weekday = window.weekday.num_days_from_monday() as i32;
+
+            sqlx::query!(
+                r#"INSERT INTO schedule_window (weekday, opens_at, closes_at) VALUES (?, ?, ?)"#,
+                weekday,
+                window.opens_at,
+                window.closes_at
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        for exception in schedule.exceptions {
+            sqlx::query!(
+                r#"INSERT INTO schedule_exception (date, description) VALUES (?, ?)"#,
+                exception.date,
+                exception.description
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn SupplierRepository>)]
+#[build_method("default")]
+pub struct SqliteSupplierRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawSupplier {
+    name: String,
+    password: String,
+}
+
+impl SqliteSupplierRepository {
+    async fn columns(&self, name: &str) -> Vec<Value> {
+        sqlx::query_scalar!(
+            r#"SELECT column_id FROM supplier_column WHERE supplier_name = ? ORDER BY column_id"#,
+            name
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![])
+        .into_iter()
+        .map(|column_id| Value::parse_i32(column_id as i32))
+        .filter_map(Result::ok)
+        .collect()
+    }
+
+    async fn to_account(
+        &self,
+        raw: RawSupplier,
+    ) -> Result<SupplierAccount, Box<dyn std::error::Error>> {
+        let columns = self.columns(raw.name.as_str()).await;
+
+        Ok(SupplierAccount {
+            name: Name::parse(raw.name.as_str())?,
+            password: HashedPassword::parse(raw.password.as_str())?,
+            columns,
+        })
+    }
+}
+
+#[async_trait]
+impl SupplierRepository for SqliteSupplierRepository {
+    async fn find(&self, name: &Name) -> Option<SupplierAccount> {
+        let name = name.as_ref();
+
+        let raw = sqlx::query_as!(
+            RawSupplier,
+            r#"SELECT name AS "name!", password FROM supplier WHERE name = ?"#,
+            name
+        )
+        .fetch_optional(&self.pool.0)
+        .await
+        .ok()??;
+
+        self.to_account(raw).await.ok()
+    }
+
+    async fn save(&mut self, account: SupplierAccount) -> Result<(), Box<dyn std::error::Error>> {
+        let name = account.name.as_ref();
+        let password = account.password.as_ref();
+
+        let mut transaction = self.pool.0.begin().await?;
+
+        sqlx::query!(
+            r#"INSERT INTO supplier (name, password) VALUES (?, ?)
+               ON CONFLICT(name) DO UPDATE SET password = excluded.password"#,
+            name,
+            password
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"DELETE FROM supplier_column WHERE supplier_name = ?"#,
+            name
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        for column in account.columns.iter() {
+            let column_id = column.as_value();
+
+            sqlx::query!(
+                r#"INSERT INTO supplier_column (column_id, supplier_name) VALUES (?, ?)
+                   ON CONFLICT(column_id) DO UPDATE SET supplier_name = excluded.supplier_name"#,
+                column_id,
+                name
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<SupplierAccount> {
+        let raw_suppliers = sqlx::query_as!(
+            RawSupplier,
+            r#"SELECT name AS "name!", password FROM supplier ORDER BY name"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        let mut suppliers = Vec::<SupplierAccount>::new();
+        for raw in raw_suppliers {
+            if let Ok(account) = self.to_account(raw).await {
+                suppliers.push(account);
+            }
+        }
+
+        suppliers
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn CashCollectionRepository>)]
+#[build_method("default")]
+pub struct SqliteCashCollectionRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawCashCollection {
+    id: i64,
+    date: NaiveDateTime,
+    collected_by: Option<String>,
+}
+
+struct RawCashCount {
+    denomination: f64,
+    count: i64,
+}
+
+impl TryFrom<RawCashCount> for CashCount {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawCashCount) -> Result<Self, Self::Error> {
+        Ok(CashCount {
+            denomination: Price::parse_f32(raw.denomination as f32)?,
+            count: Quantity::parse_i32(raw.count as i32)?,
+        })
+    }
+}
+
+struct CashCollectionCountsPair(RawCashCollection, Vec<RawCashCount>);
+
+impl TryFrom<CashCollectionCountsPair> for CashCollection {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(pair: CashCollectionCountsPair) -> Result<Self, Self::Error> {
+        let (collection, counts) = (pair.0, pair.1);
+        Ok(CashCollection {
+            date: DateTime::<Utc>::from_naive_utc_and_offset(collection.date, Utc),
+            collected_by: collection
+                .collected_by
+                .as_deref()
+                .map(Name::parse)
+                .transpose()?,
+            counts: counts
+                .into_iter()
+                .map(CashCount::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[async_trait]
+impl CashCollectionRepository for SqliteCashCollectionRepository {
+    async fn save(&mut self, collection: CashCollection) -> Result<(), Box<dyn std::error::Error>> {
+        let collected_by = collection
+            .collected_by
+            .as_ref()
+            .map(|collected_by| collected_by.as_ref().to_string());
+
+        let mut transaction = self.pool.0.begin().await?;
+
+        let collection_id = sqlx::query!(
+            r#"INSERT INTO cash_collection (date, collected_by) VALUES (?, ?)"#,
+            collection.date,
+            collected_by
+        )
+        .execute(&mut *transaction)
+        .await?
+        .last_insert_rowid();
+
+        for count in collection.counts.iter() {
+            let denomination = count.denomination.as_value();
+            let quantity = count.count.as_value();
+
+            sqlx::query!(
+                r#"INSERT INTO cash_collection_count (collection_id, denomination, count) VALUES (?, ?, ?)
+                   ON CONFLICT(collection_id, denomination) DO UPDATE SET count = count + excluded.count"#,
+                collection_id,
+                denomination,
+                quantity
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<CashCollection> {
+        let raw_collections = sqlx::query_as!(
+            RawCashCollection,
+            r#"SELECT id, date, collected_by FROM cash_collection ORDER BY date"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        let mut collections = Vec::<CashCollection>::new();
+        for collection in raw_collections {
+            let counts = sqlx::query_as!(
+                RawCashCount,
+                r#"SELECT denomination, count FROM cash_collection_count WHERE collection_id = ? ORDER BY denomination DESC"#,
+                collection.id
+            )
+            .fetch_all(&self.pool.0)
+            .await
+            .unwrap_or(vec![]);
+
+            if let Ok(collection) =
+                CashCollection::try_from(CashCollectionCountsPair(collection, counts))
+            {
+                collections.push(collection);
+            }
+        }
+
+        collections
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn StockMovementRepository>)]
+#[build_method("default")]
+pub struct SqliteStockMovementRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawStockMovement {
+    date: NaiveDateTime,
+    column_id: i64,
+    product_name: String,
+    kind: String,
+    delta: i64,
+}
+
+impl TryFrom<RawStockMovement> for StockMovement {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawStockMovement) -> Result<Self, Self::Error> {
+        Ok(StockMovement {
+            date: DateTime::<Utc>::from_naive_utc_and_offset(raw.date, Utc),
+            column_id: Value::parse_i32(raw.column_id as i32)?,
+            product_name: Name::parse(raw.product_name.as_str())?,
+            kind: StockMovementKind::parse(&raw.kind)?,
+            delta: raw.delta as i32,
+        })
+    }
+}
+
+#[async_trait]
+impl StockMovementRepository for SqliteStockMovementRepository {
+    async fn save(&mut self, movement: StockMovement) -> Result<(), Box<dyn std::error::Error>> {
+        let column_id = movement.column_id.as_value();
+        let product_name = movement.product_name.as_ref();
+        let kind = movement.kind.as_ref();
+
+        sqlx::query!(
+            r#"INSERT INTO stock_movement (date, column_id, product_name, kind, delta) VALUES (?, ?, ?, ?, ?)"#,
+            movement.date,
+            column_id,
+            product_name,
+            kind,
+            movement.delta
+        )
+        .execute(&self.pool.0)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<StockMovement> {
+        let movements = sqlx::query_as!(
+            RawStockMovement,
+            r#"SELECT date, column_id, product_name, kind, delta FROM stock_movement ORDER BY id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        movements
+            .into_iter()
+            .map(|movement| movement.try_into())
+            .filter_map(Result::ok)
+            .collect()
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn ZReportRepository>)]
+#[build_method("default")]
+pub struct SqliteZReportRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawZReport {
+    number: i64,
+    opened_at: Option<NaiveDateTime>,
+    closed_at: NaiveDateTime,
+    closed_by: Option<String>,
+    sales_count: i64,
+    revenue: f64,
+    refunds: f64,
+    cash_collected: f64,
+}
+
+struct RawProductRevenue {
+    product_name: String,
+    sales_count: i64,
+    revenue: f64,
+}
+
+impl TryFrom<RawProductRevenue> for ProductRevenue {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawProductRevenue) -> Result<Self, Self::Error> {
+        Ok(ProductRevenue {
+            product_name: Name::parse(raw.product_name.as_str())?,
+            sales_count: raw.sales_count as u32,
+            revenue: raw.revenue as f32,
+        })
+    }
+}
+
+struct RawPaymentMethodTotal {
+    method: String,
+    sales_count: i64,
+    total: f64,
+}
+
+impl From<RawPaymentMethodTotal> for PaymentMethodTotal {
+    fn from(raw: RawPaymentMethodTotal) -> Self {
+        PaymentMethodTotal {
+            method: raw.method,
+            sales_count: raw.sales_count as u32,
+            total: raw.total as f32,
+        }
+    }
+}
+
+struct ZReportParts(
+    RawZReport,
+    Vec<RawProductRevenue>,
+    Vec<RawPaymentMethodTotal>,
+    Vec<RawStockMovement>,
+);
+
+impl TryFrom<ZReportParts> for ZReport {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(parts: ZReportParts) -> Result<Self, Self::Error> {
+        let ZReportParts(report, product_revenue, payment_methods, stock_movements) = parts;
+        Ok(ZReport {
+            number: Value::parse_i32(report.number as i32)?,
+            opened_at: report
+                .opened_at
+                .map(|opened_at| DateTime::<Utc>::from_naive_utc_and_offset(opened_at, Utc)),
+            closed_at: DateTime::<Utc>::from_naive_utc_and_offset(report.closed_at, Utc),
+            closed_by: report.closed_by.as_deref().map(Name::parse).transpose()?,
+            sales_count: report.sales_count as u32,
+            revenue: report.revenue as f32,
+            refunds: report.refunds as f32,
+            cash_collected: report.cash_collected as f32,
+            product_revenue: product_revenue
+                .into_iter()
+                .map(ProductRevenue::try_from)
+                .collect::<Result<_, _>>()?,
+            payment_methods: payment_methods
+                .into_iter()
+                .map(PaymentMethodTotal::from)
+                .collect(),
+            stock_movements: stock_movements
+                .into_iter()
+                .map(StockMovement::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[async_trait]
+impl ZReportRepository for SqliteZReportRepository {
+    async fn save(&mut self, report: ZReport) -> Result<(), Box<dyn std::error::Error>> {
+        let number = report.number.as_value();
+        let closed_by = report
+            .closed_by
+            .as_ref()
+            .map(|closed_by| closed_by.as_ref().to_string());
+        let sales_count = report.sales_count;
+
+        let mut transaction = self.pool.0.begin().await?;
+
+        sqlx::query!(
+            r#"INSERT INTO z_report (number, opened_at, closed_at, closed_by, sales_count, revenue, refunds, cash_collected)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+            number,
+            report.opened_at,
+            report.closed_at,
+            closed_by,
+            sales_count,
+            report.revenue,
+            report.refunds,
+            report.cash_collected
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        for entry in report.product_revenue.iter() {
+            let product_name = entry.product_name.as_ref();
+
+            sqlx::query!(
+                r#"INSERT INTO z_report_product_revenue (report_number, product_name, sales_count, revenue) VALUES (?, ?, ?, ?)"#,
+                number,
+                product_name,
+                entry.sales_count,
+                entry.revenue
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        for entry in report.payment_methods.iter() {
+            sqlx::query!(
+                r#"INSERT INTO z_report_payment_method (report_number, method, sales_count, total) VALUES (?, ?, ?, ?)"#,
+                number,
+                entry.method,
+                entry.sales_count,
+                entry.total
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        for movement in report.stock_movements.iter() {
+            let column_id = movement.column_id.as_value();
+            let product_name = movement.product_name.as_ref();
+            let kind = movement.kind.as_ref();
+
+            sqlx::query!(
+                r#"INSERT INTO z_report_stock_movement (report_number, date, column_id, product_name, kind, delta) VALUES (?, ?, ?, ?, ?, ?)"#,
+                number,
+                movement.date,
+                column_id,
+                product_name,
+                kind,
+                movement.delta
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<ZReport> {
+        let raw_reports = sqlx::query_as!(
+            RawZReport,
+            r#"SELECT number, opened_at, closed_at, closed_by, sales_count, revenue, refunds, cash_collected
+               FROM z_report ORDER BY number"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        let mut reports = Vec::<ZReport>::new();
+        for report in raw_reports {
+            let product_revenue = sqlx::query_as!(
+                RawProductRevenue,
+                r#"SELECT product_name, sales_count, revenue FROM z_report_product_revenue WHERE report_number = ? ORDER BY rowid"#,
+                report.number
+            )
+            .fetch_all(&self.pool.0)
+            .await
+            .unwrap_or(vec![]);
+
+            let payment_methods = sqlx::query_as!(
+                RawPaymentMethodTotal,
+                r#"SELECT method, sales_count, total FROM z_report_payment_method WHERE report_number = ? ORDER BY rowid"#,
+                report.number
+            )
+            .fetch_all(&self.pool.0)
+            .await
+            .unwrap_or(vec![]);
+
+            let stock_movements = sqlx::query_as!(
+                RawStockMovement,
+                r#"SELECT date, column_id, product_name, kind, delta FROM z_report_stock_movement WHERE report_number = ? ORDER BY rowid"#,
+                report.number
+            )
+            .fetch_all(&self.pool.0)
+            .await
+            .unwrap_or(vec![]);
+
+            let parts = ZReportParts(report, product_revenue, payment_methods, stock_movements);
+            if let Ok(report) = ZReport::try_from(parts) {
+                reports.push(report);
+            }
+        }
+
+        reports
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn CustomerAccountRepository>)]
+#[build_method("default")]
+pub struct SqliteCustomerAccountRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawCustomerAccount {
+    badge_id: String,
+    holder: String,
+    pin_hash: Option<String>,
+}
+
+struct RawAccountTransaction {
+    date: NaiveDateTime,
+    kind: String,
+    amount: f64,
+}
+
+impl TryFrom<RawAccountTransaction> for AccountTransaction {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawAccountTransaction) -> Result<Self, Self::Error> {
+        Ok(AccountTransaction {
+            date: DateTime::<Utc>::from_naive_utc_and_offset(raw.date, Utc),
+            kind: AccountTransactionKind::parse(raw.kind.as_str())?,
+            amount: Price::parse_f32(raw.amount as f32)?,
+        })
+    }
+}
+
+struct CustomerAccountTransactionsPair(RawCustomerAccount, Vec<RawAccountTransaction>);
+
+impl TryFrom<CustomerAccountTransactionsPair> for CustomerAccount {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(pair: CustomerAccountTransactionsPair) -> Result<Self, Self::Error> {
+        let (account, transactions) = (pair.0, pair.1);
+        Ok(CustomerAccount {
+            badge_id: Name::parse(account.badge_id.as_str())?,
+            holder: Name::parse(account.holder.as_str())?,
+            pin: account
+                .pin_hash
+                .map(|pin_hash| HashedPassword::parse(pin_hash.as_str()))
+                .transpose()?,
+            transactions: transactions
+                .into_iter()
+                .map(AccountTransaction::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl SqliteCustomerAccountRepository {
+    async fn transactions(&self, badge_id: &str) -> Vec<RawAccountTransaction> {
+        sqlx::query_as!(
+            RawAccountTransaction,
+            r#"SELECT date, kind, amount FROM customer_account_transaction WHERE badge_id = ? ORDER BY id"#,
+            badge_id
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![])
+    }
+}
+
+#[async_trait]
+impl CustomerAccountRepository for SqliteCustomerAccountRepository {
+    async fn find(&self, badge_id: &Name) -> Option<CustomerAccount> {
+        let badge_id = badge_id.as_ref();
+
+        let raw = sqlx::query_as!(
+            RawCustomerAccount,
+            r#"SELECT badge_id AS "badge_id!", holder, pin_hash FROM customer_account WHERE badge_id = ?"#,
+            badge_id
+        )
+        .fetch_optional(&self.pool.0)
+        .await
+        .ok()??;
+
+        let transactions = self.transactions(badge_id).await;
+
+        CustomerAccount::try_from(CustomerAccountTransactionsPair(raw, transactions)).ok()
+    }
+
+    async fn save(&mut self, account: CustomerAccount) -> Result<(), Box<dyn std::error::Error>> {
+        let badge_id = account.badge_id.as_ref();
+        let holder = account.holder.as_ref();
+        let pin_hash = account.pin.as_ref().map(|pin| pin.as_ref());
+
+        let mut transaction = self.pool.0.begin().await?;
+
+        sqlx::query!(
+            r#"INSERT INTO customer_account (badge_id, holder, pin_hash) VALUES (?, ?, ?)
+               ON CONFLICT(badge_id) DO UPDATE SET holder = excluded.holder, pin_hash = excluded.pin_hash"#,
+            badge_id,
+            holder,
+            pin_hash
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let persisted = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM customer_account_transaction WHERE badge_id = ?"#,
+            badge_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        for account_transaction in account.transactions.iter().skip(persisted as usize) {
+            let kind = account_transaction.kind.as_ref();
+            let amount = account_transaction.amount.as_value();
+
+            sqlx::query!(
+                r#"INSERT INTO customer_account_transaction (badge_id, date, kind, amount) VALUES (?, ?, ?, ?)"#,
+                badge_id,
+                account_transaction.date,
+                kind,
+                amount
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<CustomerAccount> {
+        let raw_accounts = sqlx::query_as!(
+            RawCustomerAccount,
+            r#"SELECT badge_id AS "badge_id!", holder, pin_hash FROM customer_account ORDER BY badge_id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![]);
+
+        let mut accounts = Vec::<CustomerAccount>::new();
+        for raw in raw_accounts {
+            let transactions = self.transactions(raw.badge_id.as_str()).await;
+
+            if let Ok(account) =
+                CustomerAccount::try_from(CustomerAccountTransactionsPair(raw, transactions))
+            {
+                accounts.push(account);
+            }
+        }
+
+        accounts
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn CreditRepository>)]
+#[build_method("default")]
+pub struct SqliteCreditRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+#[async_trait]
+impl CreditRepository for SqliteCreditRepository {
+    async fn load(&self, transaction_id: &Value) -> f32 {
+        let transaction_id = transaction_id.as_value();
+
+        sqlx::query_scalar!(
+            r#"SELECT amount FROM credit WHERE transaction_id = ?"#,
+            transaction_id
+        )
+        .fetch_optional(&self.pool.0)
+        .await
+        .ok()
+        .flatten()
+        .map(|amount| amount as f32)
+        .unwrap_or(0.0)
+    }
+
+    async fn save(
+        &mut self,
+        transaction_id: &Value,
+        credit: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let transaction_id = transaction_id.as_value();
+        let updated_at = Utc::now();
+
+        match credit > 0.0 {
+            true => sqlx::query!(
+                r#"INSERT INTO credit (transaction_id, amount, updated_at) VALUES (?, ?, ?)
+                   ON CONFLICT (transaction_id) DO UPDATE SET amount = excluded.amount, updated_at = excluded.updated_at"#,
+                transaction_id,
+                credit,
+                updated_at
+            )
+            .execute(&self.pool.0)
+            .await?,
+            false => sqlx::query!(
+                r#"DELETE FROM credit WHERE transaction_id = ?"#,
+                transaction_id
+            )
+            .execute(&self.pool.0)
+            .await?,
+        };
+
+        Ok(())
+    }
+
+    async fn carry_over(
+        &mut self,
+        from: &Value,
+        to: &Value,
+        credit: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let from = from.as_value();
+        let to = to.as_value();
+        let updated_at = Utc::now();
+
+        let mut transaction = self.pool.0.begin().await?;
+
+        sqlx::query!(r#"DELETE FROM credit WHERE transaction_id = ?"#, from)
+            .execute(&mut *transaction)
+            .await?;
+
+        if credit > 0.0 {
+            sqlx::query!(
+                r#"INSERT INTO credit (transaction_id, amount, updated_at) VALUES (?, ?, ?)"#,
+                to,
+                credit,
+                updated_at
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<(Value, f32)> {
+        sqlx::query!(r#"SELECT transaction_id, amount FROM credit ORDER BY transaction_id"#)
+            .fetch_all(&self.pool.0)
+            .await
+            .unwrap_or(vec![])
+            .into_iter()
+            .filter_map(|row| {
+                Value::parse_i32(row.transaction_id as i32)
+                    .ok()
+                    .map(|transaction_id| (transaction_id, row.amount as f32))
+            })
+            .collect()
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn PaymentJournalRepository>)]
+#[build_method("default")]
+pub struct SqlitePaymentJournalRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawPaymentJournalEntry {
+    transaction_id: i64,
+    date: NaiveDateTime,
+    phase: String,
+    amount: f64,
+    payment_method: Option<String>,
+    reference: Option<String>,
+}
+
+impl TryFrom<RawPaymentJournalEntry> for PaymentJournalEntry {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawPaymentJournalEntry) -> Result<Self, Self::Error> {
+        Ok(PaymentJournalEntry {
+            transaction_id: Value::parse_i32(raw.transaction_id as i32)?,
+            date: DateTime::<Utc>::from_naive_utc_and_offset(raw.date, Utc),
+            phase: PaymentPhase::parse(raw.phase.as_str())?,
+            amount: raw.amount as f32,
+            method: raw
+                .payment_method
+                .as_deref()
+                .map(PaymentMethod::parse)
+                .transpose()?,
+            reference: raw.reference,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentJournalRepository for SqlitePaymentJournalRepository {
+    async fn next_transaction_id(&self) -> Result<Value, Box<dyn std::error::Error>> {
+        let started_at = Utc::now();
+        let id = sqlx::query_scalar!(
+            r#"INSERT INTO payment_transaction (started_at) VALUES (?) RETURNING id AS "id!: i64""#,
+            started_at
+        )
+        .fetch_one(&self.pool.0)
+        .await?;
+
+        Value::parse_i32(id as i32)
+    }
+
+    async fn save(&mut self, entry: PaymentJournalEntry) -> Result<(), Box<dyn std::error::Error>> {
+        let transaction_id = entry.transaction_id.as_value();
+        let phase = entry.phase.as_ref();
+        let payment_method = entry.method.as_ref().map(|method| method.as_ref());
+
+        sqlx::query!(
+            r#"INSERT INTO payment_journal (transaction_id, date, phase, amount, payment_method, reference) VALUES (?, ?, ?, ?, ?, ?)"#,
+            transaction_id,
+            entry.date,
+            phase,
+            entry.amount,
+            payment_method,
+            entry.reference
+        )
+        .execute(&self.pool.0)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<PaymentJournalEntry> {
+        sqlx::query_as!(
+            RawPaymentJournalEntry,
+            r#"SELECT transaction_id, date, phase, amount, payment_method, reference FROM payment_journal ORDER BY id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![])
+        .into_iter()
+        .map(PaymentJournalEntry::try_from)
+        .filter_map(Result::ok)
+        .collect()
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn SensorReadingRepository>)]
+#[build_method("default")]
+pub struct SqliteSensorReadingRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawSensorReading {
+    date: NaiveDateTime,
+    kind: String,
+    value: f64,
+    alarm: bool,
+}
+
+impl TryFrom<RawSensorReading> for SensorReading {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawSensorReading) -> Result<Self, Self::Error> {
+        Ok(SensorReading {
+            date: DateTime::<Utc>::from_naive_utc_and_offset(raw.date, Utc),
+            kind: SensorKind::parse(raw.kind.as_str())?,
+            value: raw.value as f32,
+            alarm: raw.alarm,
+        })
+    }
+}
+
+#[async_trait]
+impl SensorReadingRepository for SqliteSensorReadingRepository {
+    async fn save(&mut self, reading: SensorReading) -> Result<(), Box<dyn std::error::Error>> {
+        let kind = reading.kind.as_ref();
+
+        sqlx::query!(
+            r#"INSERT INTO sensor_reading (date, kind, value, alarm) VALUES (?, ?, ?, ?)"#,
+            reading.date,
+            kind,
+            reading.value,
+            reading.alarm
+        )
+        .execute(&self.pool.0)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn latest(&self, kind: &SensorKind) -> Option<SensorReading> {
+        let kind = kind.as_ref();
+
+        sqlx::query_as!(
+            RawSensorReading,
+            r#"SELECT date, kind, value, alarm FROM sensor_reading WHERE kind = ? ORDER BY date DESC, id DESC LIMIT 1"#,
+            kind
+        )
+        .fetch_optional(&self.pool.0)
+        .await
+        .ok()??
+        .try_into()
+        .ok()
+    }
+
+    async fn excursion_started_at(&self, kind: &SensorKind) -> Option<DateTime<Utc>> {
+        let kind = kind.as_ref();
+
+        let started_at = sqlx::query_scalar!(
+            r#"SELECT MIN(date) AS "started_at: NaiveDateTime" FROM sensor_reading WHERE kind = ? AND alarm = 1 AND date > COALESCE((SELECT MAX(date) FROM sensor_reading WHERE kind = ? AND alarm = 0), '')"#,
+            kind,
+            kind
+        )
+        .fetch_one(&self.pool.0)
+        .await
+        .ok()??;
+
+        Some(DateTime::<Utc>::from_naive_utc_and_offset(started_at, Utc))
+    }
+
+    async fn find_all(&self) -> Vec<SensorReading> {
+        sqlx::query_as!(
+            RawSensorReading,
+            r#"SELECT date, kind, value, alarm FROM sensor_reading ORDER BY date, id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![])
+        .into_iter()
+        .map(SensorReading::try_from)
+        .filter_map(Result::ok)
+        .collect()
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn WriteOffRepository>)]
+#[build_method("default")]
+pub struct SqliteWriteOffRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawWriteOffFlag {
+    column_id: i64,
+    product_name: String,
+    quantity: i64,
+    flagged_at: NaiveDateTime,
+    reason: String,
+}
+
+impl TryFrom<RawWriteOffFlag> for WriteOffFlag {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawWriteOffFlag) -> Result<Self, Self::Error> {
+        Ok(WriteOffFlag {
+            column_id: Value::parse_i32(raw.column_id as i32)?,
+            product_name: Name::parse(&raw.product_name)?,
+            quantity: Quantity::parse_i32(raw.quantity as i32)?,
+            flagged_at: DateTime::<Utc>::from_naive_utc_and_offset(raw.flagged_at, Utc),
+            reason: raw.reason,
+        })
+    }
+}
+
+#[async_trait]
+impl WriteOffRepository for SqliteWriteOffRepository {
+    async fn find(&self, column_id: &Value) -> Option<WriteOffFlag> {
+        let column_id = column_id.as_value();
+
+        sqlx::query_as!(
+            RawWriteOffFlag,
+            r#"SELECT column_id, product_name, quantity, flagged_at, reason FROM write_off_flag WHERE column_id = ?"#,
+            column_id
+        )
+        .fetch_optional(&self.pool.0)
+        .await
+        .ok()??
+        .try_into()
+        .ok()
+    }
+
+    async fn save(&mut self, flag: WriteOffFlag) -> Result<(), Box<dyn std::error::Error>> {
+        let column_id = flag.column_id.as_value();
+        let product_name = flag.product_name.as_ref();
+        let quantity = flag.quantity.as_value();
+
+        sqlx::query!(
+            r#"INSERT INTO write_off_flag (column_id, product_name, quantity, flagged_at, reason) VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(column_id) DO UPDATE SET product_name = excluded.product_name, quantity = excluded.quantity, flagged_at = excluded.flagged_at, reason = excluded.reason"#,
+            column_id,
+            product_name,
+            quantity,
+            flag.flagged_at,
+            flag.reason
+        )
+        .execute(&self.pool.0)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn remove(&mut self, column_id: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        let column_id = column_id.as_value();
+
+        sqlx::query!(
+            r#"DELETE FROM write_off_flag WHERE column_id = ?"#,
+            column_id
+        )
+        .execute(&self.pool.0)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Vec<WriteOffFlag> {
+        sqlx::query_as!(
+            RawWriteOffFlag,
+            r#"SELECT column_id, product_name, quantity, flagged_at, reason FROM write_off_flag ORDER BY column_id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![])
+        .into_iter()
+        .map(WriteOffFlag::try_from)
+        .filter_map(Result::ok)
+        .collect()
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn FaultLogRepository>)]
+#[build_method("default")]
+pub struct SqliteFaultLogRepository {
+    #[deps]
+    pool: DbConn,
+}
+
+struct RawFault {
+    id: i64,
+    date: NaiveDateTime,
+    code: String,
+    actor: Option<String>,
+    context: String,
+    status: String,
+    acknowledged_by: Option<String>,
+    cleared_by: Option<String>,
+}
+
+impl TryFrom<RawFault> for Fault {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawFault) -> Result<Self, Self::Error> {
+        Ok(Fault {
+            id: Some(Value::parse_i32(raw.id as i32)?),
+            date: DateTime::<Utc>::from_naive_utc_and_offset(raw.date, Utc),
+            code: FaultCode::parse(&raw.code)?,
+            actor: raw.actor.as_deref().map(Name::parse).transpose()?,
+            context: raw.context,
+            status: FaultStatus::parse(&raw.status)?,
+            acknowledged_by: raw
+                .acknowledged_by
+                .as_deref()
+                .map(Name::parse)
+                .transpose()?,
+            cleared_by: raw.cleared_by.as_deref().map(Name::parse).transpose()?,
+        })
+    }
+}
+
+#[async_trait]
+impl FaultLogRepository for SqliteFaultLogRepository {
+    async fn find(&self, id: &Value) -> Option<Fault> {
+        let id = id.as_value();
+
+        sqlx::query_as!(
+            RawFault,
+            r#"SELECT id AS "id!", date, code, actor, context, status, acknowledged_by, cleared_by FROM fault_log WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool.0)
+        .await
+        .ok()??
+        .try_into()
+        .ok()
+    }
+
+    async fn save(&mut self, fault: Fault) -> Result<Fault, Box<dyn std::error::Error>> {
+        let code = fault.code.as_ref();
+        let actor = fault.actor.as_ref().map(|actor| actor.as_ref().to_string());
+        let status = fault.status.as_ref();
+        let acknowledged_by = fault
+            .acknowledged_by
+            .as_ref()
+            .map(|name| name.as_ref().to_string());
+        let cleared_by = fault
+            .cleared_by
+            .as_ref()
+            .map(|name| name.as_ref().to_string());
+
+        if let Some(id) = fault.id.as_ref().map(Value::as_value) {
+            sqlx::query!(
+                r#"UPDATE fault_log SET status = ?, acknowledged_by = ?, cleared_by = ? WHERE id = ?"#,
+                status,
+                acknowledged_by,
+                cleared_by,
+                id
+            )
+            .execute(&self.pool.0)
+            .await?;
+
+            return Ok(fault);
+        }
+
+        let id = sqlx::query!(
+            r#"INSERT INTO fault_log (date, code, actor, context, status, acknowledged_by, cleared_by) VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+            fault.date,
+            code,
+            actor,
+            fault.context,
+            status,
+            acknowledged_by,
+            cleared_by
+        )
+        .execute(&self.pool.0)
+        .await?
+        .last_insert_rowid();
+
+        Ok(Fault {
+            id: Some(Value::parse_i32(id as i32)?),
+            ..fault
+        })
+    }
+
+    async fn find_all(&self) -> Vec<Fault> {
+        sqlx::query_as!(
+            RawFault,
+            r#"SELECT id AS "id!", date, code, actor, context, status, acknowledged_by, cleared_by FROM fault_log ORDER BY id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await
+        .unwrap_or(vec![])
+        .into_iter()
+        .map(Fault::try_from)
+        .filter_map(Result::ok)
+        .collect()
+    }
+}
+
+#[derive(Default, Clone, DIBuilder)]
+#[build_as(Box<dyn StorageDiagnostics>)]
+#[build_method("default")]
+pub struct SqliteStorageDiagnostics {
+    #[deps]
+    pool: DbConn,
+}
+
+#[async_trait]
+impl StorageDiagnostics for SqliteStorageDiagnostics {
+    async fn ping(&self) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query("SELECT 1").execute(&self.pool.0).await?;
+
+        Ok(())
+    }
+
+    async fn schema_version(&self) -> Result<SchemaVersion, Box<dyn std::error::Error>> {
+        let expected = sqlx::migrate!("../migrations")
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .unwrap_or_default();
+
+        let applied = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1",
+        )
+        .fetch_one(&self.pool.0)
+        .await?;
+
+        Ok(SchemaVersion { applied, expected })
+    }
+
+    async fn negative_stock_columns(&self) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+        sqlx::query_scalar!(
+            r#"SELECT column_id FROM product WHERE quantity < 0 ORDER BY column_id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await?
+        .into_iter()
+        .map(|column_id| Value::parse_i32(column_id as i32))
+        .collect()
+    }
+
+    async fn orphaned_sales(&self) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+        sqlx::query_scalar!(
+            r#"SELECT sale.id AS "id!" FROM sale LEFT JOIN product ON product.column_id = sale.product_id WHERE product.column_id IS NULL ORDER BY sale.id"#
+        )
+        .fetch_all(&self.pool.0)
+        .await?
+        .into_iter()
+        .map(|id| Value::parse_i32(id as i32))
+        .collect()
+    }
+}
@@ -0,0 +1,738 @@
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use infrastructure::in_memory::{
+    InMemoryCashCollectionRepository, InMemoryCreditRepository, InMemoryCustomerAccountRepository,
+    InMemoryFaultLogRepository, InMemoryMachineHistoryRepository, InMemoryMachineStateRepository,
+    InMemoryPaymentJournalRepository, InMemoryProductRepository, InMemorySaleRepository,
+    InMemoryScheduleRepository, InMemorySensorReadingRepository, InMemoryStockMovementRepository,
+    InMemorySupplierRepository, InMemoryWriteOffRepository, InMemoryZReportRepository,
+};
+use vending_machine::application::states::{Admin, AuthResult, Guest, Resumed, Unlocked};
+use vending_machine::application::{PaymentTerminals, VendingMachine};
+use vending_machine::domain::entities::{
+    CashCount, Category, ChangePolicy, FaultCode, MachineState, Name, Password,
+    PaymentJournalEntry, PaymentMethod, PaymentPhase, Price, Product, Quantity, Receipt,
+    RecoveryAction, SchemaVersion, Value, VendOutcome,
+};
+use vending_machine::domain::interfaces::{
+    CreditRepository, Dispenser, MachineStateRepository, PaymentJournalRepository, PaymentTerminal,
+    ProductRepository, ReceiptPrinter, StockMovementRepository, StorageDiagnostics, Terminal,
+};
+
+#[derive(Clone)]
+struct FakeDispenser(VendOutcome);
+
+#[async_trait]
+impl Dispenser for FakeDispenser {
+    async fn vend(
+        &self,
+        _column_id: &Value,
+        _quantity: &Value,
+    ) -> Result<VendOutcome, Box<dyn Error>> {
+        Ok(self.0.clone())
+    }
+
+    async fn self_test(&self, _column_id: &Value) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct FakeStorageDiagnostics;
+
+#[async_trait]
+impl StorageDiagnostics for FakeStorageDiagnostics {
+    async fn ping(&self) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    async fn schema_version(&self) -> Result<SchemaVersion, Box<dyn Error>> {
+        Ok(SchemaVersion {
+            applied: None,
+            expected: 0,
+        })
+    }
+
+    async fn negative_stock_columns(&self) -> Result<Vec<Value>, Box<dyn Error>> {
+        Ok(vec![])
+    }
+
+    async fn orphaned_sales(&self) -> Result<Vec<Value>, Box<dyn Error>> {
+        Ok(vec![])
+    }
+}
+
+#[derive(Clone)]
+struct SilentReceiptPrinter;
+
+impl ReceiptPrinter for SilentReceiptPrinter {
+    fn print(&self, _receipt: &Receipt) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+type Ledger<T> = Arc<Mutex<Vec<T>>>;
+
+/// Cash terminals take the queued tendered amounts one request at a time;
+/// other methods authorize the exact amount due and record what is voided.
+#[derive(Clone)]
+struct FakeTerminal {
+    method: PaymentMethod,
+    tendered: Ledger<f32>,
+    pending: Arc<Mutex<Option<Price>>>,
+    refunded: Ledger<f32>,
+    voided: Ledger<f32>,
+    reversed: Ledger<(f32, Option<String>)>,
+}
+
+impl FakeTerminal {
+    const REFERENCE: &'static str = "auth-1";
+
+    fn new(method: PaymentMethod) -> Self {
+        Self {
+            method,
+            tendered: Arc::default(),
+            pending: Arc::default(),
+            refunded: Arc::default(),
+            voided: Arc::default(),
+            reversed: Arc::default(),
+        }
+    }
+
+    fn tender(self, amounts: &[f32]) -> Self {
+        self.tendered.lock().unwrap().extend(amounts);
+        self
+    }
+
+    fn refunded(&self) -> Vec<f32> {
+        self.refunded.lock().unwrap().clone()
+    }
+
+    fn voided(&self) -> Vec<f32> {
+        self.voided.lock().unwrap().clone()
+    }
+
+    fn reversed(&self) -> Vec<(f32, Option<String>)> {
+        self.reversed.lock().unwrap().clone()
+    }
+}
+
+impl Terminal for FakeTerminal {
+    fn prompt(&self, _message: &str) {}
+}
+
+#[async_trait]
+impl PaymentTerminal for FakeTerminal {
+    fn method(&self) -> PaymentMethod {
+        self.method.clone()
+    }
+
+    async fn request(&self, due: Price) -> Result<Price, Box<dyn Error>> {
+        if self.method != PaymentMethod::Cash {
+            *self.pending.lock().unwrap() = Some(due.clone());
+            return Ok(due);
+        }
+
+        let mut tendered = self.tendered.lock().unwrap();
+        match tendered.is_empty() {
+            true => Err(Box::from("Payment cancelled")),
+            false => Price::parse_f32(tendered.remove(0)),
+        }
+    }
+
+    async fn refund(&self, amount: Price) -> Result<(), Box<dyn Error>> {
+        if self.method != PaymentMethod::Cash {
+            return Err(Box::from("Only cash can be refunded"));
+        }
+
+        self.refunded.lock().unwrap().push(amount.as_value());
+        Ok(())
+    }
+
+    fn reference(&self) -> Option<String> {
+        match self.method {
+            PaymentMethod::Cash => None,
+            _ => Some(Self::REFERENCE.to_string()),
+        }
+    }
+
+    async fn reverse(&self, amount: Price, reference: Option<&str>) -> Result<(), Box<dyn Error>> {
+        if self.method == PaymentMethod::Cash {
+            return self.refund(amount).await;
+        }
+
+        self.reversed
+            .lock()
+            .unwrap()
+            .push((amount.as_value(), reference.map(str::to_string)));
+        Ok(())
+    }
+
+    async fn confirm(&self) -> Result<(), Box<dyn Error>> {
+        self.pending.lock().unwrap().take();
+        Ok(())
+    }
+
+    async fn cancel(&self) -> Result<Option<Price>, Box<dyn Error>> {
+        let voided = self.pending.lock().unwrap().take();
+        if let Some(amount) = &voided {
+            self.voided.lock().unwrap().push(amount.as_value());
+        }
+
+        Ok(voided)
+    }
+
+    async fn release(&self, amount: Price) -> Result<Option<Price>, Box<dyn Error>> {
+        let mut pending = self.pending.lock().unwrap();
+        let Some(authorized) = pending.as_ref() else {
+            return Ok(None);
+        };
+
+        *pending = Price::parse_f32(authorized.as_value() - amount.as_value()).ok();
+        self.voided.lock().unwrap().push(amount.as_value());
+        Ok(Some(amount))
+    }
+}
+
+#[derive(Clone)]
+struct UnreadableMachineState;
+
+#[async_trait]
+impl MachineStateRepository for UnreadableMachineState {
+    async fn load(&self) -> Result<Option<MachineState>, Box<dyn Error>> {
+        Err(Box::from("Machine state is unreadable"))
+    }
+
+    async fn save(&mut self, _state: MachineState) -> Result<(), Box<dyn Error>> {
+        Err(Box::from("Machine state is unreadable"))
+    }
+}
+
+struct Fixture {
+    products: InMemoryProductRepository,
+    stock_movements: InMemoryStockMovementRepository,
+    credits: InMemoryCreditRepository,
+    journal: InMemoryPaymentJournalRepository,
+    machine_state: Box<dyn MachineStateRepository>,
+    dispenser: FakeDispenser,
+    terminals: PaymentTerminals,
+}
+
+impl Fixture {
+    async fn new(terminals: &[&FakeTerminal]) -> Self {
+        let mut products = InMemoryProductRepository::default();
+        products
+            .save(Product {
+                column_id: Value::parse("1").unwrap(),
+                name: Name::parse("Cola").unwrap(),
+                category: Category::parse("Drinks").unwrap(),
+                price: Price::parse_f32(1.5).unwrap(),
+                quantity: Quantity::parse("5").unwrap(),
+                refrigerated: false,
+            })
+            .await
+            .unwrap();
+
+        Self {
+            products,
+            stock_movements: InMemoryStockMovementRepository::default(),
+            credits: InMemoryCreditRepository::default(),
+            journal: InMemoryPaymentJournalRepository::default(),
+            machine_state: Box::new(InMemoryMachineStateRepository::default()),
+            dispenser: FakeDispenser(VendOutcome::Dispensed),
+            terminals: terminals
+                .iter()
+                .fold(PaymentTerminals::default(), |terminals, terminal| {
+                    terminals.register(Box::new((*terminal).clone()))
+                }),
+        }
+    }
+
+    fn dispensing(self, outcome: VendOutcome) -> Self {
+        Self {
+            dispenser: FakeDispenser(outcome),
+            ..self
+        }
+    }
+
+    fn with_machine_state(self, machine_state: impl MachineStateRepository + 'static) -> Self {
+        Self {
+            machine_state: Box::new(machine_state),
+            ..self
+        }
+    }
+
+    fn machine(&self) -> VendingMachine<Guest, Unlocked> {
+        VendingMachine::new(
+            Box::new(self.products.clone()),
+            Box::new(InMemorySaleRepository::new(
+                self.products.clone(),
+                self.stock_movements.clone(),
+            )),
+            self.machine_state.clone(),
+            Box::new(InMemoryMachineHistoryRepository::default()),
+            Box::new(InMemoryScheduleRepository::default()),
+            Box::new(InMemorySupplierRepository::default()),
+            Box::new(InMemoryCashCollectionRepository::default()),
+            Box::new(self.stock_movements.clone()),
+            Box::new(InMemoryZReportRepository::default()),
+            Box::new(InMemoryCustomerAccountRepository::default()),
+            Box::new(self.credits.clone()),
+            Box::new(self.journal.clone()),
+            Box::new(InMemorySensorReadingRepository::default()),
+            Box::new(InMemoryWriteOffRepository::default()),
+            Box::new(InMemoryFaultLogRepository::default()),
+            Box::new(FakeStorageDiagnostics),
+            Box::new(self.dispenser.clone()),
+            self.terminals.clone(),
+            Box::new(SilentReceiptPrinter),
+        )
+    }
+
+    async fn stock(&self) -> u32 {
+        self.products
+            .find(Value::parse("1").unwrap())
+            .await
+            .unwrap()
+            .quantity
+            .as_value()
+    }
+}
+
+fn column(id: &str) -> Value {
+    Value::parse(id).unwrap()
+}
+
+async fn admin(machine: VendingMachine<Guest, Unlocked>) -> VendingMachine<Admin, Unlocked> {
+    let username = Name::parse("admin").unwrap();
+    let password = Password::parse("admin_pass").unwrap();
+
+    match machine.login(&username, &password).await {
+        AuthResult::SuccessAdmin(machine) => machine,
+        _ => panic!("admin login failed"),
+    }
+}
+
+#[tokio::test]
+async fn buy_with_cash_records_the_sale_and_pays_out_change() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash).tender(&[1.0, 1.0]);
+    let fixture = Fixture::new(&[&cash]).await;
+    let mut machine = fixture.machine();
+
+    let receipt = machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::Refund,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(receipt.total(), 1.5);
+    assert_eq!(receipt.amount_paid, 2.0);
+    assert_eq!(receipt.change, 0.5);
+    assert_eq!(cash.refunded(), vec![0.5]);
+    assert_eq!(machine.credit().await, 0.0);
+    assert_eq!(fixture.stock().await, 4);
+
+    let movements = fixture.stock_movements.find_all().await;
+    assert_eq!(movements.len(), 1);
+    assert_eq!(movements[0].delta, -1);
+}
+
+#[tokio::test]
+async fn buy_keeping_change_spends_the_credit_on_the_next_purchase() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash).tender(&[2.0, 1.0]);
+    let fixture = Fixture::new(&[&cash]).await;
+    let mut machine = fixture.machine();
+
+    machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::KeepAsCredit,
+        )
+        .await
+        .unwrap();
+    assert_eq!(machine.credit().await, 0.5);
+
+    let receipt = machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::Refund,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(receipt.amount_paid, 1.5);
+    assert_eq!(receipt.change, 0.0);
+    assert_eq!(machine.credit().await, 0.0);
+    assert!(cash.refunded().is_empty());
+
+    let sales = admin(machine).await.list_sales_report().await;
+    assert_eq!(sales.len(), 2);
+    assert_eq!(sales[0].paid_from_credit, 0.0);
+    assert_eq!(sales[1].paid_from_credit, 0.5);
+}
+
+#[tokio::test]
+async fn credit_kept_by_one_guest_is_not_spent_by_another() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash).tender(&[2.0, 2.0, 1.0]);
+    let fixture = Fixture::new(&[&cash]).await;
+    let mut first = fixture.machine();
+    let mut second = fixture.machine();
+
+    for machine in [&mut first, &mut second] {
+        machine
+            .buy(
+                column("1"),
+                column("1"),
+                PaymentMethod::Cash,
+                ChangePolicy::KeepAsCredit,
+            )
+            .await
+            .unwrap();
+    }
+    first
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::KeepAsCredit,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(first.credit().await, 0.0);
+    assert_eq!(second.credit().await, 0.5);
+    assert_eq!(fixture.credits.find_all().await.len(), 1);
+}
+
+#[tokio::test]
+async fn pay_refunds_partial_cash_when_the_customer_gives_up() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash).tender(&[1.0]);
+    let fixture = Fixture::new(&[&cash]).await;
+    let mut machine = fixture.machine();
+
+    let bought = machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::Refund,
+        )
+        .await;
+
+    assert!(bought.is_err_and(|e| e.to_string().starts_with("Payment failed")));
+    assert_eq!(cash.refunded(), vec![1.0]);
+    assert_eq!(fixture.stock().await, 5);
+    let journal = fixture.journal.find_all().await;
+    assert_eq!(
+        journal.last().map(|entry| &entry.phase),
+        Some(&PaymentPhase::Aborted)
+    );
+}
+
+#[tokio::test]
+async fn failed_vend_cancels_the_card_authorization() {
+    let card = FakeTerminal::new(PaymentMethod::Card);
+    let fixture = Fixture::new(&[&card])
+        .await
+        .dispensing(VendOutcome::MotorJammed);
+    let mut machine = fixture.machine();
+
+    let bought = machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Card,
+            ChangePolicy::Refund,
+        )
+        .await;
+
+    assert!(bought.is_err());
+    assert_eq!(card.voided(), vec![1.5]);
+    assert_eq!(machine.credit().await, 0.0);
+    assert_eq!(fixture.stock().await, 5);
+
+    let admin = admin(machine).await;
+    assert!(admin.list_sales_report().await.is_empty());
+    assert!(admin
+        .list_faults()
+        .await
+        .iter()
+        .any(|fault| fault.code == FaultCode::MotorJammed));
+}
+
+#[tokio::test]
+async fn partial_vend_charges_only_for_dispensed_items() {
+    let card = FakeTerminal::new(PaymentMethod::Card);
+    let fixture = Fixture::new(&[&card])
+        .await
+        .dispensing(VendOutcome::PartiallyDispensed(
+            1,
+            FaultCode::NoDropDetected,
+        ));
+    let mut machine = fixture.machine();
+
+    let receipt = machine
+        .buy(
+            column("1"),
+            column("3"),
+            PaymentMethod::Card,
+            ChangePolicy::Refund,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(receipt.items[0].quantity.as_value(), 1);
+    assert_eq!(receipt.total(), 1.5);
+    assert_eq!(card.voided(), vec![3.0]);
+    assert_eq!(machine.credit().await, 0.0);
+    assert_eq!(fixture.stock().await, 4);
+}
+
+async fn open_transaction(
+    journal: &mut InMemoryPaymentJournalRepository,
+    terminal: &FakeTerminal,
+    inserted: f32,
+) -> Value {
+    let transaction_id = journal.next_transaction_id().await.unwrap();
+    let entry = |phase, amount, method: Option<PaymentMethod>| PaymentJournalEntry {
+        transaction_id: transaction_id.clone(),
+        date: Utc::now(),
+        phase,
+        amount,
+        reference: method.as_ref().and_then(|_| terminal.reference()),
+        method,
+    };
+
+    journal
+        .save(entry(PaymentPhase::Started, 1.5, None))
+        .await
+        .unwrap();
+    journal
+        .save(entry(
+            PaymentPhase::Inserted,
+            inserted,
+            Some(terminal.method()),
+        ))
+        .await
+        .unwrap();
+
+    transaction_id
+}
+
+#[tokio::test]
+async fn recover_payments_refunds_open_cash_transactions() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash);
+    let mut fixture = Fixture::new(&[&cash]).await;
+    open_transaction(&mut fixture.journal, &cash, 2.0).await;
+
+    let recovered = fixture
+        .machine()
+        .into_payment_recovery()
+        .unwrap()
+        .run(RecoveryAction::Refund)
+        .await
+        .unwrap();
+
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].recovery(), Some((RecoveryAction::Refund, 2.0)));
+    assert_eq!(cash.refunded(), vec![2.0]);
+}
+
+#[tokio::test]
+async fn recover_payments_reverses_non_cash_payments_through_their_method() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash);
+    let wallet = FakeTerminal::new(PaymentMethod::Wallet);
+    let mut fixture = Fixture::new(&[&cash, &wallet]).await;
+    open_transaction(&mut fixture.journal, &wallet, 1.5).await;
+
+    let recovered = fixture
+        .machine()
+        .into_payment_recovery()
+        .unwrap()
+        .run(RecoveryAction::Credit)
+        .await
+        .unwrap();
+
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(
+        wallet.reversed(),
+        vec![(1.5, Some(FakeTerminal::REFERENCE.to_string()))]
+    );
+    assert!(cash.refunded().is_empty());
+}
+
+#[tokio::test]
+async fn recover_payments_refunds_credit_left_on_completed_transactions() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash);
+    let mut fixture = Fixture::new(&[&cash]).await;
+    let transaction_id = fixture.journal.next_transaction_id().await.unwrap();
+    fixture
+        .journal
+        .save(PaymentJournalEntry {
+            transaction_id: transaction_id.clone(),
+            date: Utc::now(),
+            phase: PaymentPhase::Completed,
+            amount: 0.5,
+            method: None,
+            reference: None,
+        })
+        .await
+        .unwrap();
+    fixture.credits.save(&transaction_id, 0.5).await.unwrap();
+
+    let recovered = fixture
+        .machine()
+        .into_payment_recovery()
+        .unwrap()
+        .run(RecoveryAction::Refund)
+        .await
+        .unwrap();
+
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].recovery(), Some((RecoveryAction::Refund, 0.5)));
+    assert_eq!(cash.refunded(), vec![0.5]);
+    assert!(fixture.credits.find_all().await.is_empty());
+}
+
+#[tokio::test]
+async fn close_day_summarises_sales_by_payment_method() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash).tender(&[2.0]);
+    let card = FakeTerminal::new(PaymentMethod::Card);
+    let fixture = Fixture::new(&[&cash, &card]).await;
+    let mut machine = fixture.machine();
+
+    machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::KeepAsCredit,
+        )
+        .await
+        .unwrap();
+    machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Card,
+            ChangePolicy::Refund,
+        )
+        .await
+        .unwrap();
+
+    let mut admin = admin(machine).await;
+    let report = admin.close_day().await.unwrap();
+
+    assert_eq!(report.number.as_value(), 1);
+    assert_eq!(report.sales_count, 2);
+    assert_eq!(report.revenue, 3.0);
+    assert_eq!(report.stock_movements.len(), 2);
+
+    let total = |method: &str| {
+        report
+            .payment_methods
+            .iter()
+            .find(|entry| entry.method == method)
+            .map(|entry| (entry.sales_count, entry.total))
+    };
+    assert_eq!(total("cash"), Some((1, 1.5)));
+    assert_eq!(total("card"), Some((1, 1.0)));
+    assert_eq!(total("credit"), Some((0, 0.5)));
+
+    let next = admin.close_day().await.unwrap();
+    assert_eq!(next.number.as_value(), 2);
+    assert_eq!(next.sales_count, 0);
+    assert_eq!(next.revenue, 0.0);
+}
+
+fn counted(denomination: f32, count: &str) -> Vec<CashCount> {
+    vec![CashCount {
+        denomination: Price::parse_f32(denomination).unwrap(),
+        count: Quantity::parse(count).unwrap(),
+    }]
+}
+
+#[tokio::test]
+async fn collect_cash_expects_credit_held_in_the_cash_box() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash).tender(&[2.0]);
+    let fixture = Fixture::new(&[&cash]).await;
+    let mut machine = fixture.machine();
+
+    machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::KeepAsCredit,
+        )
+        .await
+        .unwrap();
+
+    let reconciliation = admin(machine)
+        .await
+        .collect_cash(counted(0.5, "4"))
+        .await
+        .unwrap();
+
+    assert_eq!(reconciliation.expected, 2.0);
+    assert!(reconciliation.is_balanced());
+}
+
+#[tokio::test]
+async fn collect_cash_subtracts_recovered_and_returned_cash() {
+    let cash = FakeTerminal::new(PaymentMethod::Cash).tender(&[2.0]);
+    let mut fixture = Fixture::new(&[&cash]).await;
+    open_transaction(&mut fixture.journal, &cash, 1.0).await;
+    fixture
+        .machine()
+        .into_payment_recovery()
+        .unwrap()
+        .run(RecoveryAction::Refund)
+        .await
+        .unwrap();
+
+    let mut machine = fixture.machine();
+    machine
+        .buy(
+            column("1"),
+            column("1"),
+            PaymentMethod::Cash,
+            ChangePolicy::KeepAsCredit,
+        )
+        .await
+        .unwrap();
+    machine.return_credit().await.unwrap();
+
+    let reconciliation = admin(machine)
+        .await
+        .collect_cash(counted(0.5, "3"))
+        .await
+        .unwrap();
+
+    assert_eq!(cash.refunded(), vec![1.0, 0.5]);
+    assert_eq!(reconciliation.expected, 1.5);
+    assert!(reconciliation.is_balanced());
+}
+
+#[tokio::test]
+async fn resume_locks_the_machine_when_its_state_cannot_be_loaded() {
+    let fixture = Fixture::new(&[])
+        .await
+        .with_machine_state(UnreadableMachineState);
+    let machine = fixture.machine();
+
+    assert!(machine.lock_state_changed().await);
+    assert!(matches!(machine.resume().await, Resumed::Locked(_)));
+}
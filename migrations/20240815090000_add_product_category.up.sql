@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE product ADD COLUMN category TEXT NOT NULL DEFAULT 'General';
@@ -0,0 +1,8 @@
+-- Add up migration script here
+CREATE TABLE machine_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    lock_state TEXT NOT NULL,
+    changed_by TEXT,
+    reason TEXT,
+    changed_at DATETIME NOT NULL
+);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE machine_history;
+ALTER TABLE machine_state DROP COLUMN expected_back;
@@ -0,0 +1,10 @@
+-- Add up migration script here
+ALTER TABLE machine_state ADD COLUMN expected_back DATETIME;
+
+CREATE TABLE machine_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date DATETIME NOT NULL,
+    kind TEXT NOT NULL,
+    actor TEXT,
+    details TEXT NOT NULL
+);
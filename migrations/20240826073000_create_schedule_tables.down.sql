@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE schedule_window;
+DROP TABLE schedule_exception;
@@ -0,0 +1,12 @@
+-- Add up migration script here
+CREATE TABLE schedule_window (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    weekday INTEGER NOT NULL CHECK (weekday BETWEEN 0 AND 6),
+    opens_at TIME NOT NULL,
+    closes_at TIME NOT NULL
+);
+
+CREATE TABLE schedule_exception (
+    date DATE PRIMARY KEY,
+    description TEXT NOT NULL
+);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE supplier_column;
+DROP TABLE supplier;
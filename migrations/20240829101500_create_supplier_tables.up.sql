@@ -0,0 +1,10 @@
+-- Add up migration script here
+CREATE TABLE supplier (
+    name TEXT PRIMARY KEY,
+    password TEXT NOT NULL
+);
+
+CREATE TABLE supplier_column (
+    column_id INTEGER PRIMARY KEY,
+    supplier_name TEXT NOT NULL REFERENCES supplier(name)
+);
@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE cash_collection (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date DATETIME NOT NULL,
+    collected_by TEXT
+);
+
+CREATE TABLE cash_collection_count (
+    collection_id INTEGER NOT NULL REFERENCES cash_collection(id),
+    denomination REAL NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (collection_id, denomination)
+);
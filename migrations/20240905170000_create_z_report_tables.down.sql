@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP TRIGGER z_report_no_delete;
+DROP TRIGGER z_report_no_update;
+DROP TABLE z_report_stock_movement;
+DROP TABLE z_report_payment_method;
+DROP TABLE z_report_product_revenue;
+DROP TABLE z_report;
+DROP TABLE stock_movement;
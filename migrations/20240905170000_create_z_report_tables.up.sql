@@ -0,0 +1,53 @@
+-- Add up migration script here
+CREATE TABLE stock_movement (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date DATETIME NOT NULL,
+    column_id INTEGER NOT NULL,
+    product_name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    delta INTEGER NOT NULL
+);
+
+CREATE TABLE z_report (
+    number INTEGER PRIMARY KEY,
+    opened_at DATETIME,
+    closed_at DATETIME NOT NULL,
+    closed_by TEXT,
+    sales_count INTEGER NOT NULL,
+    revenue REAL NOT NULL,
+    refunds REAL NOT NULL,
+    cash_collected REAL NOT NULL
+);
+
+CREATE TABLE z_report_product_revenue (
+    report_number INTEGER NOT NULL REFERENCES z_report(number),
+    product_name TEXT NOT NULL,
+    sales_count INTEGER NOT NULL,
+    revenue REAL NOT NULL
+);
+
+CREATE TABLE z_report_payment_method (
+    report_number INTEGER NOT NULL REFERENCES z_report(number),
+    method TEXT NOT NULL,
+    sales_count INTEGER NOT NULL,
+    total REAL NOT NULL
+);
+
+CREATE TABLE z_report_stock_movement (
+    report_number INTEGER NOT NULL REFERENCES z_report(number),
+    date DATETIME NOT NULL,
+    column_id INTEGER NOT NULL,
+    product_name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    delta INTEGER NOT NULL
+);
+
+CREATE TRIGGER z_report_no_update BEFORE UPDATE ON z_report
+BEGIN
+    SELECT RAISE(ABORT, 'Z-reports are immutable');
+END;
+
+CREATE TRIGGER z_report_no_delete BEFORE DELETE ON z_report
+BEGIN
+    SELECT RAISE(ABORT, 'Z-reports are immutable');
+END;
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP INDEX sale_reversal_of;
+ALTER TABLE sale DROP COLUMN void_reason;
+ALTER TABLE sale DROP COLUMN reversal_of;
+ALTER TABLE sale DROP COLUMN quantity;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+ALTER TABLE sale ADD COLUMN quantity INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE sale ADD COLUMN reversal_of INTEGER REFERENCES sale(id);
+ALTER TABLE sale ADD COLUMN void_reason TEXT;
+
+CREATE UNIQUE INDEX sale_reversal_of ON sale(reversal_of);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE customer_account_transaction;
+DROP TABLE customer_account;
@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE customer_account (
+    badge_id TEXT PRIMARY KEY,
+    holder TEXT NOT NULL
+);
+
+CREATE TABLE customer_account_transaction (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    badge_id TEXT NOT NULL REFERENCES customer_account(badge_id),
+    date DATETIME NOT NULL,
+    kind TEXT NOT NULL,
+    amount REAL NOT NULL
+);
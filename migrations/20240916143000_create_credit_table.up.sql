@@ -0,0 +1,6 @@
+-- Add up migration script here
+CREATE TABLE credit (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    amount REAL NOT NULL,
+    updated_at DATETIME NOT NULL
+);
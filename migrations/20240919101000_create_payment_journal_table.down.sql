@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP INDEX payment_journal_transaction_id;
+DROP TABLE payment_journal;
@@ -0,0 +1,10 @@
+-- Add up migration script here
+CREATE TABLE payment_journal (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_id INTEGER NOT NULL,
+    date DATETIME NOT NULL,
+    phase TEXT NOT NULL,
+    amount REAL NOT NULL
+);
+
+CREATE INDEX payment_journal_transaction_id ON payment_journal (transaction_id);
@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE sale DROP COLUMN payment_method;
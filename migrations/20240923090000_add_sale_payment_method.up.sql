@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE sale ADD COLUMN payment_method TEXT NOT NULL DEFAULT 'cash';
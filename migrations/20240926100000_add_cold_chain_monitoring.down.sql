@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE write_off_flag;
+DROP INDEX sensor_reading_kind_date;
+DROP TABLE sensor_reading;
+ALTER TABLE product DROP COLUMN refrigerated;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+ALTER TABLE product ADD COLUMN refrigerated BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE TABLE sensor_reading (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date DATETIME NOT NULL,
+    kind TEXT NOT NULL,
+    value REAL NOT NULL,
+    alarm BOOLEAN NOT NULL
+);
+
+CREATE INDEX sensor_reading_kind_date ON sensor_reading (kind, date);
+
+CREATE TABLE write_off_flag (
+    column_id INTEGER PRIMARY KEY REFERENCES product(column_id),
+    product_name TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    flagged_at DATETIME NOT NULL,
+    reason TEXT NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Add up migration script here
+CREATE TABLE fault_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date DATETIME NOT NULL,
+    code TEXT NOT NULL,
+    actor TEXT,
+    context TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'open',
+    acknowledged_by TEXT,
+    cleared_by TEXT
+);
@@ -0,0 +1,6 @@
+-- Add up migration script here
+DELETE FROM supplier_column WHERE supplier_name IN (
+    SELECT name FROM supplier WHERE name = 'supplier' AND password = 'supplier_pass'
+);
+
+DELETE FROM supplier WHERE name = 'supplier' AND password = 'supplier_pass';
@@ -0,0 +1,16 @@
+-- Add down migration script here
+ALTER TABLE sale DROP COLUMN paid_from_credit;
+
+CREATE TABLE machine_credit (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    amount REAL NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+INSERT INTO machine_credit (id, amount, updated_at)
+SELECT 1, SUM(amount), MAX(updated_at)
+FROM credit
+HAVING COUNT(*) > 0;
+
+DROP TABLE credit;
+ALTER TABLE machine_credit RENAME TO credit;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE transaction_credit (
+    transaction_id INTEGER PRIMARY KEY,
+    amount REAL NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+INSERT INTO transaction_credit (transaction_id, amount, updated_at)
+SELECT COALESCE((SELECT MAX(transaction_id) FROM payment_journal), 0), amount, updated_at
+FROM credit
+WHERE amount > 0;
+
+DROP TABLE credit;
+ALTER TABLE transaction_credit RENAME TO credit;
+
+ALTER TABLE sale ADD COLUMN paid_from_credit REAL NOT NULL DEFAULT 0;
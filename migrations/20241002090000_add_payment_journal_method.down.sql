@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE payment_journal DROP COLUMN reference;
+ALTER TABLE payment_journal DROP COLUMN payment_method;
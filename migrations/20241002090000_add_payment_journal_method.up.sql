@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE payment_journal ADD COLUMN payment_method TEXT;
+ALTER TABLE payment_journal ADD COLUMN reference TEXT;
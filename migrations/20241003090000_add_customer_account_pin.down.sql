@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE customer_account DROP COLUMN pin_hash;
@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE customer_account ADD COLUMN pin_hash TEXT;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP INDEX payment_journal_transaction_phase;
+DROP TABLE payment_transaction;
@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE payment_transaction (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    started_at DATETIME NOT NULL
+);
+
+INSERT INTO payment_transaction (id, started_at)
+SELECT transaction_id, MIN(date)
+FROM payment_journal
+GROUP BY transaction_id;
+
+CREATE UNIQUE INDEX payment_journal_transaction_phase ON payment_journal (transaction_id, phase)
+WHERE phase <> 'inserted';
@@ -0,0 +1,13 @@
+-- Add down migration script here
+CREATE TABLE transaction_credit (
+    transaction_id INTEGER PRIMARY KEY,
+    amount REAL NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+INSERT INTO transaction_credit (transaction_id, amount, updated_at)
+SELECT transaction_id, amount, updated_at
+FROM credit;
+
+DROP TABLE credit;
+ALTER TABLE transaction_credit RENAME TO credit;
@@ -0,0 +1,17 @@
+-- Add up migration script here
+INSERT OR IGNORE INTO payment_transaction (id, started_at)
+SELECT transaction_id, updated_at
+FROM credit;
+
+CREATE TABLE transaction_credit (
+    transaction_id INTEGER PRIMARY KEY REFERENCES payment_transaction (id),
+    amount REAL NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+INSERT INTO transaction_credit (transaction_id, amount, updated_at)
+SELECT transaction_id, amount, updated_at
+FROM credit;
+
+DROP TABLE credit;
+ALTER TABLE transaction_credit RENAME TO credit;
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DELETE FROM payment_journal WHERE phase = 'refunded';
+DROP INDEX payment_journal_transaction_phase;
+
+CREATE UNIQUE INDEX payment_journal_transaction_phase ON payment_journal (transaction_id, phase)
+WHERE phase <> 'inserted';
@@ -0,0 +1,5 @@
+-- Add up migration script here
+DROP INDEX payment_journal_transaction_phase;
+
+CREATE UNIQUE INDEX payment_journal_transaction_phase ON payment_journal (transaction_id, phase)
+WHERE phase NOT IN ('inserted', 'refunded');
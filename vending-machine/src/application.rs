@@ -1,21 +1,41 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Local, TimeZone, Utc};
 use yadir::core::contracts::DIBuilder;
 use yadir::core::primitives::DIObj;
 use yadir::{deps, let_deps};
 
 use crate::application::states::*;
-use crate::domain::entities::{Name, Password, Price, Product, Sale, Value};
-use crate::domain::interfaces::{PaymentTerminal, ProductRepository, SaleRepository};
+use crate::domain::entities::{
+    CashCollection, CashCount, ChangePolicy, ColdChainPolicy, CustomerAccount, DiagnosticCheck,
+    Fault, FaultCode, FaultStatus, HashedPassword, LockReason, LockState, MachineEvent,
+    MachineEventKind, MachineState, Name, Password, PaymentJournalEntry, PaymentMethod,
+    PaymentMethodTotal, PaymentPhase, Pin, Price, Product, ProductRevenue, Quantity, Receipt,
+    ReceiptItem, RecoveryAction, Reversal, Sale, Schedule, SchemaVersion, SensorKind,
+    SensorReading, StockMovement, StockMovementKind, SupplierAccount, Value, VoidReason,
+    WriteOffFlag, ZReport,
+};
+use crate::domain::interfaces::{
+    CashCollectionRepository, CreditRepository, CustomerAccountRepository, Dispenser,
+    FaultLogRepository, MachineHistoryRepository, MachineStateRepository, PaymentJournalRepository,
+    PaymentTerminal, ProductRepository, ReceiptPrinter, SaleRepository, ScheduleRepository, Sensor,
+    SensorReadingRepository, StockMovementRepository, StorageDiagnostics, SupplierRepository,
+    Terminal, WriteOffRepository, ZReportRepository,
+};
+use crate::domain::queries::ProductQuery;
+use crate::domain::views::{CashReconciliation, PaymentTransaction, SupplierReport};
 
 pub mod states {
-    use dyn_clone::{clone_trait_object, DynClone};
+    use std::fmt::Debug;
 
-    use crate::application::VendingMachine;
+    use dyn_clone::DynClone;
 
-    clone_trait_object!(Role);
-    clone_trait_object!(LockStatus);
+    use crate::application::VendingMachine;
+    use crate::domain::entities::{LockState, Product};
+    use crate::domain::views::PublicProductView;
 
-    pub trait Role: Send + Sync + DynClone {}
+    pub trait Role: Send + Sync + DynClone {
+        type ProductView: From<Product> + Clone + Debug + Send + Sync;
+    }
     pub trait Authenticated: Role {}
 
     #[derive(Clone)]
@@ -27,20 +47,91 @@ pub mod states {
     #[derive(Clone)]
     pub struct Supplier;
 
-    impl Role for Guest {}
-    impl Role for Admin {}
-    impl Role for Supplier {}
+    #[derive(Clone)]
+    pub struct Technician;
+
+    #[derive(Clone)]
+    pub struct System;
+
+    impl Role for Guest {
+        type ProductView = PublicProductView;
+    }
+    impl Role for Admin {
+        type ProductView = Product;
+    }
+    impl Role for Supplier {
+        type ProductView = Product;
+    }
+    impl Role for Technician {
+        type ProductView = Product;
+    }
+    impl Role for System {
+        type ProductView = Product;
+    }
 
     impl Authenticated for Admin {}
     impl Authenticated for Supplier {}
+    impl Authenticated for Technician {}
+    impl Authenticated for System {}
+
+    pub trait CanViewSales: Authenticated {}
+    pub trait CanViewHistory: Authenticated {}
+    pub trait CanManageSchedule: Authenticated {}
+    pub trait CanManageUsers: Authenticated {}
+    pub trait CanLock: Authenticated {}
+    pub trait CanService: Authenticated {}
+    pub trait CanDiagnose: Authenticated {}
+    pub trait CanCollectCash: Authenticated {}
+    pub trait CanCloseDay: Authenticated {}
+    pub trait CanVoidSales: Authenticated {}
+    pub trait CanManageAccounts: Authenticated {}
+    pub trait CanMonitorSensors: Authenticated {}
+    pub trait CanManageFaults: Authenticated {}
+    pub trait CanRecoverPayments: Authenticated {}
+    pub trait CanRestock: Authenticated {}
+    pub trait CanRestockOwnColumns: Authenticated {}
+
+    impl CanViewSales for Admin {}
+    impl CanViewHistory for Admin {}
+    impl CanManageSchedule for Admin {}
+    impl CanManageUsers for Admin {}
+    impl CanLock for Admin {}
+    impl CanCollectCash for Admin {}
+    impl CanCloseDay for Admin {}
+    impl CanVoidSales for Admin {}
+    impl CanManageAccounts for Admin {}
+    impl CanMonitorSensors for Admin {}
+    impl CanManageFaults for Admin {}
+    impl CanDiagnose for Admin {}
+
+    impl CanRestockOwnColumns for Supplier {}
+
+    impl CanRestock for Technician {}
+    impl CanService for Technician {}
+    impl CanDiagnose for Technician {}
+    impl CanManageFaults for Technician {}
+
+    impl CanManageSchedule for System {}
+    impl CanLock for System {}
+    impl CanMonitorSensors for System {}
+    impl CanRecoverPayments for System {}
 
     pub enum AuthResult<L: LockStatus> {
         SuccessAdmin(VendingMachine<Admin, L>),
         SuccessSupplier(VendingMachine<Supplier, L>),
+        SuccessTechnician(VendingMachine<Technician, L>),
         Failure(VendingMachine<Guest, L>),
     }
 
-    pub trait LockStatus: Send + Sync + DynClone {}
+    pub enum Resumed<U: Role> {
+        Unlocked(VendingMachine<U, Unlocked>),
+        Locked(VendingMachine<U, Locked>),
+        Maintenance(VendingMachine<U, Maintenance>),
+    }
+
+    pub trait LockStatus: Send + Sync + DynClone {
+        const STATE: LockState;
+    }
 
     #[derive(Clone)]
     pub struct Locked;
@@ -48,15 +139,92 @@ pub mod states {
     #[derive(Clone)]
     pub struct Unlocked;
 
-    impl LockStatus for Locked {}
-    impl LockStatus for Unlocked {}
+    #[derive(Clone)]
+    pub struct Maintenance;
+
+    impl LockStatus for Locked {
+        const STATE: LockState = LockState::Locked;
+    }
+    impl LockStatus for Unlocked {
+        const STATE: LockState = LockState::Unlocked;
+    }
+    impl LockStatus for Maintenance {
+        const STATE: LockState = LockState::Maintenance;
+    }
+
+    pub trait Restockable: LockStatus {}
+
+    impl Restockable for Unlocked {}
+    impl Restockable for Maintenance {}
+}
+
+#[derive(Clone, Default)]
+pub struct PaymentTerminals {
+    terminals: Vec<Box<dyn PaymentTerminal>>,
+}
+
+impl PaymentTerminals {
+    pub fn register(mut self, terminal: Box<dyn PaymentTerminal>) -> Self {
+        self.terminals
+            .retain(|registered| registered.method() != terminal.method());
+        self.terminals.push(terminal);
+        self
+    }
+
+    pub async fn available(&self) -> Vec<PaymentMethod> {
+        let mut methods = Vec::new();
+        for terminal in self.terminals.iter() {
+            if terminal.is_available().await {
+                methods.push(terminal.method());
+            }
+        }
+        methods
+    }
+
+    pub async fn select(
+        &self,
+        method: &PaymentMethod,
+    ) -> Result<Box<dyn PaymentTerminal>, Box<dyn std::error::Error>> {
+        let terminal = self
+            .terminals
+            .iter()
+            .find(|terminal| &terminal.method() == method)
+            .ok_or("This payment method is not offered")?;
+
+        if !terminal.is_available().await {
+            return Err(Box::from("This payment method is currently unavailable"));
+        }
+
+        Ok(terminal.clone())
+    }
 }
 
+impl Terminal for PaymentTerminals {}
+
 #[derive(Clone)]
 pub struct VendingMachine<U: Role, L: LockStatus> {
     product_repository: Box<dyn ProductRepository>,
     sale_repository: Box<dyn SaleRepository>,
-    payment_terminal: Box<dyn PaymentTerminal>,
+    machine_state_repository: Box<dyn MachineStateRepository>,
+    machine_history_repository: Box<dyn MachineHistoryRepository>,
+    schedule_repository: Box<dyn ScheduleRepository>,
+    supplier_repository: Box<dyn SupplierRepository>,
+    cash_collection_repository: Box<dyn CashCollectionRepository>,
+    stock_movement_repository: Box<dyn StockMovementRepository>,
+    z_report_repository: Box<dyn ZReportRepository>,
+    customer_account_repository: Box<dyn CustomerAccountRepository>,
+    credit_repository: Box<dyn CreditRepository>,
+    payment_journal_repository: Box<dyn PaymentJournalRepository>,
+    sensor_reading_repository: Box<dyn SensorReadingRepository>,
+    write_off_repository: Box<dyn WriteOffRepository>,
+    fault_log_repository: Box<dyn FaultLogRepository>,
+    storage_diagnostics: Box<dyn StorageDiagnostics>,
+    dispenser: Box<dyn Dispenser>,
+    payment_terminals: PaymentTerminals,
+    receipt_printer: Box<dyn ReceiptPrinter>,
+    user: Option<Name>,
+    last_receipt: Option<Receipt>,
+    credit_transaction: Option<Value>,
     _role: std::marker::PhantomData<U>,
     _lock: std::marker::PhantomData<L>,
 }
@@ -66,89 +234,522 @@ impl DIBuilder for VendingMachine<Guest, Unlocked> {
     type Input = deps!(
         Box<dyn ProductRepository>,
         Box<dyn SaleRepository>,
-        Box<dyn PaymentTerminal>
+        Box<dyn MachineStateRepository>,
+        Box<dyn MachineHistoryRepository>,
+        Box<dyn ScheduleRepository>,
+        Box<dyn SupplierRepository>,
+        Box<dyn CashCollectionRepository>,
+        Box<dyn StockMovementRepository>,
+        Box<dyn ZReportRepository>,
+        Box<dyn CustomerAccountRepository>,
+        Box<dyn CreditRepository>,
+        Box<dyn PaymentJournalRepository>,
+        Box<dyn SensorReadingRepository>,
+        Box<dyn WriteOffRepository>,
+        Box<dyn FaultLogRepository>,
+        Box<dyn StorageDiagnostics>,
+        Box<dyn Dispenser>,
+        PaymentTerminals,
+        Box<dyn ReceiptPrinter>
     );
     type Output = Self;
 
     async fn build(input: Self::Input) -> Self::Output {
-        let_deps!(product_repository, sale_repository, payment_terminal <- input);
+        let_deps!(
+            product_repository,
+            sale_repository,
+            machine_state_repository,
+            machine_history_repository,
+            schedule_repository,
+            supplier_repository,
+            cash_collection_repository,
+            stock_movement_repository,
+            z_report_repository,
+            customer_account_repository,
+            credit_repository,
+            payment_journal_repository,
+            sensor_reading_repository,
+            write_off_repository,
+            fault_log_repository,
+            storage_diagnostics,
+            dispenser,
+            payment_terminals,
+            receipt_printer <- input
+        );
 
-        VendingMachine::new(product_repository, sale_repository, payment_terminal)
+        VendingMachine::new(
+            product_repository,
+            sale_repository,
+            machine_state_repository,
+            machine_history_repository,
+            schedule_repository,
+            supplier_repository,
+            cash_collection_repository,
+            stock_movement_repository,
+            z_report_repository,
+            customer_account_repository,
+            credit_repository,
+            payment_journal_repository,
+            sensor_reading_repository,
+            write_off_repository,
+            fault_log_repository,
+            storage_diagnostics,
+            dispenser,
+            payment_terminals,
+            receipt_printer,
+        )
     }
 }
 
 impl<U: Role, L: LockStatus> VendingMachine<U, L> {
-    pub async fn look_up(&self) -> Vec<Product> {
-        self.product_repository.find_all().await
+    fn transition<V: Role, M: LockStatus>(self) -> VendingMachine<V, M> {
+        VendingMachine::<V, M> {
+            product_repository: self.product_repository,
+            sale_repository: self.sale_repository,
+            machine_state_repository: self.machine_state_repository,
+            machine_history_repository: self.machine_history_repository,
+            schedule_repository: self.schedule_repository,
+            supplier_repository: self.supplier_repository,
+            cash_collection_repository: self.cash_collection_repository,
+            stock_movement_repository: self.stock_movement_repository,
+            z_report_repository: self.z_report_repository,
+            customer_account_repository: self.customer_account_repository,
+            credit_repository: self.credit_repository,
+            payment_journal_repository: self.payment_journal_repository,
+            sensor_reading_repository: self.sensor_reading_repository,
+            write_off_repository: self.write_off_repository,
+            fault_log_repository: self.fault_log_repository,
+            storage_diagnostics: self.storage_diagnostics,
+            dispenser: self.dispenser,
+            payment_terminals: self.payment_terminals,
+            receipt_printer: self.receipt_printer,
+            user: self.user,
+            last_receipt: self.last_receipt,
+            credit_transaction: self.credit_transaction,
+            _role: std::marker::PhantomData,
+            _lock: std::marker::PhantomData,
+        }
+    }
+
+    async fn persist_lock_state(
+        &mut self,
+        lock_state: LockState,
+        reason: Option<LockReason>,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let kind = match lock_state {
+            LockState::Unlocked => MachineEventKind::Unlocked,
+            LockState::Locked => MachineEventKind::Locked,
+            LockState::Maintenance => MachineEventKind::Maintenance,
+        };
+
+        let details = match (&reason, &expected_back) {
+            (Some(reason), Some(expected_back)) => format!(
+                "{} (expected back at {})",
+                reason.as_ref(),
+                expected_back.to_rfc3339()
+            ),
+            (Some(reason), None) => reason.as_ref().to_string(),
+            (None, _) => format!("Machine {}", lock_state.as_ref()),
+        };
+
+        self.record_event(kind, details).await?;
+
+        self.machine_state_repository
+            .save(MachineState {
+                lock_state,
+                changed_by: self.user.clone(),
+                reason,
+                expected_back,
+                changed_at: Utc::now(),
+            })
+            .await
+    }
+
+    async fn record_event(
+        &mut self,
+        kind: MachineEventKind,
+        details: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.machine_history_repository
+            .save(MachineEvent {
+                date: Utc::now(),
+                kind,
+                actor: self.user.clone(),
+                details,
+            })
+            .await
+    }
+
+    async fn log_fault(
+        &mut self,
+        code: FaultCode,
+        context: String,
+    ) -> Result<Fault, Box<dyn std::error::Error>> {
+        self.fault_log_repository
+            .save(Fault {
+                id: None,
+                date: Utc::now(),
+                code,
+                actor: self.user.clone(),
+                context,
+                status: FaultStatus::Open,
+                acknowledged_by: None,
+                cleared_by: None,
+            })
+            .await
+    }
+
+    async fn report_fault(
+        &mut self,
+        code: FaultCode,
+        context: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let fault = self.log_fault(code, context).await?;
+
+        self.record_event(
+            MachineEventKind::Fault,
+            format!("{} {}", fault.code.as_ref(), fault.context),
+        )
+        .await
+    }
+
+    async fn journal(
+        &mut self,
+        transaction_id: &Value,
+        phase: PaymentPhase,
+        amount: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.payment_journal_repository
+            .save(PaymentJournalEntry {
+                transaction_id: transaction_id.clone(),
+                date: Utc::now(),
+                phase,
+                amount,
+                method: None,
+                reference: None,
+            })
+            .await
+    }
+
+    async fn journal_payment(
+        &mut self,
+        transaction_id: &Value,
+        payment_terminal: &dyn PaymentTerminal,
+        amount: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.payment_journal_repository
+            .save(PaymentJournalEntry {
+                transaction_id: transaction_id.clone(),
+                date: Utc::now(),
+                phase: PaymentPhase::Inserted,
+                amount,
+                method: Some(payment_terminal.method()),
+                reference: payment_terminal.reference(),
+            })
+            .await
+    }
+
+    async fn journal_refund(
+        &mut self,
+        transaction_id: &Value,
+        method: &PaymentMethod,
+        amount: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.payment_journal_repository
+            .save(PaymentJournalEntry {
+                transaction_id: transaction_id.clone(),
+                date: Utc::now(),
+                phase: PaymentPhase::Refunded,
+                amount,
+                method: Some(method.clone()),
+                reference: None,
+            })
+            .await
+    }
+
+    async fn record_stock_movement(
+        &mut self,
+        product: &Product,
+        kind: StockMovementKind,
+        delta: i32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if delta == 0 {
+            return Ok(());
+        }
+
+        self.stock_movement_repository
+            .save(StockMovement {
+                date: Utc::now(),
+                column_id: product.column_id.clone(),
+                product_name: product.name.clone(),
+                kind,
+                delta,
+            })
+            .await
+    }
+
+    pub async fn resume(self) -> Resumed<U> {
+        let lock_state = self.persisted_lock_state().await;
+
+        match lock_state {
+            LockState::Unlocked => Resumed::Unlocked(self.transition()),
+            LockState::Locked => Resumed::Locked(self.transition()),
+            LockState::Maintenance => Resumed::Maintenance(self.transition()),
+        }
+    }
+
+    pub async fn machine_state(&self) -> Result<Option<MachineState>, Box<dyn std::error::Error>> {
+        self.machine_state_repository.load().await
+    }
+
+    async fn persisted_lock_state(&self) -> LockState {
+        match self.machine_state_repository.load().await {
+            Ok(Some(state)) => state.lock_state,
+            Ok(None) => LockState::default(),
+            Err(_) => LockState::Locked,
+        }
+    }
+
+    pub async fn lock_state_changed(&self) -> bool {
+        let lock_state = self.persisted_lock_state().await;
+
+        lock_state != L::STATE
+    }
+
+    pub async fn look_up(&self) -> Vec<U::ProductView> {
+        self.product_repository
+            .find_all()
+            .await
+            .into_iter()
+            .map(U::ProductView::from)
+            .collect()
+    }
+
+    pub async fn search(&self, query: &ProductQuery) -> Vec<U::ProductView> {
+        self.product_repository
+            .search(query)
+            .await
+            .into_iter()
+            .map(U::ProductView::from)
+            .collect()
     }
 }
 
 impl<U: Authenticated, L: LockStatus> VendingMachine<U, L> {
     pub fn logout(self) -> VendingMachine<Guest, L> {
         VendingMachine::<Guest, L> {
-            product_repository: self.product_repository,
-            sale_repository: self.sale_repository,
-            payment_terminal: self.payment_terminal,
-            _role: std::marker::PhantomData,
-            _lock: std::marker::PhantomData,
+            user: None,
+            ..self.transition()
         }
     }
 }
 
 impl<L: LockStatus> VendingMachine<Guest, L> {
-    pub fn login(self, username: &Name, password: &Password) -> AuthResult<L> {
+    pub fn reprint_last_receipt(&self) -> Result<Receipt, Box<dyn std::error::Error>> {
+        let receipt = self
+            .last_receipt
+            .clone()
+            .ok_or("There is no receipt to reprint")?;
+
+        self.receipt_printer.print(&receipt)?;
+
+        Ok(receipt)
+    }
+
+    pub async fn credit(&self) -> f32 {
+        match &self.credit_transaction {
+            Some(transaction_id) => self.credit_repository.load(transaction_id).await,
+            None => 0.0,
+        }
+    }
+
+    pub async fn payment_methods(&self) -> Vec<PaymentMethod> {
+        self.payment_terminals.available().await
+    }
+
+    pub fn with_payment_terminals(self, payment_terminals: PaymentTerminals) -> Self {
+        VendingMachine::<Guest, L> {
+            payment_terminals,
+            ..self
+        }
+    }
+
+    pub async fn return_credit(&mut self) -> Result<Price, Box<dyn std::error::Error>> {
+        let credit =
+            Price::parse_f32(self.credit().await).map_err(|_| "There is no credit to return")?;
+
+        self.payment_terminals
+            .select(&PaymentMethod::Cash)
+            .await?
+            .refund(credit.clone())
+            .await?;
+        if let Some(transaction_id) = self.credit_transaction.clone() {
+            self.journal_refund(&transaction_id, &PaymentMethod::Cash, credit.as_value())
+                .await?;
+        }
+        self.store_credit(0.0).await?;
+
+        Ok(credit)
+    }
+
+    async fn hold_credit(
+        &mut self,
+        transaction_id: &Value,
+        credit: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match self.credit_transaction.replace(transaction_id.clone()) {
+            Some(previous) if previous != *transaction_id => {
+                self.credit_repository
+                    .carry_over(&previous, transaction_id, credit)
+                    .await
+            }
+            _ => self.credit_repository.save(transaction_id, credit).await,
+        }
+    }
+
+    async fn store_credit(&mut self, credit: f32) -> Result<(), Box<dyn std::error::Error>> {
+        match self.credit_transaction.clone() {
+            Some(transaction_id) => self.credit_repository.save(&transaction_id, credit).await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn login(self, username: &Name, password: &Password) -> AuthResult<L> {
         match (username.as_ref(), password.as_ref()) {
             ("admin", "admin_pass") => AuthResult::SuccessAdmin(VendingMachine::<Admin, L> {
-                product_repository: self.product_repository,
-                sale_repository: self.sale_repository,
-                payment_terminal: self.payment_terminal,
-                _role: std::marker::PhantomData,
-                _lock: std::marker::PhantomData,
+                user: Some(username.clone()),
+                ..self.transition()
             }),
-            ("supplier", "supplier_pass") => {
-                AuthResult::SuccessSupplier(VendingMachine::<Supplier, L> {
-                    product_repository: self.product_repository,
-                    sale_repository: self.sale_repository,
-                    payment_terminal: self.payment_terminal,
-                    _role: std::marker::PhantomData,
-                    _lock: std::marker::PhantomData,
+            ("technician", "technician_pass") => {
+                AuthResult::SuccessTechnician(VendingMachine::<Technician, L> {
+                    user: Some(username.clone()),
+                    ..self.transition()
                 })
             }
-            _ => AuthResult::Failure(self),
+            _ => match self.supplier_repository.find(username).await {
+                Some(account) if account.password.verify(password) => {
+                    AuthResult::SuccessSupplier(VendingMachine::<Supplier, L> {
+                        user: Some(account.name),
+                        ..self.transition()
+                    })
+                }
+                _ => AuthResult::Failure(self),
+            },
         }
     }
 }
 
 impl VendingMachine<Guest, Unlocked> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         product_repository: Box<dyn ProductRepository>,
         sale_repository: Box<dyn SaleRepository>,
-        payment_terminal: Box<dyn PaymentTerminal>,
+        machine_state_repository: Box<dyn MachineStateRepository>,
+        machine_history_repository: Box<dyn MachineHistoryRepository>,
+        schedule_repository: Box<dyn ScheduleRepository>,
+        supplier_repository: Box<dyn SupplierRepository>,
+        cash_collection_repository: Box<dyn CashCollectionRepository>,
+        stock_movement_repository: Box<dyn StockMovementRepository>,
+        z_report_repository: Box<dyn ZReportRepository>,
+        customer_account_repository: Box<dyn CustomerAccountRepository>,
+        credit_repository: Box<dyn CreditRepository>,
+        payment_journal_repository: Box<dyn PaymentJournalRepository>,
+        sensor_reading_repository: Box<dyn SensorReadingRepository>,
+        write_off_repository: Box<dyn WriteOffRepository>,
+        fault_log_repository: Box<dyn FaultLogRepository>,
+        storage_diagnostics: Box<dyn StorageDiagnostics>,
+        dispenser: Box<dyn Dispenser>,
+        payment_terminals: PaymentTerminals,
+        receipt_printer: Box<dyn ReceiptPrinter>,
     ) -> VendingMachine<Guest, Unlocked> {
         VendingMachine::<Guest, Unlocked> {
             product_repository,
             sale_repository,
-            payment_terminal,
+            machine_state_repository,
+            machine_history_repository,
+            schedule_repository,
+            supplier_repository,
+            cash_collection_repository,
+            stock_movement_repository,
+            z_report_repository,
+            customer_account_repository,
+            credit_repository,
+            payment_journal_repository,
+            sensor_reading_repository,
+            write_off_repository,
+            fault_log_repository,
+            storage_diagnostics,
+            dispenser,
+            payment_terminals,
+            receipt_printer,
+            user: None,
+            last_receipt: None,
+            credit_transaction: None,
             _role: std::marker::PhantomData,
             _lock: std::marker::PhantomData,
         }
     }
 
-    fn pay(&self, amount: Price) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn into_sensor_monitor(
+        self,
+        sensors: Vec<Box<dyn Sensor>>,
+        policy: ColdChainPolicy,
+    ) -> Result<SensorMonitor, Box<dyn std::error::Error>> {
+        Ok(SensorMonitor {
+            vending_machine: VendingMachine::<System, Unlocked> {
+                user: Some(Name::parse(SensorMonitor::USER)?),
+                ..self.transition()
+            },
+            sensors,
+            policy,
+        })
+    }
+
+    pub fn into_payment_recovery(self) -> Result<PaymentRecovery, Box<dyn std::error::Error>> {
+        Ok(PaymentRecovery {
+            vending_machine: VendingMachine::<System, Unlocked> {
+                user: Some(Name::parse(PaymentRecovery::USER)?),
+                ..self.transition()
+            },
+        })
+    }
+
+    pub fn into_scheduler(self) -> Result<Scheduler, Box<dyn std::error::Error>> {
+        Ok(Scheduler {
+            vending_machine: VendingMachine::<System, Unlocked> {
+                user: Some(Name::parse(Scheduler::USER)?),
+                ..self.transition()
+            },
+        })
+    }
+
+    async fn pay(
+        &mut self,
+        transaction_id: &Value,
+        payment_terminal: &dyn PaymentTerminal,
+        amount: Price,
+    ) -> Result<Price, Box<dyn std::error::Error>> {
         let mut payed_amount = Price::default();
-        self.payment_terminal
-            .prompt(format!("You have to pay: {}", amount.as_value()).as_str());
+        payment_terminal.prompt(format!("You have to pay: {}", amount.as_value()).as_str());
 
         loop {
-            match self.payment_terminal.request() {
+            let due = Price::parse_f32(amount.as_value() - payed_amount.as_value())?;
+            let requested = payment_terminal
+                .request(due)
+                .await
+                .map_err(|e| e.to_string());
+
+            match requested {
                 Ok(value) => {
+                    self.journal_payment(transaction_id, payment_terminal, value.as_value())
+                        .await?;
+
                     payed_amount = Price::parse_f32(payed_amount.as_value() + value.as_value())?;
                     if payed_amount.as_value() >= amount.as_value() {
-                        let refund = Price::parse_f32(payed_amount.as_value() - amount.as_value())?;
-                        self.payment_terminal.refund(refund)?;
-                        return Ok(());
+                        return Ok(payed_amount);
                     } else {
-                        self.payment_terminal.prompt(
+                        payment_terminal.prompt(
                             format!(
                                 "You have to pay: {} more",
                                 amount.as_value() - payed_amount.as_value()
@@ -157,84 +758,1826 @@ impl VendingMachine<Guest, Unlocked> {
                         );
                     }
                 }
-                Err(_) => continue,
+                Err(message) => {
+                    if payed_amount.as_value() > 0.0 {
+                        payment_terminal.refund(payed_amount.clone()).await?;
+                        self.journal_refund(
+                            transaction_id,
+                            &payment_terminal.method(),
+                            payed_amount.as_value(),
+                        )
+                        .await?;
+                    }
+                    self.journal(
+                        transaction_id,
+                        PaymentPhase::Aborted,
+                        payed_amount.as_value(),
+                    )
+                    .await?;
+                    return Err(Box::from(format!("Payment failed: {}", message)));
+                }
+            }
+        }
+    }
+
+    async fn cancel_payment(
+        &mut self,
+        payment_terminal: &dyn PaymentTerminal,
+        mut credit: f32,
+    ) -> Result<f32, Box<dyn std::error::Error>> {
+        let cancelled = payment_terminal.cancel().await.map_err(|e| e.to_string());
+
+        match cancelled {
+            Ok(Some(voided)) => {
+                credit = ((credit - voided.as_value()) * 100.0).round() / 100.0;
+                self.store_credit(credit).await?;
+            }
+            Ok(None) => {}
+            Err(reason) => {
+                payment_terminal.prompt(&format!("The payment could not be voided: {}", reason));
+                self.report_fault(
+                    FaultCode::RefundFailed,
+                    format!(
+                        "{} payment could not be voided: {}",
+                        payment_terminal.method().as_ref(),
+                        reason
+                    ),
+                )
+                .await?;
+            }
+        }
+
+        Ok(credit)
+    }
+
+    async fn settle_credit(
+        &mut self,
+        transaction_id: &Value,
+        credit: f32,
+        change_policy: &ChangePolicy,
+    ) -> Result<f32, Box<dyn std::error::Error>> {
+        if *change_policy == ChangePolicy::KeepAsCredit {
+            return Ok(0.0);
+        }
+
+        let Ok(refund) = Price::parse_f32(credit) else {
+            return Ok(0.0);
+        };
+
+        let Some(cash_terminal) = self
+            .payment_terminals
+            .select(&PaymentMethod::Cash)
+            .await
+            .ok()
+        else {
+            return Ok(0.0);
+        };
+
+        let refunded = cash_terminal
+            .refund(refund)
+            .await
+            .map_err(|e| e.to_string());
+        if let Err(reason) = refunded {
+            self.report_fault(
+                FaultCode::RefundFailed,
+                format!("Change of {:.2} could not be paid out: {}", credit, reason),
+            )
+            .await?;
+            return Err(Box::from(reason));
+        }
+        self.journal_refund(transaction_id, &PaymentMethod::Cash, credit)
+            .await?;
+        self.store_credit(0.0).await?;
+
+        Ok(credit)
+    }
+
+    async fn record_purchase(
+        &mut self,
+        product: &Product,
+        qty: &Value,
+        total_price: &Price,
+        payment_method: &PaymentMethod,
+        paid_from_credit: f32,
+    ) -> Result<Sale, Box<dyn std::error::Error>> {
+        let date = Utc::now();
+
+        self.sale_repository
+            .record(
+                Sale {
+                    id: None,
+                    column_id: product.column_id.clone(),
+                    product_name: product.name.clone(),
+                    quantity: qty.clone(),
+                    price: total_price.clone(),
+                    payment_method: payment_method.clone(),
+                    paid_from_credit,
+                    date,
+                    reversal: None,
+                },
+                StockMovement {
+                    date,
+                    column_id: product.column_id.clone(),
+                    product_name: product.name.clone(),
+                    kind: StockMovementKind::Sale,
+                    delta: -(qty.as_value() as i32),
+                },
+            )
+            .await
+    }
+
+    async fn release_payment(
+        &mut self,
+        payment_terminal: &dyn PaymentTerminal,
+        amount: f32,
+        mut credit: f32,
+    ) -> Result<f32, Box<dyn std::error::Error>> {
+        let Ok(amount) = Price::parse_f32(amount) else {
+            return Ok(credit);
+        };
+
+        let released = payment_terminal
+            .release(amount.clone())
+            .await
+            .map_err(|e| e.to_string());
+
+        match released {
+            Ok(Some(released)) => {
+                credit = ((credit - released.as_value()) * 100.0).round() / 100.0;
+                self.store_credit(credit).await?;
+            }
+            Ok(None) => {}
+            Err(reason) => {
+                payment_terminal.prompt(&format!(
+                    "{:.2} of the payment could not be released: {}",
+                    amount.as_value(),
+                    reason
+                ));
+                self.report_fault(
+                    FaultCode::RefundFailed,
+                    format!(
+                        "{:.2} of a {} payment could not be released: {}",
+                        amount.as_value(),
+                        payment_terminal.method().as_ref(),
+                        reason
+                    ),
+                )
+                .await?;
             }
         }
+
+        Ok(credit)
     }
 
     pub async fn buy(
         &mut self,
         column_id: Value,
         qty: Value,
-    ) -> Result<Product, Box<dyn std::error::Error>> {
+        payment_method: PaymentMethod,
+        change_policy: ChangePolicy,
+    ) -> Result<Receipt, Box<dyn std::error::Error>> {
+        if self.lock_state_changed().await {
+            return Err(Box::from("The machine is no longer accepting purchases"));
+        }
+
         let product = self
             .product_repository
             .find(column_id)
             .await
             .ok_or("Product not found")?;
 
+        if product.refrigerated {
+            if self
+                .write_off_repository
+                .find(&product.column_id)
+                .await
+                .is_some()
+            {
+                return Err(Box::from("This product is flagged for write-off"));
+            }
+
+            if self
+                .sensor_reading_repository
+                .latest(&SensorKind::Temperature)
+                .await
+                .is_some_and(|reading| reading.alarm)
+            {
+                return Err(Box::from(
+                    "Cold products are unavailable while the temperature is out of range",
+                ));
+            }
+        }
+
         let total_price =
             Price::parse_f32(product.price.clone().as_value() * qty.as_value() as f32)?;
 
-        let new_qty =
-            Value::parse_i32(product.quantity.clone().as_value() as i32 - qty.as_value() as i32)
-                .map_err(|_| "Insufficient quantity in stock")?;
+        if product.quantity.as_value() < qty.as_value() {
+            return Err(Box::from("Insufficient quantity in stock"));
+        }
 
-        self.pay(total_price.clone())?;
+        let payment_terminal = self.payment_terminals.select(&payment_method).await?;
 
-        let bought_product = Product {
-            quantity: new_qty,
-            ..product.clone()
-        };
-        self.product_repository.save(bought_product.clone()).await?;
+        let transaction_id = self
+            .payment_journal_repository
+            .next_transaction_id()
+            .await?;
+        self.journal(
+            &transaction_id,
+            PaymentPhase::Started,
+            total_price.as_value(),
+        )
+        .await?;
 
-        self.sale_repository
-            .save(Sale {
-                product_name: product.name.clone(),
-                price: Price::parse_f32(total_price.as_value())?,
-                date: chrono::Utc::now(),
-            })
+        let mut credit = self.credit().await;
+        if credit > 0.0 {
+            payment_terminal.prompt(&format!("Using your credit: {:.2}", credit));
+        }
+        let prior_credit = credit;
+
+        let mut inserted = 0.0;
+        let due = Price::parse_f32(total_price.as_value() - credit).ok();
+        if let Some(due) = due {
+            inserted = self
+                .pay(&transaction_id, payment_terminal.as_ref(), due)
+                .await?
+                .as_value();
+            credit += inserted;
+        }
+        let amount_paid = credit;
+
+        self.hold_credit(&transaction_id, credit).await?;
+        self.journal(&transaction_id, PaymentPhase::Credited, credit)
             .await?;
 
-        Ok(bought_product)
-    }
-}
+        let vended = self
+            .dispenser
+            .vend(&product.column_id, &qty)
+            .await
+            .map_err(|e| e.to_string());
 
-impl<L: LockStatus> VendingMachine<Admin, L> {
-    pub async fn list_sales_report(&self) -> Vec<Sale> {
-        self.sale_repository.find_all().await
-    }
-}
+        let (dispensed, vend_failure) = match vended {
+            Ok(outcome) => (
+                outcome.dispensed(qty.as_value()),
+                outcome
+                    .fault_code()
+                    .map(|code| (code, outcome.as_ref().to_string())),
+            ),
+            Err(message) => (0, Some((FaultCode::DispenserUnresponsive, message))),
+        };
 
-impl VendingMachine<Admin, Unlocked> {
-    pub fn lock(self) -> VendingMachine<Admin, Locked> {
-        VendingMachine::<Admin, Locked> {
-            product_repository: self.product_repository,
-            sale_repository: self.sale_repository,
-            payment_terminal: self.payment_terminal,
-            _role: std::marker::PhantomData,
-            _lock: std::marker::PhantomData,
-        }
-    }
-}
+        if let (0, Some((code, reason))) = (dispensed, vend_failure.clone()) {
+            credit = self
+                .cancel_payment(payment_terminal.as_ref(), credit)
+                .await?;
+            let refunded = self
+                .settle_credit(&transaction_id, credit, &change_policy)
+                .await?;
+            credit = ((credit - refunded) * 100.0).round() / 100.0;
 
-impl VendingMachine<Admin, Locked> {
-    pub fn unlock(self) -> VendingMachine<Admin, Unlocked> {
-        VendingMachine::<Admin, Unlocked> {
-            product_repository: self.product_repository,
-            sale_repository: self.sale_repository,
-            payment_terminal: self.payment_terminal,
-            _role: std::marker::PhantomData,
-            _lock: std::marker::PhantomData,
+            self.journal(&transaction_id, PaymentPhase::Aborted, credit)
+                .await?;
+            self.report_fault(
+                code,
+                format!(
+                    "Vend failed on column {}: {}",
+                    product.column_id.as_value(),
+                    reason
+                ),
+            )
+            .await?;
+
+            return Err(Box::from(match credit > 0.0 {
+                true => format!(
+                    "The product could not be dispensed, your payment was kept as credit: {:.2}",
+                    credit
+                ),
+                false => {
+                    "The product could not be dispensed, your payment was refunded".to_string()
+                }
+            }));
         }
-    }
-}
 
-impl VendingMachine<Supplier, Unlocked> {
-    pub async fn supply_product(
-        &mut self,
-        product: Product,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        self.product_repository.save(product).await
+        let (qty, total_price) = match vend_failure {
+            Some((code, reason)) => {
+                let charged = Price::parse_f32(product.price.as_value() * dispensed as f32)?;
+                let undispensed = total_price.as_value() - charged.as_value();
+
+                credit = self
+                    .release_payment(payment_terminal.as_ref(), undispensed.min(inserted), credit)
+                    .await?;
+                self.report_fault(
+                    code,
+                    format!(
+                        "Only {} of {} item(s) dispensed on column {}: {}",
+                        dispensed,
+                        qty.as_value(),
+                        product.column_id.as_value(),
+                        reason
+                    ),
+                )
+                .await?;
+                payment_terminal.prompt(&format!(
+                    "Only {} of {} item(s) could be dispensed, you were charged {:.2}",
+                    dispensed,
+                    qty.as_value(),
+                    charged.as_value()
+                ));
+
+                (Value::parse_i32(dispensed as i32)?, charged)
+            }
+            None => (qty, total_price),
+        };
+        let paid_from_credit = prior_credit.min(total_price.as_value());
+
+        let recorded = self
+            .record_purchase(
+                &product,
+                &qty,
+                &total_price,
+                &payment_method,
+                paid_from_credit,
+            )
+            .await
+            .map_err(|e| e.to_string());
+
+        if let Err(message) = &recorded {
+            self.report_fault(
+                FaultCode::StorageUnavailable,
+                format!(
+                    "Sale of {} x{} on column {} in transaction #{} could not be recorded: {}",
+                    product.name.as_ref(),
+                    qty.as_value(),
+                    product.column_id.as_value(),
+                    transaction_id.as_value(),
+                    message
+                ),
+            )
+            .await?;
+        }
+
+        let confirmed = payment_terminal.confirm().await.map_err(|e| e.to_string());
+        if let Err(reason) = confirmed {
+            payment_terminal.prompt(&format!("The payment could not be captured: {}", reason));
+            self.report_fault(
+                FaultCode::PaymentFailed,
+                format!(
+                    "{} payment of transaction #{} could not be captured: {}",
+                    payment_method.as_ref(),
+                    transaction_id.as_value(),
+                    reason
+                ),
+            )
+            .await?;
+        }
+
+        credit = ((credit - total_price.as_value()) * 100.0).round() / 100.0;
+        self.store_credit(credit).await?;
+        self.journal(&transaction_id, PaymentPhase::Sold, credit)
+            .await?;
+
+        let change = self
+            .settle_credit(&transaction_id, credit, &change_policy)
+            .await?;
+        credit = ((credit - change) * 100.0).round() / 100.0;
+        if credit > 0.0 {
+            payment_terminal.prompt(&format!(
+                "Credit left for your next purchase: {:.2}",
+                credit
+            ));
+        }
+
+        self.journal(
+            &transaction_id,
+            PaymentPhase::Completed,
+            total_price.as_value(),
+        )
+        .await?;
+
+        let sale = recorded.map_err(|message| {
+            format!(
+                "The product was dispensed but the sale could not be recorded: {}",
+                message
+            )
+        })?;
+
+        let receipt = Receipt {
+            transaction_id: sale.id.ok_or("Sale was not assigned a transaction id")?,
+            date: sale.date,
+            items: vec![ReceiptItem {
+                product_name: product.name,
+                quantity: qty,
+                unit_price: product.price,
+                discount: 0.0,
+            }],
+            amount_paid,
+            change,
+            credit,
+        };
+        self.last_receipt = Some(receipt.clone());
+
+        if let Err(e) = self.receipt_printer.print(&receipt) {
+            payment_terminal.prompt(&format!("The receipt could not be printed: {}", e));
+        }
+
+        Ok(receipt)
+    }
+}
+
+impl<U: CanManageAccounts, L: LockStatus> VendingMachine<U, L> {
+    pub async fn list_customer_accounts(&self) -> Vec<CustomerAccount> {
+        self.customer_account_repository.find_all().await
+    }
+
+    pub async fn open_customer_account(
+        &mut self,
+        badge_id: Name,
+        holder: Name,
+    ) -> Result<CustomerAccount, Box<dyn std::error::Error>> {
+        if self
+            .customer_account_repository
+            .find(&badge_id)
+            .await
+            .is_some()
+        {
+            return Err(Box::from("An account with this badge id already exists"));
+        }
+
+        let account = CustomerAccount::new(badge_id, holder);
+        self.customer_account_repository
+            .save(account.clone())
+            .await?;
+
+        Ok(account)
+    }
+
+    pub async fn set_customer_account_pin(
+        &mut self,
+        badge_id: Name,
+        pin: Pin,
+    ) -> Result<CustomerAccount, Box<dyn std::error::Error>> {
+        let mut account = self
+            .customer_account_repository
+            .find(&badge_id)
+            .await
+            .ok_or("Customer account not found")?;
+
+        account.set_pin(&pin)?;
+        self.customer_account_repository
+            .save(account.clone())
+            .await?;
+
+        Ok(account)
+    }
+
+    pub async fn top_up_customer_account(
+        &mut self,
+        badge_id: Name,
+        amount: Price,
+    ) -> Result<CustomerAccount, Box<dyn std::error::Error>> {
+        let mut account = self
+            .customer_account_repository
+            .find(&badge_id)
+            .await
+            .ok_or("Customer account not found")?;
+
+        account.top_up(amount);
+        self.customer_account_repository
+            .save(account.clone())
+            .await?;
+
+        Ok(account)
+    }
+
+    pub async fn refund_customer_account(
+        &mut self,
+        badge_id: Name,
+        amount: Price,
+    ) -> Result<CustomerAccount, Box<dyn std::error::Error>> {
+        let mut account = self
+            .customer_account_repository
+            .find(&badge_id)
+            .await
+            .ok_or("Customer account not found")?;
+
+        account.refund(amount)?;
+        self.customer_account_repository
+            .save(account.clone())
+            .await?;
+
+        Ok(account)
+    }
+}
+
+impl<U: CanMonitorSensors, L: LockStatus> VendingMachine<U, L> {
+    pub async fn list_sensor_readings(&self) -> Vec<SensorReading> {
+        self.sensor_reading_repository.find_all().await
+    }
+
+    pub async fn list_write_off_flags(&self) -> Vec<WriteOffFlag> {
+        self.write_off_repository.find_all().await
+    }
+
+    pub async fn write_off(
+        &mut self,
+        column_id: Value,
+    ) -> Result<Product, Box<dyn std::error::Error>> {
+        let flag = self
+            .write_off_repository
+            .find(&column_id)
+            .await
+            .ok_or("This column is not flagged for write-off")?;
+
+        let product = self
+            .product_repository
+            .find(column_id.clone())
+            .await
+            .ok_or("Product not found")?;
+
+        let written_off_product = Product {
+            quantity: Quantity::parse_i32(0)?,
+            ..product.clone()
+        };
+        self.product_repository
+            .save(written_off_product.clone())
+            .await?;
+        self.record_stock_movement(
+            &written_off_product,
+            StockMovementKind::WriteOff,
+            -(product.quantity.as_value() as i32),
+        )
+        .await?;
+        self.write_off_repository.remove(&column_id).await?;
+
+        self.record_event(
+            MachineEventKind::StockAdjusted,
+            format!(
+                "Column {} ({}) written off: {} items ({})",
+                product.column_id.as_value(),
+                product.name.as_ref(),
+                product.quantity.as_value(),
+                flag.reason
+            ),
+        )
+        .await?;
+
+        Ok(written_off_product)
+    }
+
+    pub async fn dismiss_write_off(
+        &mut self,
+        column_id: Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let flag = self
+            .write_off_repository
+            .find(&column_id)
+            .await
+            .ok_or("This column is not flagged for write-off")?;
+
+        self.write_off_repository.remove(&column_id).await?;
+        self.record_event(
+            MachineEventKind::SensorAlarm,
+            format!(
+                "Write-off of column {} ({}) dismissed",
+                flag.column_id.as_value(),
+                flag.product_name.as_ref()
+            ),
+        )
+        .await
+    }
+
+    async fn record_sensor_reading(
+        &mut self,
+        reading: SensorReading,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let was_alarm = self
+            .sensor_reading_repository
+            .latest(&reading.kind)
+            .await
+            .is_some_and(|previous| previous.alarm);
+
+        self.sensor_reading_repository.save(reading.clone()).await?;
+
+        if reading.alarm == was_alarm {
+            return Ok(());
+        }
+
+        let details = match (&reading.kind, reading.alarm) {
+            (SensorKind::Temperature, true) => {
+                format!("Temperature out of range: {:.1} C", reading.value)
+            }
+            (SensorKind::Temperature, false) => {
+                format!("Temperature back in range: {:.1} C", reading.value)
+            }
+            (SensorKind::Door, true) => "Door opened".to_string(),
+            (SensorKind::Door, false) => "Door closed".to_string(),
+        };
+
+        if reading.alarm {
+            let code = match reading.kind {
+                SensorKind::Temperature => FaultCode::TemperatureOutOfRange,
+                SensorKind::Door => FaultCode::DoorOpen,
+            };
+            self.log_fault(code, details.clone()).await?;
+        }
+
+        self.record_event(MachineEventKind::SensorAlarm, details)
+            .await
+    }
+
+    async fn flag_spoiled_products(
+        &mut self,
+        policy: &ColdChainPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(since) = self
+            .sensor_reading_repository
+            .excursion_started_at(&SensorKind::Temperature)
+            .await
+        else {
+            return Ok(());
+        };
+
+        if now - since < policy.write_off_after {
+            return Ok(());
+        }
+
+        let products = self.product_repository.find_all().await;
+        for product in products
+            .into_iter()
+            .filter(|product| product.refrigerated && product.quantity.as_value() > 0)
+        {
+            if self
+                .write_off_repository
+                .find(&product.column_id)
+                .await
+                .is_some()
+            {
+                continue;
+            }
+
+            self.write_off_repository
+                .save(WriteOffFlag {
+                    column_id: product.column_id.clone(),
+                    product_name: product.name.clone(),
+                    quantity: product.quantity.clone(),
+                    flagged_at: now,
+                    reason: format!(
+                        "Temperature out of range since {}",
+                        since.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                    ),
+                })
+                .await?;
+
+            let details = format!(
+                "Column {} ({}) flagged for write-off",
+                product.column_id.as_value(),
+                product.name.as_ref()
+            );
+            self.log_fault(FaultCode::ProductSpoiled, details.clone())
+                .await?;
+            self.record_event(MachineEventKind::SensorAlarm, details)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<U: CanViewSales, L: LockStatus> VendingMachine<U, L> {
+    pub async fn list_sales_report(&self) -> Vec<Sale> {
+        self.sale_repository.find_all().await
+    }
+
+    pub async fn list_recovered_payments(&self) -> Vec<PaymentTransaction> {
+        PaymentTransaction::group(self.payment_journal_repository.find_all().await)
+            .into_iter()
+            .filter(|transaction| transaction.recovery().is_some())
+            .collect()
+    }
+}
+
+impl<U: CanVoidSales, L: LockStatus> VendingMachine<U, L> {
+    pub async fn void_sale(
+        &mut self,
+        sale_id: Value,
+        reason: VoidReason,
+        restore_stock: bool,
+    ) -> Result<Sale, Box<dyn std::error::Error>> {
+        let sale = self
+            .sale_repository
+            .find(sale_id.clone())
+            .await
+            .ok_or("Sale not found")?;
+
+        if sale.is_reversal() {
+            return Err(Box::from("Reversal entries cannot be voided"));
+        }
+
+        if self
+            .sale_repository
+            .find_all()
+            .await
+            .iter()
+            .any(|existing| {
+                existing
+                    .reversal
+                    .as_ref()
+                    .is_some_and(|reversal| reversal.sale_id == sale_id)
+            })
+        {
+            return Err(Box::from("Sale has already been voided"));
+        }
+
+        let reversal = self
+            .sale_repository
+            .save(Sale {
+                id: None,
+                date: Utc::now(),
+                reversal: Some(Reversal {
+                    sale_id: sale_id.clone(),
+                    reason: reason.clone(),
+                }),
+                ..sale.clone()
+            })
+            .await?;
+
+        if restore_stock {
+            let product = self
+                .product_repository
+                .find(sale.column_id.clone())
+                .await
+                .ok_or("Product not found")?;
+
+            let restored_product = Product {
+                quantity: Quantity::parse_i32(
+                    (product.quantity.as_value() + sale.quantity.as_value()) as i32,
+                )?,
+                ..product
+            };
+            self.product_repository
+                .save(restored_product.clone())
+                .await?;
+
+            self.record_stock_movement(
+                &restored_product,
+                StockMovementKind::Reversal,
+                sale.quantity.as_value() as i32,
+            )
+            .await?;
+        }
+
+        self.record_event(
+            MachineEventKind::SaleVoided,
+            format!(
+                "Sale {} of {:.2} voided: {}",
+                sale_id.as_value(),
+                sale.price.as_value(),
+                reason.as_ref()
+            ),
+        )
+        .await?;
+
+        Ok(reversal)
+    }
+}
+
+impl<U: CanRecoverPayments, L: LockStatus> VendingMachine<U, L> {
+    async fn return_payment(
+        &mut self,
+        transaction_id: &Value,
+        method: &PaymentMethod,
+        reference: Option<&str>,
+        amount: f32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Ok(refund) = Price::parse_f32(amount) else {
+            return Ok(());
+        };
+
+        let returned = match self.payment_terminals.select(method).await {
+            Ok(terminal) => terminal
+                .reverse(refund, reference)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match returned {
+            Ok(()) => self.journal_refund(transaction_id, method, amount).await,
+            Err(reason) => {
+                self.report_fault(
+                    FaultCode::RefundFailed,
+                    format!(
+                        "{:.2} of transaction #{} could not be returned by {}: {}",
+                        amount,
+                        transaction_id.as_value(),
+                        method.as_ref(),
+                        reason
+                    ),
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn recover_payments(
+        &mut self,
+        action: RecoveryAction,
+    ) -> Result<Vec<PaymentTransaction>, Box<dyn std::error::Error>> {
+        let held = self.credit_repository.find_all().await;
+        let pending = PaymentTransaction::group(self.payment_journal_repository.find_all().await)
+            .into_iter()
+            .filter_map(|transaction| {
+                let credit = held
+                    .iter()
+                    .find(|(transaction_id, _)| *transaction_id == transaction.transaction_id)
+                    .map(|(_, credit)| *credit);
+
+                match transaction.is_open()
+                    || (credit.is_some() && transaction.recovery().is_none())
+                {
+                    true => Some((transaction, credit)),
+                    false => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut recovered = Vec::new();
+        for (mut transaction, credit) in pending {
+            let transaction_id = transaction.transaction_id.clone();
+            let credited = credit.is_some() || transaction.recorded_balance().is_some();
+            let amount = match credited {
+                true => credit.unwrap_or(0.0),
+                false => transaction.inserted(),
+            };
+
+            let mut kept = amount;
+            if transaction.is_open() && !transaction.reached(&PaymentPhase::Sold) {
+                for payment in transaction.payments() {
+                    let method = payment.method.clone().unwrap_or(PaymentMethod::Cash);
+                    if action == RecoveryAction::Credit && method == PaymentMethod::Cash {
+                        continue;
+                    }
+
+                    self.return_payment(
+                        &transaction_id,
+                        &method,
+                        payment.reference.as_deref(),
+                        payment.amount,
+                    )
+                    .await?;
+                    kept = ((kept - payment.amount) * 100.0).round() / 100.0;
+                }
+            }
+
+            match action {
+                RecoveryAction::Credit => {
+                    self.credit_repository.save(&transaction_id, kept).await?;
+                }
+                RecoveryAction::Refund => {
+                    self.return_payment(&transaction_id, &PaymentMethod::Cash, None, kept)
+                        .await?;
+                    self.credit_repository.save(&transaction_id, 0.0).await?;
+                }
+            }
+
+            let entry = PaymentJournalEntry {
+                transaction_id: transaction_id.clone(),
+                date: Utc::now(),
+                phase: PaymentPhase::Recovered(action.clone()),
+                amount,
+                method: None,
+                reference: None,
+            };
+            self.payment_journal_repository.save(entry.clone()).await?;
+
+            self.record_event(
+                MachineEventKind::PaymentRecovered,
+                format!(
+                    "Incomplete payment #{} recovered by {} of {:.2}",
+                    transaction_id.as_value(),
+                    action.as_ref(),
+                    amount
+                ),
+            )
+            .await?;
+
+            transaction.entries.push(entry);
+            recovered.push(transaction);
+        }
+
+        Ok(recovered)
+    }
+}
+
+impl<U: CanViewHistory, L: LockStatus> VendingMachine<U, L> {
+    pub async fn list_history(&self) -> Vec<MachineEvent> {
+        self.machine_history_repository.find_all().await
+    }
+}
+
+impl<U: CanManageSchedule, L: LockStatus> VendingMachine<U, L> {
+    pub async fn schedule(&self) -> Schedule {
+        self.schedule_repository.load().await
+    }
+
+    pub async fn update_schedule(
+        &mut self,
+        schedule: Schedule,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let details = format!(
+            "Schedule updated to {} window(s) and {} exception date(s)",
+            schedule.windows.len(),
+            schedule.exceptions.len()
+        );
+
+        self.schedule_repository.save(schedule).await?;
+
+        self.record_event(MachineEventKind::ScheduleChanged, details)
+            .await
+    }
+}
+
+impl<U: CanManageUsers, L: LockStatus> VendingMachine<U, L> {
+    pub async fn list_suppliers(&self) -> Vec<SupplierAccount> {
+        self.supplier_repository.find_all().await
+    }
+
+    pub async fn add_supplier(
+        &mut self,
+        name: Name,
+        password: Password,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.supplier_repository.find(&name).await.is_some() {
+            return Err(Box::from("Supplier already exists"));
+        }
+
+        self.supplier_repository
+            .save(SupplierAccount {
+                name,
+                password: HashedPassword::hash(&password)?,
+                columns: vec![],
+            })
+            .await
+    }
+
+    pub async fn assign_column(
+        &mut self,
+        name: &Name,
+        column_id: Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut account = self
+            .supplier_repository
+            .find(name)
+            .await
+            .ok_or("Supplier not found")?;
+
+        self.unassign_column(column_id.clone()).await?;
+
+        account.columns.retain(|column| *column != column_id);
+        account.columns.push(column_id);
+        self.supplier_repository.save(account).await
+    }
+
+    pub async fn unassign_column(
+        &mut self,
+        column_id: Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for mut account in self.supplier_repository.find_all().await {
+            if account.owns(&column_id) {
+                account.columns.retain(|column| *column != column_id);
+                self.supplier_repository.save(account).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<U: CanCollectCash, L: LockStatus> VendingMachine<U, L> {
+    async fn reconcile(
+        &self,
+        since: Option<DateTime<Utc>>,
+        collection: &CashCollection,
+    ) -> CashReconciliation {
+        let in_period = |date: &DateTime<Utc>| {
+            since.is_none_or(|since| *date > since) && *date <= collection.date
+        };
+
+        let cash_movements = self
+            .payment_journal_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|entry| in_period(&entry.date))
+            .filter(|entry| entry.method == Some(PaymentMethod::Cash))
+            .fold(0.0, |total, entry| match entry.phase {
+                PaymentPhase::Inserted => total + entry.amount,
+                PaymentPhase::Refunded => total - entry.amount,
+                _ => total,
+            });
+
+        let voided = self
+            .sale_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|sale| sale.is_reversal() && in_period(&sale.date))
+            .fold(0.0, |total, sale| match sale.payment_method {
+                PaymentMethod::Cash => total + sale.amount(),
+                _ => total + sale.credit_amount(),
+            });
+
+        let expected = ((cash_movements + voided) * 100.0).round() / 100.0;
+
+        CashReconciliation {
+            since,
+            until: collection.date,
+            expected,
+            counted: collection.total(),
+        }
+    }
+
+    pub async fn collect_cash(
+        &mut self,
+        counts: Vec<CashCount>,
+    ) -> Result<CashReconciliation, Box<dyn std::error::Error>> {
+        let since = self
+            .cash_collection_repository
+            .find_all()
+            .await
+            .into_iter()
+            .map(|collection| collection.date)
+            .max();
+
+        let collection = CashCollection {
+            date: Utc::now(),
+            collected_by: self.user.clone(),
+            counts,
+        };
+
+        let reconciliation = self.reconcile(since, &collection).await;
+
+        self.cash_collection_repository.save(collection).await?;
+
+        self.record_event(
+            MachineEventKind::CashCollected,
+            format!(
+                "Collected {:.2}, expected {:.2}",
+                reconciliation.counted, reconciliation.expected
+            ),
+        )
+        .await?;
+
+        Ok(reconciliation)
+    }
+
+    pub async fn reconciliation_report(&self) -> Vec<CashReconciliation> {
+        let mut collections = self.cash_collection_repository.find_all().await;
+        collections.sort_by_key(|collection| collection.date);
+
+        let mut report = Vec::new();
+        let mut since = None;
+        for collection in collections.iter() {
+            report.push(self.reconcile(since, collection).await);
+            since = Some(collection.date);
+        }
+
+        report
+    }
+}
+
+impl<U: CanCloseDay, L: LockStatus> VendingMachine<U, L> {
+    pub async fn close_day(&mut self) -> Result<ZReport, Box<dyn std::error::Error>> {
+        let reports = self.z_report_repository.find_all().await;
+        let opened_at = reports.iter().map(|report| report.closed_at).max();
+        let closed_at = Utc::now();
+        let in_period = |date: &DateTime<Utc>| {
+            opened_at.is_none_or(|opened_at| *date > opened_at) && *date <= closed_at
+        };
+
+        let sales = self
+            .sale_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|sale| in_period(&sale.date))
+            .collect::<Vec<_>>();
+
+        let sales_count = sales.iter().filter(|sale| !sale.is_reversal()).count() as u32;
+
+        let mut product_revenue = Vec::<ProductRevenue>::new();
+        for sale in sales.iter() {
+            let sales_count = match sale.is_reversal() {
+                true => 0,
+                false => 1,
+            };
+
+            match product_revenue
+                .iter_mut()
+                .find(|entry| entry.product_name.as_ref() == sale.product_name.as_ref())
+            {
+                Some(entry) => {
+                    entry.sales_count += sales_count;
+                    entry.revenue += sale.amount();
+                }
+                None => product_revenue.push(ProductRevenue {
+                    product_name: sale.product_name.clone(),
+                    sales_count,
+                    revenue: sale.amount(),
+                }),
+            }
+        }
+
+        let revenue = sales.iter().fold(0.0, |total, sale| total + sale.amount());
+
+        let refunds = sales
+            .iter()
+            .filter(|sale| sale.is_reversal())
+            .fold(0.0, |total, sale| total + sale.price.as_value());
+
+        const CREDIT_PAYMENT_METHOD: &str = "credit";
+
+        let mut payment_methods = Vec::<PaymentMethodTotal>::new();
+        for sale in sales.iter() {
+            let sales_count = match sale.is_reversal() {
+                true => 0,
+                false => 1,
+            };
+
+            let mut portions = vec![(
+                sale.payment_method.as_ref(),
+                sales_count,
+                sale.method_amount(),
+            )];
+            if sale.paid_from_credit > 0.0 {
+                portions.push((CREDIT_PAYMENT_METHOD, 0, sale.credit_amount()));
+            }
+
+            for (method, sales_count, total) in portions {
+                match payment_methods
+                    .iter_mut()
+                    .find(|entry| entry.method == method)
+                {
+                    Some(entry) => {
+                        entry.sales_count += sales_count;
+                        entry.total += total;
+                    }
+                    None => payment_methods.push(PaymentMethodTotal {
+                        method: method.to_string(),
+                        sales_count,
+                        total,
+                    }),
+                }
+            }
+        }
+
+        let cash_collected = self
+            .cash_collection_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|collection| in_period(&collection.date))
+            .fold(0.0, |total, collection| total + collection.total());
+
+        let stock_movements = self
+            .stock_movement_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|movement| in_period(&movement.date))
+            .collect();
+
+        let report = ZReport {
+            number: Value::parse_i32(reports.len() as i32 + 1)?,
+            opened_at,
+            closed_at,
+            closed_by: self.user.clone(),
+            sales_count,
+            revenue,
+            refunds,
+            cash_collected,
+            product_revenue,
+            payment_methods,
+            stock_movements,
+        };
+
+        self.z_report_repository.save(report.clone()).await?;
+
+        self.record_event(
+            MachineEventKind::DayClosed,
+            format!(
+                "Z-report {} closed with {} sale(s) totalling {:.2}",
+                report.number.as_value(),
+                report.sales_count,
+                report.revenue
+            ),
+        )
+        .await?;
+
+        Ok(report)
+    }
+
+    pub async fn list_z_reports(&self) -> Vec<ZReport> {
+        self.z_report_repository.find_all().await
+    }
+}
+
+impl<U: CanLock> VendingMachine<U, Unlocked> {
+    pub async fn lock(
+        mut self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> Result<VendingMachine<U, Locked>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Locked, Some(reason), expected_back)
+            .await?;
+
+        Ok(self.transition())
+    }
+
+    pub async fn enter_maintenance(
+        mut self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> Result<VendingMachine<U, Maintenance>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Maintenance, Some(reason), expected_back)
+            .await?;
+
+        Ok(self.transition())
+    }
+}
+
+impl<U: CanLock> VendingMachine<U, Locked> {
+    pub async fn unlock(
+        mut self,
+    ) -> Result<VendingMachine<U, Unlocked>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Unlocked, None, None)
+            .await?;
+
+        Ok(self.transition())
+    }
+
+    pub async fn enter_maintenance(
+        mut self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> Result<VendingMachine<U, Maintenance>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Maintenance, Some(reason), expected_back)
+            .await?;
+
+        Ok(self.transition())
+    }
+}
+
+impl<U: CanLock> VendingMachine<U, Maintenance> {
+    pub async fn unlock(
+        mut self,
+    ) -> Result<VendingMachine<U, Unlocked>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Unlocked, None, None)
+            .await?;
+
+        Ok(self.transition())
+    }
+
+    pub async fn lock(
+        mut self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> Result<VendingMachine<U, Locked>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Locked, Some(reason), expected_back)
+            .await?;
+
+        Ok(self.transition())
+    }
+}
+
+impl<U: Role, L: Restockable> VendingMachine<U, L> {
+    async fn store_supply(&mut self, product: Product) -> Result<(), Box<dyn std::error::Error>> {
+        let previous_quantity = self
+            .product_repository
+            .find(product.column_id.clone())
+            .await
+            .map(|previous| previous.quantity.as_value())
+            .unwrap_or_default();
+
+        self.product_repository.save(product.clone()).await?;
+
+        self.record_stock_movement(
+            &product,
+            StockMovementKind::Supply,
+            product.quantity.as_value() as i32 - previous_quantity as i32,
+        )
+        .await
+    }
+}
+
+impl<U: Role> VendingMachine<U, Maintenance> {
+    async fn store_adjustment(
+        &mut self,
+        column_id: Value,
+        quantity: Quantity,
+    ) -> Result<Product, Box<dyn std::error::Error>> {
+        let product = self
+            .product_repository
+            .find(column_id)
+            .await
+            .ok_or("Product not found")?;
+
+        let adjusted_product = Product {
+            quantity: quantity.clone(),
+            ..product.clone()
+        };
+        self.product_repository
+            .save(adjusted_product.clone())
+            .await?;
+        self.record_stock_movement(
+            &adjusted_product,
+            StockMovementKind::Adjustment,
+            quantity.as_value() as i32 - product.quantity.as_value() as i32,
+        )
+        .await?;
+
+        self.record_event(
+            MachineEventKind::StockAdjusted,
+            format!(
+                "Column {} ({}) adjusted from {} to {}",
+                product.column_id.as_value(),
+                product.name.as_ref(),
+                product.quantity.as_value(),
+                quantity.as_value()
+            ),
+        )
+        .await?;
+
+        Ok(adjusted_product)
+    }
+}
+
+impl<U: CanRestock, L: Restockable> VendingMachine<U, L> {
+    pub async fn supply_product(
+        &mut self,
+        product: Product,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.store_supply(product).await
+    }
+}
+
+impl<U: CanRestock> VendingMachine<U, Maintenance> {
+    pub async fn adjust_stock(
+        &mut self,
+        column_id: Value,
+        quantity: Quantity,
+    ) -> Result<Product, Box<dyn std::error::Error>> {
+        self.store_adjustment(column_id, quantity).await
+    }
+}
+
+impl<U: CanRestockOwnColumns, L: LockStatus> VendingMachine<U, L> {
+    async fn supplier_account(&self) -> Result<SupplierAccount, Box<dyn std::error::Error>> {
+        let name = self.user.as_ref().ok_or("Supplier is not logged in")?;
+
+        self.supplier_repository
+            .find(name)
+            .await
+            .ok_or(Box::from("Supplier account not found"))
+    }
+
+    async fn ensure_owns(&self, column_id: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.supplier_account().await?.owns(column_id) {
+            return Err(Box::from(format!(
+                "Column {} is not assigned to this supplier",
+                column_id.as_value()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Stock and sales of the columns assigned to the logged-in supplier.
+    ///
+    /// Only suppliers can pull this report; technicians restock but never see sales:
+    ///
+    /// ```compile_fail
+    /// # use vending_machine::application::states::{Technician, Unlocked};
+    /// # use vending_machine::application::VendingMachine;
+    /// # async fn report(vending_machine: VendingMachine<Technician, Unlocked>) {
+    /// let _ = vending_machine.supplier_report().await;
+    /// # }
+    /// ```
+    pub async fn supplier_report(&self) -> Result<SupplierReport, Box<dyn std::error::Error>> {
+        let account = self.supplier_account().await?;
+
+        let products = self
+            .product_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|product| account.owns(&product.column_id))
+            .collect();
+
+        let sales = self
+            .sale_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|sale| account.owns(&sale.column_id))
+            .collect();
+
+        Ok(SupplierReport {
+            supplier: account.name.clone(),
+            products,
+            sales,
+        })
+    }
+}
+
+impl<U: CanRestockOwnColumns, L: Restockable> VendingMachine<U, L> {
+    pub async fn supply_own_product(
+        &mut self,
+        product: Product,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.ensure_owns(&product.column_id).await?;
+
+        self.store_supply(product).await
+    }
+}
+
+impl<U: CanRestockOwnColumns> VendingMachine<U, Maintenance> {
+    pub async fn adjust_own_stock(
+        &mut self,
+        column_id: Value,
+        quantity: Quantity,
+    ) -> Result<Product, Box<dyn std::error::Error>> {
+        self.ensure_owns(&column_id).await?;
+
+        self.store_adjustment(column_id, quantity).await
+    }
+}
+
+impl<U: CanManageFaults, L: LockStatus> VendingMachine<U, L> {
+    pub async fn list_faults(&self) -> Vec<Fault> {
+        self.fault_log_repository.find_all().await
+    }
+
+    pub async fn acknowledge_fault(
+        &mut self,
+        id: Value,
+    ) -> Result<Fault, Box<dyn std::error::Error>> {
+        let fault = self
+            .fault_log_repository
+            .find(&id)
+            .await
+            .ok_or("Fault not found")?
+            .acknowledge(self.user.clone())?;
+
+        self.fault_log_repository.save(fault).await
+    }
+
+    pub async fn clear_fault(&mut self, id: Value) -> Result<Fault, Box<dyn std::error::Error>> {
+        let fault = self
+            .fault_log_repository
+            .find(&id)
+            .await
+            .ok_or("Fault not found")?
+            .clear(self.user.clone())?;
+        let fault = self.fault_log_repository.save(fault).await?;
+
+        self.record_event(
+            MachineEventKind::Fault,
+            format!("Fault #{} ({}) cleared", id.as_value(), fault.code.as_ref()),
+        )
+        .await?;
+
+        Ok(fault)
+    }
+}
+
+impl<U: CanDiagnose, L: LockStatus> VendingMachine<U, L> {
+    pub async fn run_diagnostics(
+        &mut self,
+    ) -> Result<Vec<DiagnosticCheck>, Box<dyn std::error::Error>> {
+        let mut checks = Vec::new();
+        let join = |values: &Vec<Value>| {
+            values
+                .iter()
+                .map(|value| value.as_value().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let connected = self
+            .storage_diagnostics
+            .ping()
+            .await
+            .map_err(|e| e.to_string());
+        checks.push(DiagnosticCheck {
+            name: "Database connectivity".to_string(),
+            passed: connected.is_ok(),
+            details: match &connected {
+                Ok(_) => "Database is reachable".to_string(),
+                Err(message) => format!("Database is unreachable: {}", message),
+            },
+            fault_code: FaultCode::StorageUnavailable,
+        });
+
+        let schema_version = self
+            .storage_diagnostics
+            .schema_version()
+            .await
+            .map_err(|e| e.to_string());
+        checks.push(DiagnosticCheck {
+            name: "Migration version".to_string(),
+            passed: schema_version
+                .as_ref()
+                .is_ok_and(|version| version.is_current()),
+            details: match &schema_version {
+                Ok(SchemaVersion {
+                    applied: Some(applied),
+                    expected,
+                }) => format!("Applied version {}, expected {}", applied, expected),
+                Ok(SchemaVersion {
+                    applied: None,
+                    expected,
+                }) => format!("No migrations applied, expected {}", expected),
+                Err(message) => format!("Migration version is unknown: {}", message),
+            },
+            fault_code: FaultCode::SchemaOutdated,
+        });
+
+        let lock_state = self
+            .machine_state_repository
+            .load()
+            .await
+            .map(|state| state.map(|state| state.lock_state).unwrap_or_default())
+            .map_err(|e| e.to_string());
+        checks.push(DiagnosticCheck {
+            name: "Lock state".to_string(),
+            passed: lock_state.as_ref() == Ok(&L::STATE),
+            details: match &lock_state {
+                Ok(lock_state) => format!(
+                    "Persisted state is {}, session state is {}",
+                    lock_state.as_ref(),
+                    L::STATE.as_ref()
+                ),
+                Err(message) => format!("Persisted state is unknown: {}", message),
+            },
+            fault_code: FaultCode::StateMismatch,
+        });
+
+        let negative_stock = self
+            .storage_diagnostics
+            .negative_stock_columns()
+            .await
+            .map_err(|e| e.to_string());
+        checks.push(DiagnosticCheck {
+            name: "Stock consistency".to_string(),
+            passed: negative_stock
+                .as_ref()
+                .is_ok_and(|columns| columns.is_empty()),
+            details: match &negative_stock {
+                Ok(columns) if columns.is_empty() => "No column has negative stock".to_string(),
+                Ok(columns) => format!("Negative stock in column(s): {}", join(columns)),
+                Err(message) => format!("Stock could not be verified: {}", message),
+            },
+            fault_code: FaultCode::DataInconsistent,
+        });
+
+        let orphaned_sales = self
+            .storage_diagnostics
+            .orphaned_sales()
+            .await
+            .map_err(|e| e.to_string());
+        checks.push(DiagnosticCheck {
+            name: "Sales consistency".to_string(),
+            passed: orphaned_sales.as_ref().is_ok_and(|sales| sales.is_empty()),
+            details: match &orphaned_sales {
+                Ok(sales) if sales.is_empty() => "All sales reference existing columns".to_string(),
+                Ok(sales) => format!("Sale(s) referencing missing columns: {}", join(sales)),
+                Err(message) => format!("Sales could not be verified: {}", message),
+            },
+            fault_code: FaultCode::DataInconsistent,
+        });
+
+        // Sold-out columns are expected in normal operation and only reported as info.
+        let sold_out = self
+            .product_repository
+            .find_all()
+            .await
+            .into_iter()
+            .filter(|product| product.quantity.is_empty())
+            .map(|product| product.column_id.as_value().to_string())
+            .collect::<Vec<_>>();
+        checks.push(DiagnosticCheck {
+            name: "Stock levels".to_string(),
+            passed: true,
+            details: match sold_out.is_empty() {
+                true => "All columns are stocked".to_string(),
+                false => format!("Sold out column(s), restock due: {}", sold_out.join(", ")),
+            },
+            fault_code: FaultCode::ColumnSoldOut,
+        });
+
+        let payment_terminals = self.payment_terminals.terminals.clone();
+        for terminal in payment_terminals.iter() {
+            let tested = terminal.self_test().await.map_err(|e| e.to_string());
+            checks.push(DiagnosticCheck {
+                name: format!("Payment terminal ({})", terminal.method().as_ref()),
+                passed: tested.is_ok(),
+                details: match &tested {
+                    Ok(_) => "Self-test passed".to_string(),
+                    Err(message) => format!("Self-test failed: {}", message),
+                },
+                fault_code: FaultCode::TerminalSelfTestFailed,
+            });
+        }
+
+        let products = self.product_repository.find_all().await;
+        let mut failed_columns = Vec::new();
+        for product in products.iter() {
+            let tested = self
+                .dispenser
+                .self_test(&product.column_id)
+                .await
+                .map_err(|e| e.to_string());
+            if let Err(message) = tested {
+                failed_columns.push(format!("{} ({})", product.column_id.as_value(), message));
+            }
+        }
+        checks.push(DiagnosticCheck {
+            name: "Dispenser".to_string(),
+            passed: failed_columns.is_empty(),
+            details: match failed_columns.is_empty() {
+                true => format!("{} column motor(s) tested", products.len()),
+                false => format!(
+                    "Motor test failed on column(s): {}",
+                    failed_columns.join(", ")
+                ),
+            },
+            fault_code: FaultCode::DispenserUnresponsive,
+        });
+
+        if connected.is_err() {
+            return Ok(checks);
+        }
+
+        for check in checks.iter().filter(|check| !check.passed) {
+            self.report_fault(
+                check.fault_code.clone(),
+                format!("{}: {}", check.name, check.details),
+            )
+            .await?;
+        }
+
+        let failed = checks.iter().filter(|check| !check.passed).count();
+        self.record_event(
+            MachineEventKind::Diagnostics,
+            format!("{} check(s) run, {} failed", checks.len(), failed),
+        )
+        .await?;
+
+        Ok(checks)
+    }
+}
+
+impl<U: CanService> VendingMachine<U, Unlocked> {
+    pub async fn take_out_of_service(
+        mut self,
+        reason: LockReason,
+        expected_back: Option<DateTime<Utc>>,
+    ) -> Result<VendingMachine<U, Maintenance>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Maintenance, Some(reason), expected_back)
+            .await?;
+
+        Ok(self.transition())
+    }
+}
+
+impl<U: CanService> VendingMachine<U, Maintenance> {
+    pub async fn return_to_service(
+        mut self,
+    ) -> Result<VendingMachine<U, Unlocked>, Box<dyn std::error::Error>> {
+        self.persist_lock_state(LockState::Unlocked, None, None)
+            .await?;
+
+        Ok(self.transition())
+    }
+}
+
+#[derive(Clone)]
+pub struct Scheduler {
+    vending_machine: VendingMachine<System, Unlocked>,
+}
+
+impl Scheduler {
+    const USER: &'static str = "scheduler";
+    const LOCK_REASON: &'static str = "Outside opening hours";
+
+    pub async fn tick(&self, now: DateTime<Local>) -> Result<(), Box<dyn std::error::Error>> {
+        let schedule = self.vending_machine.schedule().await;
+        if !schedule.is_enabled() {
+            return Ok(());
+        }
+
+        let is_open = schedule.is_open_at(now.naive_local());
+        let state = self.vending_machine.machine_state().await?;
+        let changed_by_scheduler = state
+            .as_ref()
+            .and_then(|state| state.changed_by.as_ref())
+            .is_some_and(|changed_by| changed_by.as_ref() == Self::USER);
+        let overridden = !changed_by_scheduler
+            && state.is_some_and(|state| {
+                let changed_at = state.changed_at.with_timezone(&Local).naive_local();
+
+                !schedule.is_open_at(changed_at)
+                    && schedule
+                        .next_opening_after(changed_at)
+                        .is_none_or(|opening| opening > now.naive_local())
+            });
+
+        match self.vending_machine.clone().resume().await {
+            Resumed::Unlocked(vending_machine) if !is_open && !overridden => {
+                let reason = LockReason::parse(Self::LOCK_REASON)?;
+                let expected_back = schedule
+                    .next_opening_after(now.naive_local())
+                    .and_then(|opening| Local.from_local_datetime(&opening).earliest())
+                    .map(|opening| opening.with_timezone(&Utc));
+
+                vending_machine.lock(reason, expected_back).await?;
+            }
+            Resumed::Locked(vending_machine) if is_open && changed_by_scheduler => {
+                vending_machine.unlock().await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct PaymentRecovery {
+    vending_machine: VendingMachine<System, Unlocked>,
+}
+
+impl PaymentRecovery {
+    const USER: &'static str = "recovery";
+
+    pub async fn run(
+        mut self,
+        action: RecoveryAction,
+    ) -> Result<Vec<PaymentTransaction>, Box<dyn std::error::Error>> {
+        self.vending_machine.recover_payments(action).await
+    }
+}
+
+#[derive(Clone)]
+pub struct SensorMonitor {
+    vending_machine: VendingMachine<System, Unlocked>,
+    sensors: Vec<Box<dyn Sensor>>,
+    policy: ColdChainPolicy,
+}
+
+impl SensorMonitor {
+    const USER: &'static str = "monitor";
+
+    pub async fn tick(&self, now: DateTime<Utc>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut vending_machine = self.vending_machine.clone();
+
+        for sensor in self.sensors.iter() {
+            let kind = sensor.kind();
+            let value = match sensor.read().await.map_err(|e| e.to_string()) {
+                Ok(value) => value,
+                Err(message) => {
+                    vending_machine
+                        .report_fault(
+                            FaultCode::SensorUnreadable,
+                            format!(
+                                "The {} sensor could not be read: {}",
+                                kind.as_ref(),
+                                message
+                            ),
+                        )
+                        .await?;
+                    continue;
+                }
+            };
+
+            let alarm = match kind {
+                SensorKind::Temperature => !self.policy.is_in_range(value),
+                SensorKind::Door => value > 0.0,
+            };
+
+            vending_machine
+                .record_sensor_reading(SensorReading {
+                    date: now,
+                    kind,
+                    value,
+                    alarm,
+                })
+                .await?;
+        }
+
+        vending_machine
+            .flag_spoiled_products(&self.policy, now)
+            .await
     }
 }
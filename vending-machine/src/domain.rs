@@ -1,5 +1,8 @@
 pub mod entities {
-    use chrono::{DateTime, Utc};
+    use argon2::password_hash::rand_core::OsRng;
+    use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+    use argon2::Argon2;
+    use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
 
     #[derive(Clone, Debug)]
     pub struct Name(String);
@@ -24,6 +27,52 @@ pub mod entities {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct Category(String);
+
+    impl Category {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            if value.is_empty() {
+                return Err(Box::from("Category cannot be empty"));
+            }
+
+            if value.len() > 30 {
+                return Err(Box::from("Category is too long"));
+            }
+
+            Ok(Self(value.to_string()))
+        }
+    }
+
+    impl AsRef<str> for Category {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LockReason(String);
+
+    impl LockReason {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            if value.is_empty() {
+                return Err(Box::from("Lock reason cannot be empty"));
+            }
+
+            if value.len() > 100 {
+                return Err(Box::from("Lock reason is too long"));
+            }
+
+            Ok(Self(value.to_string()))
+        }
+    }
+
+    impl AsRef<str> for LockReason {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct Password(String);
 
@@ -47,6 +96,94 @@ pub mod entities {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct Pin(String);
+
+    impl Pin {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            if value.len() < 4 || value.len() > 8 {
+                return Err(Box::from("PIN must have between 4 and 8 digits"));
+            }
+
+            if !value.chars().all(|c| c.is_ascii_digit()) {
+                return Err(Box::from("PIN must contain only digits"));
+            }
+
+            Ok(Self(value.to_string()))
+        }
+    }
+
+    impl AsRef<str> for Pin {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct HashedPassword(String);
+
+    impl HashedPassword {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            if value.is_empty() {
+                return Err(Box::from("Password hash cannot be empty"));
+            }
+
+            Ok(Self(value.to_string()))
+        }
+
+        pub fn hash(password: &impl AsRef<str>) -> Result<Self, Box<dyn std::error::Error>> {
+            let salt = SaltString::generate(&mut OsRng);
+            let hash = Argon2::default()
+                .hash_password(password.as_ref().as_bytes(), &salt)
+                .map_err(|e| e.to_string())?;
+
+            Ok(Self(hash.to_string()))
+        }
+
+        pub fn verify(&self, password: &impl AsRef<str>) -> bool {
+            PasswordHash::new(&self.0).is_ok_and(|hash| {
+                Argon2::default()
+                    .verify_password(password.as_ref().as_bytes(), &hash)
+                    .is_ok()
+            })
+        }
+    }
+
+    impl AsRef<str> for HashedPassword {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CardNumber(String);
+
+    impl CardNumber {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            let digits = value.replace([' ', '-'], "");
+
+            if !digits.chars().all(|c| c.is_ascii_digit()) {
+                return Err(Box::from("Card number must contain only digits"));
+            }
+
+            if !(12..=19).contains(&digits.len()) {
+                return Err(Box::from("Card number must have between 12 and 19 digits"));
+            }
+
+            Ok(Self(digits))
+        }
+
+        pub fn masked(&self) -> String {
+            format!("**** {}", &self.0[self.0.len() - 4..])
+        }
+    }
+
+    impl AsRef<str> for CardNumber {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
     #[derive(Clone, Debug, Default)]
     pub struct Price(f32);
 
@@ -117,52 +254,1611 @@ pub mod entities {
         }
     }
 
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    pub struct Quantity(u32);
+
+    impl Quantity {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            let value = value
+                .parse::<u32>()
+                .map_err(|_| "Quantity must be a non-negative number".to_string())?;
+
+            Ok(Self(value))
+        }
+
+        pub fn parse_i32(value: i32) -> Result<Self, Box<dyn std::error::Error>> {
+            if value < 0 {
+                return Err(Box::from("Quantity cannot be negative"));
+            }
+
+            Ok(Self(value as u32))
+        }
+
+        pub fn as_value(&self) -> u32 {
+            self.0
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl AsRef<u32> for Quantity {
+        fn as_ref(&self) -> &u32 {
+            &self.0
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct Product {
         pub column_id: Value,
         pub name: Name,
+        pub category: Category,
         pub price: Price,
-        pub quantity: Value,
+        pub quantity: Quantity,
+        pub refrigerated: bool,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct VoidReason(String);
+
+    impl VoidReason {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            if value.is_empty() {
+                return Err(Box::from("Void reason cannot be empty"));
+            }
+
+            if value.len() > 100 {
+                return Err(Box::from("Void reason is too long"));
+            }
+
+            Ok(Self(value.to_string()))
+        }
+    }
+
+    impl AsRef<str> for VoidReason {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Reversal {
+        pub sale_id: Value,
+        pub reason: VoidReason,
     }
 
     #[derive(Clone, Debug)]
     pub struct Sale {
+        pub id: Option<Value>,
         pub date: DateTime<Utc>,
+        pub column_id: Value,
         pub product_name: Name,
+        pub quantity: Value,
         pub price: Price,
+        pub payment_method: PaymentMethod,
+        pub paid_from_credit: f32,
+        pub reversal: Option<Reversal>,
     }
-}
 
-pub mod interfaces {
-    use super::entities::{Price, Product, Sale, Value};
-    use async_trait::async_trait;
-    use dyn_clone::{clone_trait_object, DynClone};
+    impl Sale {
+        pub fn is_reversal(&self) -> bool {
+            self.reversal.is_some()
+        }
 
-    clone_trait_object!(ProductRepository);
-    clone_trait_object!(SaleRepository);
-    clone_trait_object!(PaymentTerminal);
+        pub fn amount(&self) -> f32 {
+            match self.is_reversal() {
+                true => -self.price.as_value(),
+                false => self.price.as_value(),
+            }
+        }
 
-    #[async_trait]
-    pub trait ProductRepository: Send + Sync + DynClone {
-        async fn find(&self, column_id: Value) -> Option<Product>;
-        async fn save(&mut self, product: Product) -> Result<(), Box<dyn std::error::Error>>;
-        async fn find_all(&self) -> Vec<Product>;
+        pub fn credit_amount(&self) -> f32 {
+            match self.is_reversal() {
+                true => -self.paid_from_credit,
+                false => self.paid_from_credit,
+            }
+        }
+
+        pub fn method_amount(&self) -> f32 {
+            self.amount() - self.credit_amount()
+        }
     }
 
-    #[async_trait]
-    pub trait SaleRepository: Send + Sync + DynClone {
-        async fn save(&mut self, sale: Sale) -> Result<(), Box<dyn std::error::Error>>;
-        async fn find_all(&self) -> Vec<Sale>;
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    pub enum LockState {
+        #[default]
+        Unlocked,
+        Locked,
+        Maintenance,
     }
 
-    pub trait Terminal: Send + Sync {
-        fn prompt(&self, message: &str) {
-            println!("{}", message);
+    impl LockState {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "unlocked" => Ok(LockState::Unlocked),
+                "locked" => Ok(LockState::Locked),
+                "maintenance" => Ok(LockState::Maintenance),
+                _ => Err(Box::from("Unknown lock state")),
+            }
         }
     }
 
-    pub trait PaymentTerminal: Terminal + DynClone {
-        fn request(&self) -> Result<Price, Box<dyn std::error::Error>>;
-        fn refund(&self, amount: Price) -> Result<(), Box<dyn std::error::Error>>;
+    impl AsRef<str> for LockState {
+        fn as_ref(&self) -> &str {
+            match self {
+                LockState::Unlocked => "unlocked",
+                LockState::Locked => "locked",
+                LockState::Maintenance => "maintenance",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct MachineState {
+        pub lock_state: LockState,
+        pub changed_by: Option<Name>,
+        pub reason: Option<LockReason>,
+        pub expected_back: Option<DateTime<Utc>>,
+        pub changed_at: DateTime<Utc>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum MachineEventKind {
+        Locked,
+        Unlocked,
+        Maintenance,
+        StockAdjusted,
+        ScheduleChanged,
+        Fault,
+        Diagnostics,
+        CashCollected,
+        DayClosed,
+        SaleVoided,
+        PaymentRecovered,
+        SensorAlarm,
+    }
+
+    impl MachineEventKind {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "locked" => Ok(MachineEventKind::Locked),
+                "unlocked" => Ok(MachineEventKind::Unlocked),
+                "maintenance" => Ok(MachineEventKind::Maintenance),
+                "stock_adjusted" => Ok(MachineEventKind::StockAdjusted),
+                "schedule_changed" => Ok(MachineEventKind::ScheduleChanged),
+                "fault" => Ok(MachineEventKind::Fault),
+                "diagnostics" => Ok(MachineEventKind::Diagnostics),
+                "cash_collected" => Ok(MachineEventKind::CashCollected),
+                "day_closed" => Ok(MachineEventKind::DayClosed),
+                "sale_voided" => Ok(MachineEventKind::SaleVoided),
+                "payment_recovered" => Ok(MachineEventKind::PaymentRecovered),
+                "sensor_alarm" => Ok(MachineEventKind::SensorAlarm),
+                _ => Err(Box::from("Unknown machine event kind")),
+            }
+        }
+    }
+
+    impl AsRef<str> for MachineEventKind {
+        fn as_ref(&self) -> &str {
+            match self {
+                MachineEventKind::Locked => "locked",
+                MachineEventKind::Unlocked => "unlocked",
+                MachineEventKind::Maintenance => "maintenance",
+                MachineEventKind::StockAdjusted => "stock_adjusted",
+                MachineEventKind::ScheduleChanged => "schedule_changed",
+                MachineEventKind::Fault => "fault",
+                MachineEventKind::Diagnostics => "diagnostics",
+                MachineEventKind::CashCollected => "cash_collected",
+                MachineEventKind::DayClosed => "day_closed",
+                MachineEventKind::SaleVoided => "sale_voided",
+                MachineEventKind::PaymentRecovered => "payment_recovered",
+                MachineEventKind::SensorAlarm => "sensor_alarm",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct MachineEvent {
+        pub date: DateTime<Utc>,
+        pub kind: MachineEventKind,
+        pub actor: Option<Name>,
+        pub details: String,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SupplierAccount {
+        pub name: Name,
+        pub password: HashedPassword,
+        pub columns: Vec<Value>,
+    }
+
+    impl SupplierAccount {
+        pub fn owns(&self, column_id: &Value) -> bool {
+            self.columns.contains(column_id)
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CashCount {
+        pub denomination: Price,
+        pub count: Quantity,
+    }
+
+    impl CashCount {
+        pub fn total(&self) -> f32 {
+            self.denomination.as_value() * self.count.as_value() as f32
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CashCollection {
+        pub date: DateTime<Utc>,
+        pub collected_by: Option<Name>,
+        pub counts: Vec<CashCount>,
+    }
+
+    impl CashCollection {
+        pub fn total(&self) -> f32 {
+            self.counts
+                .iter()
+                .fold(0.0, |total, count| total + count.total())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub enum AccountTransactionKind {
+        TopUp,
+        Payment,
+        Refund,
+        Reversal,
+    }
+
+    impl AccountTransactionKind {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "top_up" => Ok(AccountTransactionKind::TopUp),
+                "payment" => Ok(AccountTransactionKind::Payment),
+                "refund" => Ok(AccountTransactionKind::Refund),
+                "reversal" => Ok(AccountTransactionKind::Reversal),
+                _ => Err(Box::from("Unknown account transaction kind")),
+            }
+        }
+    }
+
+    impl AsRef<str> for AccountTransactionKind {
+        fn as_ref(&self) -> &str {
+            match self {
+                AccountTransactionKind::TopUp => "top_up",
+                AccountTransactionKind::Payment => "payment",
+                AccountTransactionKind::Refund => "refund",
+                AccountTransactionKind::Reversal => "reversal",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct AccountTransaction {
+        pub date: DateTime<Utc>,
+        pub kind: AccountTransactionKind,
+        pub amount: Price,
+    }
+
+    impl AccountTransaction {
+        pub fn signed_amount(&self) -> f32 {
+            match self.kind {
+                AccountTransactionKind::TopUp | AccountTransactionKind::Reversal => {
+                    self.amount.as_value()
+                }
+                AccountTransactionKind::Payment | AccountTransactionKind::Refund => {
+                    -self.amount.as_value()
+                }
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CustomerAccount {
+        pub badge_id: Name,
+        pub holder: Name,
+        pub pin: Option<HashedPassword>,
+        pub transactions: Vec<AccountTransaction>,
+    }
+
+    impl CustomerAccount {
+        pub fn new(badge_id: Name, holder: Name) -> Self {
+            CustomerAccount {
+                badge_id,
+                holder,
+                pin: None,
+                transactions: vec![],
+            }
+        }
+
+        pub fn set_pin(&mut self, pin: &Pin) -> Result<(), Box<dyn std::error::Error>> {
+            self.pin = Some(HashedPassword::hash(pin)?);
+            Ok(())
+        }
+
+        pub fn verify_pin(&self, pin: &Pin) -> bool {
+            self.pin.as_ref().is_some_and(|hash| hash.verify(pin))
+        }
+
+        pub fn balance(&self) -> f32 {
+            self.transactions.iter().fold(0.0, |balance, transaction| {
+                balance + transaction.signed_amount()
+            })
+        }
+
+        pub fn top_up(&mut self, amount: Price) {
+            self.record(AccountTransactionKind::TopUp, amount);
+        }
+
+        pub fn pay(&mut self, amount: Price) -> Result<(), Box<dyn std::error::Error>> {
+            self.withdraw(AccountTransactionKind::Payment, amount)
+        }
+
+        pub fn refund(&mut self, amount: Price) -> Result<(), Box<dyn std::error::Error>> {
+            self.withdraw(AccountTransactionKind::Refund, amount)
+        }
+
+        pub fn reverse_payment(&mut self, amount: Price) {
+            self.record(AccountTransactionKind::Reversal, amount);
+        }
+
+        fn withdraw(
+            &mut self,
+            kind: AccountTransactionKind,
+            amount: Price,
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            if amount.as_value() - self.balance() > 0.005 {
+                return Err(Box::from("Insufficient balance"));
+            }
+
+            self.record(kind, amount);
+            Ok(())
+        }
+
+        fn record(&mut self, kind: AccountTransactionKind, amount: Price) {
+            self.transactions.push(AccountTransaction {
+                date: Utc::now(),
+                kind,
+                amount,
+            });
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum RecoveryAction {
+        Refund,
+        Credit,
+    }
+
+    impl RecoveryAction {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "refund" => Ok(RecoveryAction::Refund),
+                "credit" => Ok(RecoveryAction::Credit),
+                _ => Err(Box::from("Unknown recovery action")),
+            }
+        }
+    }
+
+    impl AsRef<str> for RecoveryAction {
+        fn as_ref(&self) -> &str {
+            match self {
+                RecoveryAction::Refund => "refund",
+                RecoveryAction::Credit => "credit",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum PaymentPhase {
+        Started,
+        Inserted,
+        Refunded,
+        Credited,
+        Sold,
+        Completed,
+        Aborted,
+        Recovered(RecoveryAction),
+    }
+
+    impl PaymentPhase {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "started" => Ok(PaymentPhase::Started),
+                "inserted" => Ok(PaymentPhase::Inserted),
+                "refunded" => Ok(PaymentPhase::Refunded),
+                "credited" => Ok(PaymentPhase::Credited),
+                "sold" => Ok(PaymentPhase::Sold),
+                "completed" => Ok(PaymentPhase::Completed),
+                "aborted" => Ok(PaymentPhase::Aborted),
+                "recovered_refund" => Ok(PaymentPhase::Recovered(RecoveryAction::Refund)),
+                "recovered_credit" => Ok(PaymentPhase::Recovered(RecoveryAction::Credit)),
+                _ => Err(Box::from("Unknown payment phase")),
+            }
+        }
+
+        pub fn is_final(&self) -> bool {
+            matches!(
+                self,
+                PaymentPhase::Completed | PaymentPhase::Aborted | PaymentPhase::Recovered(_)
+            )
+        }
+    }
+
+    impl AsRef<str> for PaymentPhase {
+        fn as_ref(&self) -> &str {
+            match self {
+                PaymentPhase::Started => "started",
+                PaymentPhase::Inserted => "inserted",
+                PaymentPhase::Refunded => "refunded",
+                PaymentPhase::Credited => "credited",
+                PaymentPhase::Sold => "sold",
+                PaymentPhase::Completed => "completed",
+                PaymentPhase::Aborted => "aborted",
+                PaymentPhase::Recovered(RecoveryAction::Refund) => "recovered_refund",
+                PaymentPhase::Recovered(RecoveryAction::Credit) => "recovered_credit",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PaymentJournalEntry {
+        pub transaction_id: Value,
+        pub date: DateTime<Utc>,
+        pub phase: PaymentPhase,
+        pub amount: f32,
+        pub method: Option<PaymentMethod>,
+        pub reference: Option<String>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum SensorKind {
+        Temperature,
+        Door,
+    }
+
+    impl SensorKind {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "temperature" => Ok(SensorKind::Temperature),
+                "door" => Ok(SensorKind::Door),
+                _ => Err(Box::from("Unknown sensor kind")),
+            }
+        }
+    }
+
+    impl AsRef<str> for SensorKind {
+        fn as_ref(&self) -> &str {
+            match self {
+                SensorKind::Temperature => "temperature",
+                SensorKind::Door => "door",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SensorReading {
+        pub date: DateTime<Utc>,
+        pub kind: SensorKind,
+        pub value: f32,
+        pub alarm: bool,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ColdChainPolicy {
+        pub min_temperature: f32,
+        pub max_temperature: f32,
+        pub write_off_after: Duration,
+    }
+
+    impl ColdChainPolicy {
+        pub fn new(
+            min_temperature: f32,
+            max_temperature: f32,
+            write_off_after: Duration,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
+            if min_temperature >= max_temperature {
+                return Err(Box::from(
+                    "Minimum temperature must be below the maximum temperature",
+                ));
+            }
+
+            if write_off_after <= Duration::zero() {
+                return Err(Box::from("Write-off delay must be positive"));
+            }
+
+            Ok(Self {
+                min_temperature,
+                max_temperature,
+                write_off_after,
+            })
+        }
+
+        pub fn is_in_range(&self, temperature: f32) -> bool {
+            temperature >= self.min_temperature && temperature <= self.max_temperature
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct WriteOffFlag {
+        pub column_id: Value,
+        pub product_name: Name,
+        pub quantity: Quantity,
+        pub flagged_at: DateTime<Utc>,
+        pub reason: String,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum FaultCategory {
+        Payment,
+        Dispenser,
+        Storage,
+        Sensor,
+    }
+
+    impl AsRef<str> for FaultCategory {
+        fn as_ref(&self) -> &str {
+            match self {
+                FaultCategory::Payment => "payment",
+                FaultCategory::Dispenser => "dispenser",
+                FaultCategory::Storage => "storage",
+                FaultCategory::Sensor => "sensor",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum FaultCode {
+        PaymentFailed,
+        RefundFailed,
+        TerminalSelfTestFailed,
+        MotorJammed,
+        NoDropDetected,
+        DispenserUnresponsive,
+        ColumnSoldOut,
+        StorageUnavailable,
+        StateMismatch,
+        SchemaOutdated,
+        DataInconsistent,
+        SensorUnreadable,
+        TemperatureOutOfRange,
+        DoorOpen,
+        ProductSpoiled,
+    }
+
+    impl FaultCode {
+        pub const ALL: [FaultCode; 15] = [
+            FaultCode::PaymentFailed,
+            FaultCode::RefundFailed,
+            FaultCode::TerminalSelfTestFailed,
+            FaultCode::MotorJammed,
+            FaultCode::NoDropDetected,
+            FaultCode::DispenserUnresponsive,
+            FaultCode::ColumnSoldOut,
+            FaultCode::StorageUnavailable,
+            FaultCode::StateMismatch,
+            FaultCode::SchemaOutdated,
+            FaultCode::DataInconsistent,
+            FaultCode::SensorUnreadable,
+            FaultCode::TemperatureOutOfRange,
+            FaultCode::DoorOpen,
+            FaultCode::ProductSpoiled,
+        ];
+
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            FaultCode::ALL
+                .into_iter()
+                .find(|code| code.as_ref() == value)
+                .ok_or(Box::from("Unknown fault code"))
+        }
+
+        pub fn category(&self) -> FaultCategory {
+            match self {
+                FaultCode::PaymentFailed
+                | FaultCode::RefundFailed
+                | FaultCode::TerminalSelfTestFailed => FaultCategory::Payment,
+                FaultCode::MotorJammed
+                | FaultCode::NoDropDetected
+                | FaultCode::DispenserUnresponsive
+                | FaultCode::ColumnSoldOut => FaultCategory::Dispenser,
+                FaultCode::StorageUnavailable
+                | FaultCode::StateMismatch
+                | FaultCode::SchemaOutdated
+                | FaultCode::DataInconsistent => FaultCategory::Storage,
+                FaultCode::SensorUnreadable
+                | FaultCode::TemperatureOutOfRange
+                | FaultCode::DoorOpen
+                | FaultCode::ProductSpoiled => FaultCategory::Sensor,
+            }
+        }
+
+        pub fn description(&self) -> &str {
+            match self {
+                FaultCode::PaymentFailed => "Payment could not be completed",
+                FaultCode::RefundFailed => "Payment could not be refunded or voided",
+                FaultCode::TerminalSelfTestFailed => "Payment terminal failed its self-test",
+                FaultCode::MotorJammed => "Dispenser motor jammed",
+                FaultCode::NoDropDetected => "No product drop detected",
+                FaultCode::DispenserUnresponsive => "Dispenser did not respond",
+                FaultCode::ColumnSoldOut => "Column sold out",
+                FaultCode::StorageUnavailable => "Machine data could not be stored",
+                FaultCode::StateMismatch => "Stored machine state does not match the session",
+                FaultCode::SchemaOutdated => "Database schema is not up to date",
+                FaultCode::DataInconsistent => "Stored data is inconsistent",
+                FaultCode::SensorUnreadable => "Sensor could not be read",
+                FaultCode::TemperatureOutOfRange => "Temperature out of range",
+                FaultCode::DoorOpen => "Door left open",
+                FaultCode::ProductSpoiled => "Cold product flagged for write-off",
+            }
+        }
+    }
+
+    impl AsRef<str> for FaultCode {
+        fn as_ref(&self) -> &str {
+            match self {
+                FaultCode::PaymentFailed => "PAY-01",
+                FaultCode::RefundFailed => "PAY-02",
+                FaultCode::TerminalSelfTestFailed => "PAY-03",
+                FaultCode::MotorJammed => "DSP-01",
+                FaultCode::NoDropDetected => "DSP-02",
+                FaultCode::DispenserUnresponsive => "DSP-03",
+                FaultCode::ColumnSoldOut => "DSP-04",
+                FaultCode::StorageUnavailable => "STO-01",
+                FaultCode::StateMismatch => "STO-02",
+                FaultCode::SchemaOutdated => "STO-03",
+                FaultCode::DataInconsistent => "STO-04",
+                FaultCode::SensorUnreadable => "SEN-01",
+                FaultCode::TemperatureOutOfRange => "SEN-02",
+                FaultCode::DoorOpen => "SEN-03",
+                FaultCode::ProductSpoiled => "SEN-04",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    pub enum FaultStatus {
+        #[default]
+        Open,
+        Acknowledged,
+        Cleared,
+    }
+
+    impl FaultStatus {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "open" => Ok(FaultStatus::Open),
+                "acknowledged" => Ok(FaultStatus::Acknowledged),
+                "cleared" => Ok(FaultStatus::Cleared),
+                _ => Err(Box::from("Unknown fault status")),
+            }
+        }
+    }
+
+    impl AsRef<str> for FaultStatus {
+        fn as_ref(&self) -> &str {
+            match self {
+                FaultStatus::Open => "open",
+                FaultStatus::Acknowledged => "acknowledged",
+                FaultStatus::Cleared => "cleared",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Fault {
+        pub id: Option<Value>,
+        pub date: DateTime<Utc>,
+        pub code: FaultCode,
+        pub actor: Option<Name>,
+        pub context: String,
+        pub status: FaultStatus,
+        pub acknowledged_by: Option<Name>,
+        pub cleared_by: Option<Name>,
+    }
+
+    impl Fault {
+        pub fn is_active(&self) -> bool {
+            self.status != FaultStatus::Cleared
+        }
+
+        pub fn acknowledge(self, by: Option<Name>) -> Result<Self, Box<dyn std::error::Error>> {
+            match self.status {
+                FaultStatus::Open => Ok(Fault {
+                    status: FaultStatus::Acknowledged,
+                    acknowledged_by: by,
+                    ..self
+                }),
+                FaultStatus::Acknowledged => Err(Box::from("Fault is already acknowledged")),
+                FaultStatus::Cleared => Err(Box::from("Fault is already cleared")),
+            }
+        }
+
+        pub fn clear(self, by: Option<Name>) -> Result<Self, Box<dyn std::error::Error>> {
+            match self.status {
+                FaultStatus::Cleared => Err(Box::from("Fault is already cleared")),
+                _ => Ok(Fault {
+                    status: FaultStatus::Cleared,
+                    cleared_by: by,
+                    ..self
+                }),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    pub enum PaymentMethod {
+        #[default]
+        Cash,
+        Card,
+        Wallet,
+    }
+
+    impl PaymentMethod {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "cash" => Ok(PaymentMethod::Cash),
+                "card" => Ok(PaymentMethod::Card),
+                "wallet" => Ok(PaymentMethod::Wallet),
+                _ => Err(Box::from("Unknown payment method")),
+            }
+        }
+    }
+
+    impl AsRef<str> for PaymentMethod {
+        fn as_ref(&self) -> &str {
+            match self {
+                PaymentMethod::Cash => "cash",
+                PaymentMethod::Card => "card",
+                PaymentMethod::Wallet => "wallet",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CardAuthorization {
+        pub id: String,
+        pub card: String,
+        pub amount: Price,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ChangePolicy {
+        Refund,
+        KeepAsCredit,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum VendOutcome {
+        Dispensed,
+        MotorJammed,
+        NoDropDetected,
+        PartiallyDispensed(u32, FaultCode),
+    }
+
+    impl VendOutcome {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "dispensed" => Ok(VendOutcome::Dispensed),
+                "motor_jammed" => Ok(VendOutcome::MotorJammed),
+                "no_drop_detected" => Ok(VendOutcome::NoDropDetected),
+                _ => Err(Box::from("Unknown vend outcome")),
+            }
+        }
+
+        pub fn fault_code(&self) -> Option<FaultCode> {
+            match self {
+                VendOutcome::Dispensed => None,
+                VendOutcome::MotorJammed => Some(FaultCode::MotorJammed),
+                VendOutcome::NoDropDetected => Some(FaultCode::NoDropDetected),
+                VendOutcome::PartiallyDispensed(_, code) => Some(code.clone()),
+            }
+        }
+
+        pub fn dispensed(&self, requested: u32) -> u32 {
+            match self {
+                VendOutcome::Dispensed => requested,
+                VendOutcome::MotorJammed | VendOutcome::NoDropDetected => 0,
+                VendOutcome::PartiallyDispensed(dispensed, _) => (*dispensed).min(requested),
+            }
+        }
+    }
+
+    impl AsRef<str> for VendOutcome {
+        fn as_ref(&self) -> &str {
+            match self {
+                VendOutcome::Dispensed => "dispensed",
+                VendOutcome::MotorJammed => "motor_jammed",
+                VendOutcome::NoDropDetected => "no_drop_detected",
+                VendOutcome::PartiallyDispensed(_, _) => "partially_dispensed",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ReceiptItem {
+        pub product_name: Name,
+        pub quantity: Value,
+        pub unit_price: Price,
+        pub discount: f32,
+    }
+
+    impl ReceiptItem {
+        pub fn total(&self) -> f32 {
+            self.unit_price.as_value() * self.quantity.as_value() as f32 - self.discount
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Receipt {
+        pub transaction_id: Value,
+        pub date: DateTime<Utc>,
+        pub items: Vec<ReceiptItem>,
+        pub amount_paid: f32,
+        pub change: f32,
+        pub credit: f32,
+    }
+
+    impl Receipt {
+        pub fn discount(&self) -> f32 {
+            self.items
+                .iter()
+                .fold(0.0, |total, item| total + item.discount)
+        }
+
+        pub fn total(&self) -> f32 {
+            self.items
+                .iter()
+                .fold(0.0, |total, item| total + item.total())
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum StockMovementKind {
+        Sale,
+        Supply,
+        Adjustment,
+        Reversal,
+        WriteOff,
+    }
+
+    impl StockMovementKind {
+        pub fn parse(value: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            match value {
+                "sale" => Ok(StockMovementKind::Sale),
+                "supply" => Ok(StockMovementKind::Supply),
+                "adjustment" => Ok(StockMovementKind::Adjustment),
+                "reversal" => Ok(StockMovementKind::Reversal),
+                "write_off" => Ok(StockMovementKind::WriteOff),
+                _ => Err(Box::from("Unknown stock movement kind")),
+            }
+        }
+    }
+
+    impl AsRef<str> for StockMovementKind {
+        fn as_ref(&self) -> &str {
+            match self {
+                StockMovementKind::Sale => "sale",
+                StockMovementKind::Supply => "supply",
+                StockMovementKind::Adjustment => "adjustment",
+                StockMovementKind::Reversal => "reversal",
+                StockMovementKind::WriteOff => "write_off",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct StockMovement {
+        pub date: DateTime<Utc>,
+        pub column_id: Value,
+        pub product_name: Name,
+        pub kind: StockMovementKind,
+        pub delta: i32,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProductRevenue {
+        pub product_name: Name,
+        pub sales_count: u32,
+        pub revenue: f32,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PaymentMethodTotal {
+        pub method: String,
+        pub sales_count: u32,
+        pub total: f32,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ZReport {
+        pub number: Value,
+        pub opened_at: Option<DateTime<Utc>>,
+        pub closed_at: DateTime<Utc>,
+        pub closed_by: Option<Name>,
+        pub sales_count: u32,
+        pub revenue: f32,
+        pub refunds: f32,
+        pub cash_collected: f32,
+        pub product_revenue: Vec<ProductRevenue>,
+        pub payment_methods: Vec<PaymentMethodTotal>,
+        pub stock_movements: Vec<StockMovement>,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DiagnosticCheck {
+        pub name: String,
+        pub passed: bool,
+        pub details: String,
+        pub fault_code: FaultCode,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SchemaVersion {
+        pub applied: Option<i64>,
+        pub expected: i64,
+    }
+
+    impl SchemaVersion {
+        pub fn is_current(&self) -> bool {
+            self.applied == Some(self.expected)
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ScheduleWindow {
+        pub weekday: Weekday,
+        pub opens_at: NaiveTime,
+        pub closes_at: NaiveTime,
+    }
+
+    impl ScheduleWindow {
+        pub fn parse(
+            weekday: &str,
+            opens_at: &str,
+            closes_at: &str,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
+            let weekday = weekday
+                .parse::<Weekday>()
+                .map_err(|_| "Weekday must be a day name such as Mon or Monday".to_string())?;
+            let opens_at = NaiveTime::parse_from_str(opens_at, "%H:%M")
+                .map_err(|_| "Opening time must be in the HH:MM format".to_string())?;
+            let closes_at = NaiveTime::parse_from_str(closes_at, "%H:%M")
+                .map_err(|_| "Closing time must be in the HH:MM format".to_string())?;
+
+            Self::new(weekday, opens_at, closes_at)
+        }
+
+        pub fn new(
+            weekday: Weekday,
+            opens_at: NaiveTime,
+            closes_at: NaiveTime,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
+            if opens_at >= closes_at {
+                return Err(Box::from("Opening time must be before closing time"));
+            }
+
+            Ok(Self {
+                weekday,
+                opens_at,
+                closes_at,
+            })
+        }
+
+        pub fn contains(&self, at: NaiveDateTime) -> bool {
+            at.weekday() == self.weekday && at.time() >= self.opens_at && at.time() < self.closes_at
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ScheduleException {
+        pub date: NaiveDate,
+        pub description: String,
+    }
+
+    impl ScheduleException {
+        pub fn parse(date: &str, description: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| "Date must be in the YYYY-MM-DD format".to_string())?;
+
+            if description.len() > 100 {
+                return Err(Box::from("Description is too long"));
+            }
+
+            Ok(Self {
+                date,
+                description: description.to_string(),
+            })
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    pub struct Schedule {
+        pub windows: Vec<ScheduleWindow>,
+        pub exceptions: Vec<ScheduleException>,
+    }
+
+    impl Schedule {
+        pub fn is_enabled(&self) -> bool {
+            !self.windows.is_empty()
+        }
+
+        pub fn is_open_at(&self, at: NaiveDateTime) -> bool {
+            if self
+                .exceptions
+                .iter()
+                .any(|exception| exception.date == at.date())
+            {
+                return false;
+            }
+
+            self.windows.iter().any(|window| window.contains(at))
+        }
+
+        pub fn next_opening_after(&self, at: NaiveDateTime) -> Option<NaiveDateTime> {
+            (0..=7 + self.exceptions.len() as i64)
+                .map(|offset| at.date() + Duration::days(offset))
+                .filter(|date| {
+                    !self
+                        .exceptions
+                        .iter()
+                        .any(|exception| exception.date == *date)
+                })
+                .flat_map(|date| {
+                    self.windows
+                        .iter()
+                        .filter(move |window| window.weekday == date.weekday())
+                        .map(move |window| date.and_time(window.opens_at))
+                })
+                .filter(|opening| *opening > at)
+                .min()
+        }
+    }
+}
+
+pub mod views {
+    use chrono::{DateTime, Utc};
+
+    use super::entities::{
+        Category, Name, PaymentJournalEntry, PaymentPhase, Price, Product, RecoveryAction, Sale,
+        Value,
+    };
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum Availability {
+        Available,
+        SoldOut,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PublicProductView {
+        pub column_id: Value,
+        pub name: Name,
+        pub category: Category,
+        pub price: Price,
+        pub availability: Availability,
+    }
+
+    impl From<Product> for PublicProductView {
+        fn from(product: Product) -> Self {
+            let availability = if product.quantity.is_empty() {
+                Availability::SoldOut
+            } else {
+                Availability::Available
+            };
+
+            PublicProductView {
+                column_id: product.column_id,
+                name: product.name,
+                category: product.category,
+                price: product.price,
+                availability,
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CashReconciliation {
+        pub since: Option<DateTime<Utc>>,
+        pub until: DateTime<Utc>,
+        pub expected: f32,
+        pub counted: f32,
+    }
+
+    impl CashReconciliation {
+        const TOLERANCE: f32 = 0.005;
+
+        pub fn discrepancy(&self) -> f32 {
+            self.counted - self.expected
+        }
+
+        pub fn is_balanced(&self) -> bool {
+            self.discrepancy().abs() < Self::TOLERANCE
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PaymentTransaction {
+        pub transaction_id: Value,
+        pub entries: Vec<PaymentJournalEntry>,
+    }
+
+    impl PaymentTransaction {
+        pub fn group(entries: Vec<PaymentJournalEntry>) -> Vec<PaymentTransaction> {
+            let mut transactions = Vec::<PaymentTransaction>::new();
+
+            for entry in entries {
+                match transactions
+                    .iter_mut()
+                    .find(|transaction| transaction.transaction_id == entry.transaction_id)
+                {
+                    Some(transaction) => transaction.entries.push(entry),
+                    None => transactions.push(PaymentTransaction {
+                        transaction_id: entry.transaction_id.clone(),
+                        entries: vec![entry],
+                    }),
+                }
+            }
+
+            transactions
+        }
+
+        pub fn started_at(&self) -> Option<DateTime<Utc>> {
+            self.entries.first().map(|entry| entry.date)
+        }
+
+        pub fn is_open(&self) -> bool {
+            !self.entries.iter().any(|entry| entry.phase.is_final())
+        }
+
+        pub fn inserted(&self) -> f32 {
+            self.payments()
+                .iter()
+                .fold(0.0, |total, entry| total + entry.amount)
+        }
+
+        pub fn payments(&self) -> Vec<PaymentJournalEntry> {
+            self.entries
+                .iter()
+                .filter(|entry| entry.phase == PaymentPhase::Inserted)
+                .cloned()
+                .collect()
+        }
+
+        pub fn reached(&self, phase: &PaymentPhase) -> bool {
+            self.entries.iter().any(|entry| entry.phase == *phase)
+        }
+
+        pub fn recorded_balance(&self) -> Option<f32> {
+            self.entries
+                .iter()
+                .rev()
+                .find(|entry| matches!(entry.phase, PaymentPhase::Credited | PaymentPhase::Sold))
+                .map(|entry| entry.amount)
+        }
+
+        pub fn recovery(&self) -> Option<(RecoveryAction, f32)> {
+            self.entries.iter().find_map(|entry| match &entry.phase {
+                PaymentPhase::Recovered(action) => Some((action.clone(), entry.amount)),
+                _ => None,
+            })
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SupplierReport {
+        pub supplier: Name,
+        pub products: Vec<Product>,
+        pub sales: Vec<Sale>,
+    }
+}
+
+pub mod queries {
+    use super::entities::{Category, Price, Product};
+
+    #[derive(Clone, Debug, Default)]
+    pub enum SortOrder {
+        #[default]
+        Column,
+        NameAsc,
+        NameDesc,
+        PriceAsc,
+        PriceDesc,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    pub struct ProductQuery {
+        pub name: Option<String>,
+        pub category: Option<Category>,
+        pub min_price: Option<Price>,
+        pub max_price: Option<Price>,
+        pub in_stock_only: bool,
+        pub sort: SortOrder,
+    }
+
+    impl ProductQuery {
+        pub fn matches(&self, product: &Product) -> bool {
+            if let Some(name) = &self.name {
+                if !product
+                    .name
+                    .as_ref()
+                    .to_lowercase()
+                    .contains(&name.to_lowercase())
+                {
+                    return false;
+                }
+            }
+
+            if let Some(category) = &self.category {
+                if !product
+                    .category
+                    .as_ref()
+                    .eq_ignore_ascii_case(category.as_ref())
+                {
+                    return false;
+                }
+            }
+
+            if let Some(min_price) = &self.min_price {
+                if product.price.as_value() < min_price.as_value() {
+                    return false;
+                }
+            }
+
+            if let Some(max_price) = &self.max_price {
+                if product.price.as_value() > max_price.as_value() {
+                    return false;
+                }
+            }
+
+            !(self.in_stock_only && product.quantity.is_empty())
+        }
+
+        pub fn sort(&self, products: &mut [Product]) {
+            match self.sort {
+                SortOrder::Column => {
+                    products.sort_by_key(|product| product.column_id.as_value());
+                }
+                SortOrder::NameAsc => {
+                    products.sort_by_key(|product| product.name.as_ref().to_lowercase());
+                }
+                SortOrder::NameDesc => {
+                    products.sort_by_key(|product| product.name.as_ref().to_lowercase());
+                    products.reverse();
+                }
+                SortOrder::PriceAsc => {
+                    products.sort_by(|a, b| a.price.as_value().total_cmp(&b.price.as_value()));
+                }
+                SortOrder::PriceDesc => {
+                    products.sort_by(|a, b| b.price.as_value().total_cmp(&a.price.as_value()));
+                }
+            }
+        }
+    }
+}
+
+pub mod interfaces {
+    use super::entities::{
+        CardAuthorization, CardNumber, CashCollection, CustomerAccount, Fault, MachineEvent,
+        MachineState, Name, PaymentJournalEntry, PaymentMethod, Price, Product, Receipt, Sale,
+        Schedule, SchemaVersion, SensorKind, SensorReading, StockMovement, SupplierAccount, Value,
+        VendOutcome, WriteOffFlag, ZReport,
+    };
+    use super::queries::ProductQuery;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use dyn_clone::{clone_trait_object, DynClone};
+
+    clone_trait_object!(ProductRepository);
+    clone_trait_object!(SaleRepository);
+    clone_trait_object!(MachineStateRepository);
+    clone_trait_object!(MachineHistoryRepository);
+    clone_trait_object!(ScheduleRepository);
+    clone_trait_object!(SupplierRepository);
+    clone_trait_object!(CashCollectionRepository);
+    clone_trait_object!(StockMovementRepository);
+    clone_trait_object!(ZReportRepository);
+    clone_trait_object!(CustomerAccountRepository);
+    clone_trait_object!(CreditRepository);
+    clone_trait_object!(PaymentJournalRepository);
+    clone_trait_object!(FaultLogRepository);
+    clone_trait_object!(PaymentTerminal);
+    clone_trait_object!(CardProcessor);
+    clone_trait_object!(Dispenser);
+    clone_trait_object!(StorageDiagnostics);
+    clone_trait_object!(Sensor);
+    clone_trait_object!(SensorReadingRepository);
+    clone_trait_object!(WriteOffRepository);
+    clone_trait_object!(ReceiptPrinter);
+
+    #[async_trait]
+    pub trait ProductRepository: Send + Sync + DynClone {
+        async fn find(&self, column_id: Value) -> Option<Product>;
+        async fn save(&mut self, product: Product) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<Product>;
+        async fn search(&self, query: &ProductQuery) -> Vec<Product>;
+    }
+
+    #[async_trait]
+    pub trait SaleRepository: Send + Sync + DynClone {
+        async fn find(&self, id: Value) -> Option<Sale>;
+        async fn save(&mut self, sale: Sale) -> Result<Sale, Box<dyn std::error::Error>>;
+        async fn record(
+            &mut self,
+            sale: Sale,
+            movement: StockMovement,
+        ) -> Result<Sale, Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<Sale>;
+    }
+
+    #[async_trait]
+    pub trait MachineStateRepository: Send + Sync + DynClone {
+        async fn load(&self) -> Result<Option<MachineState>, Box<dyn std::error::Error>>;
+        async fn save(&mut self, state: MachineState) -> Result<(), Box<dyn std::error::Error>>;
+    }
+
+    #[async_trait]
+    pub trait MachineHistoryRepository: Send + Sync + DynClone {
+        async fn save(&mut self, event: MachineEvent) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<MachineEvent>;
+    }
+
+    #[async_trait]
+    pub trait ScheduleRepository: Send + Sync + DynClone {
+        async fn load(&self) -> Schedule;
+        async fn save(&mut self, schedule: Schedule) -> Result<(), Box<dyn std::error::Error>>;
+    }
+
+    #[async_trait]
+    pub trait SupplierRepository: Send + Sync + DynClone {
+        async fn find(&self, name: &Name) -> Option<SupplierAccount>;
+        async fn save(
+            &mut self,
+            account: SupplierAccount,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<SupplierAccount>;
+    }
+
+    #[async_trait]
+    pub trait CashCollectionRepository: Send + Sync + DynClone {
+        async fn save(
+            &mut self,
+            collection: CashCollection,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<CashCollection>;
+    }
+
+    #[async_trait]
+    pub trait StockMovementRepository: Send + Sync + DynClone {
+        async fn save(&mut self, movement: StockMovement)
+            -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<StockMovement>;
+    }
+
+    #[async_trait]
+    pub trait ZReportRepository: Send + Sync + DynClone {
+        async fn save(&mut self, report: ZReport) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<ZReport>;
+    }
+
+    #[async_trait]
+    pub trait CustomerAccountRepository: Send + Sync + DynClone {
+        async fn find(&self, badge_id: &Name) -> Option<CustomerAccount>;
+        async fn save(
+            &mut self,
+            account: CustomerAccount,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<CustomerAccount>;
+    }
+
+    #[async_trait]
+    pub trait CreditRepository: Send + Sync + DynClone {
+        async fn load(&self, transaction_id: &Value) -> f32;
+        async fn save(
+            &mut self,
+            transaction_id: &Value,
+            credit: f32,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+        async fn carry_over(
+            &mut self,
+            from: &Value,
+            to: &Value,
+            credit: f32,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<(Value, f32)>;
+    }
+
+    #[async_trait]
+    pub trait PaymentJournalRepository: Send + Sync + DynClone {
+        async fn next_transaction_id(&self) -> Result<Value, Box<dyn std::error::Error>>;
+        async fn save(
+            &mut self,
+            entry: PaymentJournalEntry,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<PaymentJournalEntry>;
+    }
+
+    pub trait Terminal: Send + Sync {
+        fn prompt(&self, message: &str) {
+            println!("{}", message);
+        }
+    }
+
+    #[async_trait]
+    pub trait PaymentTerminal: Terminal + DynClone {
+        fn method(&self) -> PaymentMethod;
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn request(&self, due: Price) -> Result<Price, Box<dyn std::error::Error>>;
+        async fn refund(&self, amount: Price) -> Result<(), Box<dyn std::error::Error>>;
+
+        fn reference(&self) -> Option<String> {
+            None
+        }
+
+        async fn reverse(
+            &self,
+            amount: Price,
+            _reference: Option<&str>,
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            self.refund(amount).await
+        }
+
+        async fn confirm(&self) -> Result<(), Box<dyn std::error::Error>> {
+            Ok(())
+        }
+
+        async fn cancel(&self) -> Result<Option<Price>, Box<dyn std::error::Error>> {
+            Ok(None)
+        }
+
+        async fn release(
+            &self,
+            _amount: Price,
+        ) -> Result<Option<Price>, Box<dyn std::error::Error>> {
+            Ok(None)
+        }
+
+        async fn self_test(&self) -> Result<(), Box<dyn std::error::Error>> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    pub trait CardProcessor: Send + Sync + DynClone {
+        async fn is_online(&self) -> bool {
+            true
+        }
+
+        async fn authorize(
+            &self,
+            card: &CardNumber,
+            amount: Price,
+        ) -> Result<CardAuthorization, Box<dyn std::error::Error>>;
+        async fn capture(
+            &self,
+            authorization: &CardAuthorization,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+        async fn void(
+            &self,
+            authorization: &CardAuthorization,
+        ) -> Result<(), Box<dyn std::error::Error>>;
+    }
+
+    #[async_trait]
+    pub trait SensorReadingRepository: Send + Sync + DynClone {
+        async fn save(&mut self, reading: SensorReading) -> Result<(), Box<dyn std::error::Error>>;
+        async fn latest(&self, kind: &SensorKind) -> Option<SensorReading>;
+        async fn excursion_started_at(&self, kind: &SensorKind) -> Option<DateTime<Utc>>;
+        async fn find_all(&self) -> Vec<SensorReading>;
+    }
+
+    #[async_trait]
+    pub trait WriteOffRepository: Send + Sync + DynClone {
+        async fn find(&self, column_id: &Value) -> Option<WriteOffFlag>;
+        async fn save(&mut self, flag: WriteOffFlag) -> Result<(), Box<dyn std::error::Error>>;
+        async fn remove(&mut self, column_id: &Value) -> Result<(), Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<WriteOffFlag>;
+    }
+
+    #[async_trait]
+    pub trait FaultLogRepository: Send + Sync + DynClone {
+        async fn find(&self, id: &Value) -> Option<Fault>;
+        async fn save(&mut self, fault: Fault) -> Result<Fault, Box<dyn std::error::Error>>;
+        async fn find_all(&self) -> Vec<Fault>;
+    }
+
+    #[async_trait]
+    pub trait Sensor: Send + Sync + DynClone {
+        fn kind(&self) -> SensorKind;
+        async fn read(&self) -> Result<f32, Box<dyn std::error::Error>>;
+    }
+
+    #[async_trait]
+    pub trait Dispenser: Send + Sync + DynClone {
+        async fn vend(
+            &self,
+            column_id: &Value,
+            quantity: &Value,
+        ) -> Result<VendOutcome, Box<dyn std::error::Error>>;
+        async fn self_test(&self, column_id: &Value) -> Result<(), Box<dyn std::error::Error>>;
+    }
+
+    #[async_trait]
+    pub trait StorageDiagnostics: Send + Sync + DynClone {
+        async fn ping(&self) -> Result<(), Box<dyn std::error::Error>>;
+        async fn schema_version(&self) -> Result<SchemaVersion, Box<dyn std::error::Error>>;
+        async fn negative_stock_columns(&self) -> Result<Vec<Value>, Box<dyn std::error::Error>>;
+        async fn orphaned_sales(&self) -> Result<Vec<Value>, Box<dyn std::error::Error>>;
+    }
+
+    pub trait ReceiptPrinter: Send + Sync + DynClone {
+        fn print(&self, receipt: &Receipt) -> Result<(), Box<dyn std::error::Error>>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::entities::{Schedule, ScheduleException, ScheduleWindow};
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn weekday_schedule() -> Schedule {
+        Schedule {
+            windows: vec![
+                ScheduleWindow::parse("Mon", "08:00", "18:00").unwrap(),
+                ScheduleWindow::parse("Tue", "08:00", "12:00").unwrap(),
+            ],
+            exceptions: vec![],
+        }
+    }
+
+    #[test]
+    fn is_open_within_a_window() {
+        let schedule = weekday_schedule();
+
+        // 2026-10-19 is a Monday.
+        assert!(schedule.is_open_at(at("2026-10-19", "08:00")));
+        assert!(schedule.is_open_at(at("2026-10-19", "17:59")));
+        assert!(schedule.is_open_at(at("2026-10-20", "11:30")));
+    }
+
+    #[test]
+    fn is_closed_outside_windows() {
+        let schedule = weekday_schedule();
+
+        assert!(!schedule.is_open_at(at("2026-10-19", "07:59")));
+        assert!(!schedule.is_open_at(at("2026-10-19", "18:00")));
+        assert!(!schedule.is_open_at(at("2026-10-20", "12:00")));
+        assert!(!schedule.is_open_at(at("2026-10-21", "10:00")));
+    }
+
+    #[test]
+    fn is_closed_on_exception_dates() {
+        let schedule = Schedule {
+            exceptions: vec![ScheduleException::parse("2026-10-19", "Public holiday").unwrap()],
+            ..weekday_schedule()
+        };
+
+        assert!(!schedule.is_open_at(at("2026-10-19", "10:00")));
+        assert!(schedule.is_open_at(at("2026-10-26", "10:00")));
+    }
+
+    #[test]
+    fn empty_schedule_is_never_open() {
+        let schedule = Schedule::default();
+
+        assert!(!schedule.is_enabled());
+        assert!(!schedule.is_open_at(at("2026-10-19", "10:00")));
     }
 }